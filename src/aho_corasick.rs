@@ -0,0 +1,231 @@
+//! Multi pattern search and replace over chars with an Aho-Corasick automaton.
+//!
+//! All the patterns are searched at the same time, in one pass over the
+//! text, so the result of a replace doesn't depend on the order of the
+//! patterns and the text produced by a replacement is never searched again.
+//! When two patterns match at the same start position, the `MatchKind`
+//! chooses the longest one or the first one in the order of the patterns.
+
+use std::collections::HashMap;
+
+use crate::str_utils::StringUtils;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatchKind {
+    /// The leftmost match, and from those the longest one.
+    #[default]
+    LeftmostLongest,
+    /// The leftmost match, and from those the one of the first pattern.
+    LeftmostFirst,
+}
+
+/// A match of the pattern with index `pattern` at `start..end` .
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharMatch {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    trans: HashMap<char, usize>,
+    fail: usize,
+    // Pattern that ends in this state, the first one if duplicated.
+    pattern: Option<usize>,
+    // Next state in the chain of failures that has a pattern.
+    dict_link: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CharAhoCorasick {
+    states: Vec<State>,
+    patterns_len: Vec<usize>,
+    max_pattern_len: usize,
+    match_kind: MatchKind,
+}
+
+impl CharAhoCorasick {
+    /// Empty patterns never match.
+    pub fn new(patterns: &[&[char]], match_kind: MatchKind) -> CharAhoCorasick {
+        let mut states: Vec<State> = vec![State::default()];
+
+        // Build the trie of the patterns.
+        for (pattern_index, pattern) in patterns.iter().enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0_usize;
+            for c in pattern.iter() {
+                state = match states[state].trans.get(c) {
+                    Some(&next_state) => next_state,
+                    None => {
+                        states.push(State::default());
+                        let next_state = states.len() - 1;
+                        states[state].trans.insert(*c, next_state);
+                        next_state
+                    },
+                };
+            }
+            if states[state].pattern.is_none() {
+                states[state].pattern = Some(pattern_index);
+            }
+        }
+
+        // Compute the failure and the dictionary links, in breadth first order.
+        // The children of the root fail to the root.
+        let mut queue: Vec<usize> = states[0].trans.values().copied().collect();
+        let mut queue_index = 0_usize;
+        while queue_index < queue.len() {
+            let state = queue[queue_index];
+            queue_index += 1;
+            let trans: Vec<(char, usize)> = states[state].trans.iter().map(|(c, s)| (*c, *s)).collect();
+            for (c, next_state) in trans {
+                let mut fail = states[state].fail;
+                let fail_next = loop {
+                    if let Some(&s) = states[fail].trans.get(&c) {
+                        break s;
+                    }
+                    if fail == 0 {
+                        break 0;
+                    }
+                    fail = states[fail].fail;
+                };
+                states[next_state].fail = fail_next;
+                states[next_state].dict_link = if states[fail_next].pattern.is_some() {
+                    Some(fail_next)
+                } else {
+                    states[fail_next].dict_link
+                };
+                queue.push(next_state);
+            }
+        }
+
+        CharAhoCorasick {
+            states,
+            patterns_len: patterns.iter().map(|p| p.len()).collect(),
+            max_pattern_len: patterns.iter().map(|p| p.len()).max().unwrap_or(0),
+            match_kind,
+        }
+    }
+
+    /// The patterns are normalized like in `StringUtils::get_vec_chars()`.
+    pub fn new_str(patterns: &[&str], match_kind: MatchKind) -> CharAhoCorasick {
+        let patterns_vec: Vec<Vec<char>> = patterns.iter().map(|p| p.get_vec_chars()).collect();
+        let patterns_slices: Vec<&[char]> = patterns_vec.iter().map(|p| &p[..]).collect();
+        CharAhoCorasick::new(&patterns_slices, match_kind)
+    }
+
+    pub fn patterns_len(&self) -> usize {
+        self.patterns_len.len()
+    }
+
+    pub fn match_kind(&self) -> MatchKind {
+        self.match_kind
+    }
+
+    #[inline]
+    fn next_state(&self, mut state: usize, c: char) -> usize {
+        loop {
+            if let Some(&next_state) = self.states[state].trans.get(&c) {
+                return next_state;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail;
+        }
+    }
+
+    // The candidate is better than best, by the match kind.
+    fn is_better(&self, candidate: &CharMatch, best: &CharMatch) -> bool {
+        if candidate.start != best.start {
+            return candidate.start < best.start;
+        }
+        match self.match_kind {
+            MatchKind::LeftmostLongest => candidate.end > best.end,
+            MatchKind::LeftmostFirst => candidate.pattern < best.pattern,
+        }
+    }
+
+    /// Returns a None or the first match at or after start_pos.
+    pub fn find_at(&self, haystack: &[char], start_pos: usize) -> Option<CharMatch> {
+        let mut best: Option<CharMatch> = None;
+        let mut state = 0_usize;
+        let mut pos = start_pos;
+        while pos < haystack.len() {
+            // No match that ends after pos can start before the best one.
+            if let Some(best_match) = best {
+                if pos >= best_match.start + self.max_pattern_len {
+                    break;
+                }
+            }
+            state = self.next_state(state, haystack[pos]);
+            pos += 1;
+
+            let mut out_state = if self.states[state].pattern.is_some() {
+                Some(state)
+            } else {
+                self.states[state].dict_link
+            };
+            while let Some(s) = out_state {
+                if let Some(pattern) = self.states[s].pattern {
+                    let candidate = CharMatch {
+                        pattern,
+                        start: pos - self.patterns_len[pattern],
+                        end: pos,
+                    };
+                    if best.is_none_or(|best_match| self.is_better(&candidate, &best_match)) {
+                        best = Some(candidate);
+                    }
+                }
+                out_state = self.states[s].dict_link;
+            }
+        }
+        best
+    }
+
+    pub fn find(&self, haystack: &[char]) -> Option<CharMatch> {
+        self.find_at(haystack, 0)
+    }
+
+    /// Iterator over the non overlapping matches, from left to right.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [char]) -> CharAhoFindIter<'a> {
+        CharAhoFindIter { automaton: self, haystack, next_start_pos: 0 }
+    }
+
+    /// Replaces every match of the pattern i with replacements[i], in one
+    /// pass. Returns the new chars and the number of replaces of each
+    /// pattern.
+    pub fn replace_all(&self, haystack: &[char], replacements: &[&[char]]) -> (Vec<char>, Vec<usize>) {
+        assert_eq!(replacements.len(), self.patterns_len(),
+            "Error: In replace_all() there must be one replacement for each pattern.");
+        let mut counts = vec![0_usize; self.patterns_len()];
+        let mut target_vec_chars: Vec<char> = Vec::with_capacity(haystack.len());
+        let mut last_index = 0_usize;
+        for char_match in self.find_iter(haystack) {
+            target_vec_chars.extend_from_slice(&haystack[last_index..char_match.start]);
+            target_vec_chars.extend_from_slice(replacements[char_match.pattern]);
+            counts[char_match.pattern] += 1;
+            last_index = char_match.end;
+        }
+        target_vec_chars.extend_from_slice(&haystack[last_index..]);
+        (target_vec_chars, counts)
+    }
+}
+
+pub struct CharAhoFindIter<'a> {
+    automaton: &'a CharAhoCorasick,
+    haystack: &'a [char],
+    next_start_pos: usize,
+}
+
+impl Iterator for CharAhoFindIter<'_> {
+    type Item = CharMatch;
+
+    fn next(&mut self) -> Option<CharMatch> {
+        let char_match = self.automaton.find_at(self.haystack, self.next_start_pos)?;
+        self.next_start_pos = char_match.end;
+        Some(char_match)
+    }
+}
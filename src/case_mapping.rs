@@ -0,0 +1,751 @@
+//! Case conversion of chars with the full mappings of Unicode.
+//!
+//! The Std maps a single char to lowercase and to uppercase, with the
+//! mappings that change the len like 'ß' to "SS", but it has no titlecase
+//! nor case folding, it applies the final sigma of Greek only on a whole
+//! `str` and it has no tailoring for a language. The `StringUtilsCase` trait
+//! adds them for `[char]`, and so for `Vec<char>`, with the context rules of
+//! the SpecialCasing of Unicode and the tailoring of `CaseLocale`.
+//!
+//! The mappings and the Lowercase and Uppercase properties are from tables
+//! of the Unicode Character Database 14.0.0, like the `GeneralCategory`,
+//! and not from the Std, whose version of Unicode changes with the Rust
+//! version. So the results don't change with the toolchain and are the ones
+//! of CPython 3.11 .
+
+use unic_ucd_normal::CanonicalCombiningClass;
+
+use crate::general_category::GeneralCategory;
+
+const CAPITAL_SIGMA: char = '\u{3A3}';
+const SMALL_SIGMA: char = '\u{3C3}';
+const SMALL_FINAL_SIGMA: char = '\u{3C2}';
+const COMBINING_DOT_ABOVE: char = '\u{307}';
+
+/// The language tailoring of the case mappings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CaseLocale {
+    /// The mappings of Unicode without tailoring.
+    #[default]
+    Root,
+    /// Turkish and Azeri, with the dotted 'İ' and 'i' and the dotless 'I'
+    /// and 'ı'.
+    Turkic,
+    /// Lithuanian, that keeps the dot of an 'i' with an accent above in
+    /// lowercase and removes it in uppercase.
+    Lithuanian,
+}
+
+pub trait StringUtilsCase {
+    /// The full uppercase, 'ß' is "SS" and 'ﬁ' is "FI".
+    fn to_uppercase(&self) -> Vec<char>;
+    fn to_uppercase_with(&self, locale: CaseLocale) -> Vec<char>;
+    /// The full lowercase, with the final sigma 'ς' at the end of a word.
+    fn to_lowercase(&self) -> Vec<char>;
+    fn to_lowercase_with(&self, locale: CaseLocale) -> Vec<char>;
+    /// The first cased char of each word in titlecase and the others in
+    /// lowercase. A word is a run of cased chars, with the case ignorable
+    /// chars like the apostrophe inside, so "they're" is "They're".
+    fn to_titlecase(&self) -> Vec<char>;
+    fn to_titlecase_with(&self, locale: CaseLocale) -> Vec<char>;
+    /// The full case folding, for caseless comparisons, 'ß' is "ss".
+    fn to_casefold(&self) -> Vec<char>;
+    fn to_casefold_with(&self, locale: CaseLocale) -> Vec<char>;
+
+    // The in place variants, for the mappings that don't change the len.
+    // They return false, and the chars are unchanged, if the mapping of any
+    // char has a different len, like 'ß' in uppercase.
+
+    fn make_uppercase(&mut self, locale: CaseLocale) -> bool;
+    fn make_lowercase(&mut self, locale: CaseLocale) -> bool;
+    fn make_titlecase(&mut self, locale: CaseLocale) -> bool;
+    fn make_casefold(&mut self, locale: CaseLocale) -> bool;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CaseKind {
+    Upper,
+    Lower,
+    Title,
+    Fold,
+}
+
+impl StringUtilsCase for [char] {
+    fn to_uppercase(&self) -> Vec<char> {
+        self.to_uppercase_with(CaseLocale::Root)
+    }
+
+    fn to_uppercase_with(&self, locale: CaseLocale) -> Vec<char> {
+        convert(self, CaseKind::Upper, locale)
+    }
+
+    fn to_lowercase(&self) -> Vec<char> {
+        self.to_lowercase_with(CaseLocale::Root)
+    }
+
+    fn to_lowercase_with(&self, locale: CaseLocale) -> Vec<char> {
+        convert(self, CaseKind::Lower, locale)
+    }
+
+    fn to_titlecase(&self) -> Vec<char> {
+        self.to_titlecase_with(CaseLocale::Root)
+    }
+
+    fn to_titlecase_with(&self, locale: CaseLocale) -> Vec<char> {
+        convert(self, CaseKind::Title, locale)
+    }
+
+    fn to_casefold(&self) -> Vec<char> {
+        self.to_casefold_with(CaseLocale::Root)
+    }
+
+    fn to_casefold_with(&self, locale: CaseLocale) -> Vec<char> {
+        convert(self, CaseKind::Fold, locale)
+    }
+
+    fn make_uppercase(&mut self, locale: CaseLocale) -> bool {
+        convert_in_place(self, CaseKind::Upper, locale)
+    }
+
+    fn make_lowercase(&mut self, locale: CaseLocale) -> bool {
+        convert_in_place(self, CaseKind::Lower, locale)
+    }
+
+    fn make_titlecase(&mut self, locale: CaseLocale) -> bool {
+        convert_in_place(self, CaseKind::Title, locale)
+    }
+
+    fn make_casefold(&mut self, locale: CaseLocale) -> bool {
+        convert_in_place(self, CaseKind::Fold, locale)
+    }
+}
+
+// The conversion is done from left to right, the context before each char
+// is kept in the state of the `CaseMapper`, from the chars before they are
+// converted, and the context after is read from the chars not converted
+// yet, so the same code converts into a new `Vec<char>` and in place.
+
+fn convert(chars: &[char], kind: CaseKind, locale: CaseLocale) -> Vec<char> {
+    let mut res_vec_chars = Vec::with_capacity(chars.len());
+    let mut mapper = CaseMapper::new(locale);
+    for index in 0..chars.len() {
+        mapper.push(chars, index, kind, &mut res_vec_chars);
+    }
+    res_vec_chars
+}
+
+fn convert_in_place(chars: &mut [char], kind: CaseKind, locale: CaseLocale) -> bool {
+    let mut mapper = CaseMapper::new(locale);
+    for index in 0..chars.len() {
+        let mut sink = OneCharSink::default();
+        mapper.push(chars, index, kind, &mut sink);
+        if sink.len != 1 {
+            return false;
+        }
+    }
+    let mut mapper = CaseMapper::new(locale);
+    for index in 0..chars.len() {
+        let mut sink = OneCharSink::default();
+        mapper.push(chars, index, kind, &mut sink);
+        chars[index] = sink.last;
+    }
+    true
+}
+
+// Counts the chars of a mapping and keeps the last one.
+#[derive(Default)]
+struct OneCharSink {
+    len: usize,
+    last: char,
+}
+
+impl Extend<char> for OneCharSink {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        for c in iter {
+            self.len += 1;
+            self.last = c;
+        }
+    }
+}
+
+/// Maps the chars one by one from left to right, with the context of the
+/// chars before in its state.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct CaseMapper {
+    locale: CaseLocale,
+    // The last char before that is not case ignorable is cased.
+    cased_before: bool,
+    // In a word for the titlecase, after a cased char.
+    in_word: bool,
+    // After an 'I', with no char of combining class 0 or 230 after it.
+    after_i: bool,
+    // After a soft dotted char like 'i', with no char of combining class 0
+    // or 230 after it.
+    after_soft_dotted: bool,
+}
+
+impl CaseMapper {
+    pub(crate) fn new(locale: CaseLocale) -> CaseMapper {
+        CaseMapper { locale, ..CaseMapper::default() }
+    }
+
+    fn push(& mut self, chars: &[char], index: usize, kind: CaseKind, out: &mut impl Extend<char>) {
+        match kind {
+            CaseKind::Upper => self.push_uppercase(chars, index, out),
+            CaseKind::Lower => self.push_lowercase(chars, index, out),
+            CaseKind::Title if self.in_word => self.push_lowercase(chars, index, out),
+            CaseKind::Title => self.push_titlecase(chars, index, out),
+            CaseKind::Fold => self.push_casefold(chars, index, out),
+        }
+    }
+
+    // Moves the context after the char c.
+    fn advance(& mut self, c: char) {
+        let cased = is_cased(c);
+        let ignorable = is_case_ignorable(c);
+        if !ignorable {
+            self.cased_before = cased;
+        }
+        if cased {
+            self.in_word = true;
+        } else if !ignorable {
+            self.in_word = false;
+        }
+        let class = CanonicalCombiningClass::of(c).number();
+        let blocks_dot = class == 0 || class == 230;
+        self.after_i = c == 'I' || (self.after_i && !blocks_dot);
+        self.after_soft_dotted = is_soft_dotted(c) || (self.after_soft_dotted && !blocks_dot);
+    }
+
+    /// Pushes the chars unchanged, only to move the context.
+    pub(crate) fn push_unchanged(& mut self, chars: &[char], index: usize, out: &mut impl Extend<char>) {
+        out.extend([chars[index]]);
+        self.advance(chars[index]);
+    }
+
+    /// Pushes the lowercase of the char at index.
+    pub(crate) fn push_lowercase(& mut self, chars: &[char], index: usize, out: &mut impl Extend<char>) {
+        let c = chars[index];
+        match (self.locale, c) {
+            (_, CAPITAL_SIGMA) => {
+                let final_sigma = self.cased_before && !is_cased_after(chars, index);
+                out.extend([if final_sigma { SMALL_FINAL_SIGMA } else { SMALL_SIGMA }]);
+            }
+            (CaseLocale::Turkic, '\u{130}') => out.extend(['i']),
+            (CaseLocale::Turkic, COMBINING_DOT_ABOVE) if self.after_i => (),
+            (CaseLocale::Turkic, 'I') if !is_before_dot(chars, index) => out.extend(['\u{131}']),
+            (CaseLocale::Lithuanian, 'I' | 'J' | '\u{12E}') if is_more_above(chars, index) => {
+                push_lowercase_of(c, out);
+                out.extend([COMBINING_DOT_ABOVE]);
+            }
+            (CaseLocale::Lithuanian, '\u{CC}') => out.extend(['i', COMBINING_DOT_ABOVE, '\u{300}']),
+            (CaseLocale::Lithuanian, '\u{CD}') => out.extend(['i', COMBINING_DOT_ABOVE, '\u{301}']),
+            (CaseLocale::Lithuanian, '\u{128}') => out.extend(['i', COMBINING_DOT_ABOVE, '\u{303}']),
+            _ => push_lowercase_of(c, out),
+        }
+        self.advance(c);
+    }
+
+    /// Pushes the uppercase of the char at index.
+    pub(crate) fn push_uppercase(& mut self, chars: &[char], index: usize, out: &mut impl Extend<char>) {
+        let c = chars[index];
+        match (self.locale, c) {
+            (CaseLocale::Turkic, 'i') => out.extend(['\u{130}']),
+            (CaseLocale::Lithuanian, COMBINING_DOT_ABOVE) if self.after_soft_dotted => (),
+            _ => push_uppercase_of(c, out),
+        }
+        self.advance(c);
+    }
+
+    /// Pushes the titlecase of the char at index, the uppercase for most
+    /// chars.
+    pub(crate) fn push_titlecase(& mut self, chars: &[char], index: usize, out: &mut impl Extend<char>) {
+        let c = chars[index];
+        match (self.locale, c) {
+            (CaseLocale::Turkic, 'i') => out.extend(['\u{130}']),
+            (CaseLocale::Lithuanian, COMBINING_DOT_ABOVE) if self.after_soft_dotted => (),
+            _ => match TITLECASE_TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+                Ok(table_index) => out.extend(TITLECASE_TABLE[table_index].1.chars()),
+                Err(_) => push_uppercase_of(c, out),
+            },
+        }
+        self.advance(c);
+    }
+
+    /// Pushes the full case folding of the char at index, the lowercase for
+    /// most chars.
+    pub(crate) fn push_casefold(& mut self, chars: &[char], index: usize, out: &mut impl Extend<char>) {
+        let c = chars[index];
+        match (self.locale, c) {
+            (CaseLocale::Turkic, 'I') => out.extend(['\u{131}']),
+            (CaseLocale::Turkic, '\u{130}') => out.extend(['i']),
+            _ => match CASEFOLD_TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+                Ok(table_index) => out.extend(CASEFOLD_TABLE[table_index].1.chars()),
+                Err(_) => push_lowercase_of(c, out),
+            },
+        }
+        self.advance(c);
+    }
+}
+
+/// The simple case folding of c, one char to one char, 'ẞ' is 'ß' and 'İ'
+/// has no simple folding, so it's kept.
+pub(crate) fn simple_casefold(c: char) -> char {
+    if let Ok(table_index) = CASEFOLD_TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+        let mut folded = CASEFOLD_TABLE[table_index].1.chars();
+        if let (Some(folded_c), None) = (folded.next(), folded.next()) {
+            return folded_c;
+        }
+    }
+    // The full folding is the lowercase or has many chars, the simple
+    // folding is the lowercase when it's one char.
+    if LOWERCASE_SPECIAL_TABLE.binary_search_by_key(&c, |&(key, _)| key).is_ok() {
+        c
+    } else {
+        map_by_runs(c, LOWERCASE_TABLE)
+    }
+}
+
+/// A char with case, uppercase, lowercase or titlecase.
+pub(crate) fn is_cased(c: char) -> bool {
+    is_lowercase(c) || is_uppercase(c) || GeneralCategory::of(c) == GeneralCategory::Lt
+}
+
+/// The Lowercase property, the Ll chars and some others like 'ª'.
+pub(crate) fn is_lowercase(c: char) -> bool {
+    GeneralCategory::of(c) == GeneralCategory::Ll || in_ranges(c, OTHER_LOWERCASE_RANGES)
+}
+
+/// The Uppercase property, the Lu chars and some others like 'Ⅻ'.
+pub(crate) fn is_uppercase(c: char) -> bool {
+    GeneralCategory::of(c) == GeneralCategory::Lu || in_ranges(c, OTHER_UPPERCASE_RANGES)
+}
+
+/// c is in one of the sorted ranges, first and last inclusive.
+pub(crate) fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let code = c as u32;
+    let index = ranges.partition_point(|&(_, last)| last < code);
+    ranges.get(index).is_some_and(|&(first, _)| first <= code)
+}
+
+// The full lowercase of c, without the context rules.
+fn push_lowercase_of(c: char, out: &mut impl Extend<char>) {
+    match LOWERCASE_SPECIAL_TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+        Ok(table_index) => out.extend(LOWERCASE_SPECIAL_TABLE[table_index].1.chars()),
+        Err(_) => out.extend([map_by_runs(c, LOWERCASE_TABLE)]),
+    }
+}
+
+// The full uppercase of c, without the context rules.
+fn push_uppercase_of(c: char, out: &mut impl Extend<char>) {
+    match UPPERCASE_SPECIAL_TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+        Ok(table_index) => out.extend(UPPERCASE_SPECIAL_TABLE[table_index].1.chars()),
+        Err(_) => out.extend([map_by_runs(c, UPPERCASE_TABLE)]),
+    }
+}
+
+// The char mapped by a table of runs, c itself when it's in no run.
+fn map_by_runs(c: char, table: &[(u32, u32, i32, u32)]) -> char {
+    let code = c as u32;
+    let index = table.partition_point(|&(_, last, _, _)| last < code);
+    match table.get(index) {
+        Some(&(first, _, delta, step)) if first <= code && (code - first).is_multiple_of(step) => {
+            char::from_u32(code.wrapping_add_signed(delta)).unwrap_or(c)
+        }
+        _ => c,
+    }
+}
+
+/// A char that is skipped when looking for the cased chars around a
+/// sigma, like the marks and the apostrophe.
+pub(crate) fn is_case_ignorable(c: char) -> bool {
+    use GeneralCategory::*;
+    // The chars of Word_Break MidLetter, MidNumLet and Single_Quote.
+    const MID_WORD: [char; 17] = ['\'', '.', ':', '\u{B7}', '\u{387}', '\u{55F}', '\u{5F4}', '\u{2018}', '\u{2019}',
+                                  '\u{2024}', '\u{2027}', '\u{FE13}', '\u{FE52}', '\u{FE55}', '\u{FF07}', '\u{FF0E}', '\u{FF1A}'];
+    matches!(GeneralCategory::of(c), Mn | Me | Cf | Lm | Sk) || MID_WORD.contains(&c)
+}
+
+// There is a cased char after index, skipping the case ignorable chars.
+fn is_cased_after(chars: &[char], index: usize) -> bool {
+    chars[index + 1..].iter().find(|&&c| !is_case_ignorable(c)).is_some_and(|&c| is_cased(c))
+}
+
+// The first char after index, of combining class 0 or 230, is the
+// combining dot above.
+fn is_before_dot(chars: &[char], index: usize) -> bool {
+    for &c in &chars[index + 1..] {
+        if c == COMBINING_DOT_ABOVE {
+            return true;
+        }
+        let class = CanonicalCombiningClass::of(c).number();
+        if class == 0 || class == 230 {
+            return false;
+        }
+    }
+    false
+}
+
+// There is an accent above after index, before any char of combining
+// class 0.
+fn is_more_above(chars: &[char], index: usize) -> bool {
+    for &c in &chars[index + 1..] {
+        match CanonicalCombiningClass::of(c).number() {
+            230 => return true,
+            0 => return false,
+            _ => (),
+        }
+    }
+    false
+}
+
+// The chars with a dot that is removed by an accent above, like 'i' and
+// 'j'.
+fn is_soft_dotted(c: char) -> bool {
+    const SOFT_DOTTED: &[(char, char)] = &[
+        ('\u{69}', '\u{6A}'), ('\u{12F}', '\u{12F}'), ('\u{249}', '\u{249}'), ('\u{268}', '\u{268}'),
+        ('\u{29D}', '\u{29D}'), ('\u{2B2}', '\u{2B2}'), ('\u{3F3}', '\u{3F3}'), ('\u{456}', '\u{456}'),
+        ('\u{458}', '\u{458}'), ('\u{1D62}', '\u{1D62}'), ('\u{1D96}', '\u{1D96}'), ('\u{1DA4}', '\u{1DA4}'),
+        ('\u{1DA8}', '\u{1DA8}'), ('\u{1E2D}', '\u{1E2D}'), ('\u{1ECB}', '\u{1ECB}'), ('\u{2071}', '\u{2071}'),
+        ('\u{2148}', '\u{2149}'), ('\u{2C7C}', '\u{2C7C}'), ('\u{1D422}', '\u{1D423}'), ('\u{1D456}', '\u{1D457}'),
+        ('\u{1D48A}', '\u{1D48B}'), ('\u{1D4BE}', '\u{1D4BF}'), ('\u{1D4F2}', '\u{1D4F3}'), ('\u{1D526}', '\u{1D527}'),
+        ('\u{1D55A}', '\u{1D55B}'), ('\u{1D58E}', '\u{1D58F}'), ('\u{1D5C2}', '\u{1D5C3}'), ('\u{1D5F6}', '\u{1D5F7}'),
+        ('\u{1D62A}', '\u{1D62B}'), ('\u{1D65E}', '\u{1D65F}'), ('\u{1D692}', '\u{1D693}'), ('\u{1DF1A}', '\u{1DF1A}'),
+    ];
+    SOFT_DOTTED.iter().any(|&(first, last)| first <= c && c <= last)
+}
+
+// The chars whose lowercase is one other char, in runs of chars with the same
+// delta: the first char, the last char, the delta and the step of the run.
+const LOWERCASE_TABLE: &[(u32, u32, i32, u32)] = &[
+    (0x0041, 0x005A, 32, 1), (0x00C0, 0x00D6, 32, 1), (0x00D8, 0x00DE, 32, 1), (0x0100, 0x012E, 1, 2),
+    (0x0132, 0x0136, 1, 2), (0x0139, 0x0147, 1, 2), (0x014A, 0x0176, 1, 2), (0x0178, 0x0178, -121, 1),
+    (0x0179, 0x017D, 1, 2), (0x0181, 0x0181, 210, 1), (0x0182, 0x0184, 1, 2), (0x0186, 0x0186, 206, 1),
+    (0x0187, 0x0187, 1, 1), (0x0189, 0x018A, 205, 1), (0x018B, 0x018B, 1, 1), (0x018E, 0x018E, 79, 1),
+    (0x018F, 0x018F, 202, 1), (0x0190, 0x0190, 203, 1), (0x0191, 0x0191, 1, 1), (0x0193, 0x0193, 205, 1),
+    (0x0194, 0x0194, 207, 1), (0x0196, 0x0196, 211, 1), (0x0197, 0x0197, 209, 1), (0x0198, 0x0198, 1, 1),
+    (0x019C, 0x019C, 211, 1), (0x019D, 0x019D, 213, 1), (0x019F, 0x019F, 214, 1), (0x01A0, 0x01A4, 1, 2),
+    (0x01A6, 0x01A6, 218, 1), (0x01A7, 0x01A7, 1, 1), (0x01A9, 0x01A9, 218, 1), (0x01AC, 0x01AC, 1, 1),
+    (0x01AE, 0x01AE, 218, 1), (0x01AF, 0x01AF, 1, 1), (0x01B1, 0x01B2, 217, 1), (0x01B3, 0x01B5, 1, 2),
+    (0x01B7, 0x01B7, 219, 1), (0x01B8, 0x01B8, 1, 1), (0x01BC, 0x01BC, 1, 1), (0x01C4, 0x01C4, 2, 1),
+    (0x01C5, 0x01C5, 1, 1), (0x01C7, 0x01C7, 2, 1), (0x01C8, 0x01C8, 1, 1), (0x01CA, 0x01CA, 2, 1),
+    (0x01CB, 0x01DB, 1, 2), (0x01DE, 0x01EE, 1, 2), (0x01F1, 0x01F1, 2, 1), (0x01F2, 0x01F4, 1, 2),
+    (0x01F6, 0x01F6, -97, 1), (0x01F7, 0x01F7, -56, 1), (0x01F8, 0x021E, 1, 2), (0x0220, 0x0220, -130, 1),
+    (0x0222, 0x0232, 1, 2), (0x023A, 0x023A, 10795, 1), (0x023B, 0x023B, 1, 1), (0x023D, 0x023D, -163, 1),
+    (0x023E, 0x023E, 10792, 1), (0x0241, 0x0241, 1, 1), (0x0243, 0x0243, -195, 1), (0x0244, 0x0244, 69, 1),
+    (0x0245, 0x0245, 71, 1), (0x0246, 0x024E, 1, 2), (0x0370, 0x0372, 1, 2), (0x0376, 0x0376, 1, 1),
+    (0x037F, 0x037F, 116, 1), (0x0386, 0x0386, 38, 1), (0x0388, 0x038A, 37, 1), (0x038C, 0x038C, 64, 1),
+    (0x038E, 0x038F, 63, 1), (0x0391, 0x03A1, 32, 1), (0x03A3, 0x03AB, 32, 1), (0x03CF, 0x03CF, 8, 1),
+    (0x03D8, 0x03EE, 1, 2), (0x03F4, 0x03F4, -60, 1), (0x03F7, 0x03F7, 1, 1), (0x03F9, 0x03F9, -7, 1),
+    (0x03FA, 0x03FA, 1, 1), (0x03FD, 0x03FF, -130, 1), (0x0400, 0x040F, 80, 1), (0x0410, 0x042F, 32, 1),
+    (0x0460, 0x0480, 1, 2), (0x048A, 0x04BE, 1, 2), (0x04C0, 0x04C0, 15, 1), (0x04C1, 0x04CD, 1, 2),
+    (0x04D0, 0x052E, 1, 2), (0x0531, 0x0556, 48, 1), (0x10A0, 0x10C5, 7264, 1), (0x10C7, 0x10C7, 7264, 1),
+    (0x10CD, 0x10CD, 7264, 1), (0x13A0, 0x13EF, 38864, 1), (0x13F0, 0x13F5, 8, 1),
+    (0x1C90, 0x1CBA, -3008, 1), (0x1CBD, 0x1CBF, -3008, 1), (0x1E00, 0x1E94, 1, 2),
+    (0x1E9E, 0x1E9E, -7615, 1), (0x1EA0, 0x1EFE, 1, 2), (0x1F08, 0x1F0F, -8, 1), (0x1F18, 0x1F1D, -8, 1),
+    (0x1F28, 0x1F2F, -8, 1), (0x1F38, 0x1F3F, -8, 1), (0x1F48, 0x1F4D, -8, 1), (0x1F59, 0x1F5F, -8, 2),
+    (0x1F68, 0x1F6F, -8, 1), (0x1F88, 0x1F8F, -8, 1), (0x1F98, 0x1F9F, -8, 1), (0x1FA8, 0x1FAF, -8, 1),
+    (0x1FB8, 0x1FB9, -8, 1), (0x1FBA, 0x1FBB, -74, 1), (0x1FBC, 0x1FBC, -9, 1), (0x1FC8, 0x1FCB, -86, 1),
+    (0x1FCC, 0x1FCC, -9, 1), (0x1FD8, 0x1FD9, -8, 1), (0x1FDA, 0x1FDB, -100, 1), (0x1FE8, 0x1FE9, -8, 1),
+    (0x1FEA, 0x1FEB, -112, 1), (0x1FEC, 0x1FEC, -7, 1), (0x1FF8, 0x1FF9, -128, 1),
+    (0x1FFA, 0x1FFB, -126, 1), (0x1FFC, 0x1FFC, -9, 1), (0x2126, 0x2126, -7517, 1),
+    (0x212A, 0x212A, -8383, 1), (0x212B, 0x212B, -8262, 1), (0x2132, 0x2132, 28, 1),
+    (0x2160, 0x216F, 16, 1), (0x2183, 0x2183, 1, 1), (0x24B6, 0x24CF, 26, 1), (0x2C00, 0x2C2F, 48, 1),
+    (0x2C60, 0x2C60, 1, 1), (0x2C62, 0x2C62, -10743, 1), (0x2C63, 0x2C63, -3814, 1),
+    (0x2C64, 0x2C64, -10727, 1), (0x2C67, 0x2C6B, 1, 2), (0x2C6D, 0x2C6D, -10780, 1),
+    (0x2C6E, 0x2C6E, -10749, 1), (0x2C6F, 0x2C6F, -10783, 1), (0x2C70, 0x2C70, -10782, 1),
+    (0x2C72, 0x2C72, 1, 1), (0x2C75, 0x2C75, 1, 1), (0x2C7E, 0x2C7F, -10815, 1), (0x2C80, 0x2CE2, 1, 2),
+    (0x2CEB, 0x2CED, 1, 2), (0x2CF2, 0x2CF2, 1, 1), (0xA640, 0xA66C, 1, 2), (0xA680, 0xA69A, 1, 2),
+    (0xA722, 0xA72E, 1, 2), (0xA732, 0xA76E, 1, 2), (0xA779, 0xA77B, 1, 2), (0xA77D, 0xA77D, -35332, 1),
+    (0xA77E, 0xA786, 1, 2), (0xA78B, 0xA78B, 1, 1), (0xA78D, 0xA78D, -42280, 1), (0xA790, 0xA792, 1, 2),
+    (0xA796, 0xA7A8, 1, 2), (0xA7AA, 0xA7AA, -42308, 1), (0xA7AB, 0xA7AB, -42319, 1),
+    (0xA7AC, 0xA7AC, -42315, 1), (0xA7AD, 0xA7AD, -42305, 1), (0xA7AE, 0xA7AE, -42308, 1),
+    (0xA7B0, 0xA7B0, -42258, 1), (0xA7B1, 0xA7B1, -42282, 1), (0xA7B2, 0xA7B2, -42261, 1),
+    (0xA7B3, 0xA7B3, 928, 1), (0xA7B4, 0xA7C2, 1, 2), (0xA7C4, 0xA7C4, -48, 1),
+    (0xA7C5, 0xA7C5, -42307, 1), (0xA7C6, 0xA7C6, -35384, 1), (0xA7C7, 0xA7C9, 1, 2),
+    (0xA7D0, 0xA7D0, 1, 1), (0xA7D6, 0xA7D8, 1, 2), (0xA7F5, 0xA7F5, 1, 1), (0xFF21, 0xFF3A, 32, 1),
+    (0x10400, 0x10427, 40, 1), (0x104B0, 0x104D3, 40, 1), (0x10570, 0x1057A, 39, 1),
+    (0x1057C, 0x1058A, 39, 1), (0x1058C, 0x10592, 39, 1), (0x10594, 0x10595, 39, 1),
+    (0x10C80, 0x10CB2, 64, 1), (0x118A0, 0x118BF, 32, 1), (0x16E40, 0x16E5F, 32, 1),
+    (0x1E900, 0x1E921, 34, 1),
+];
+
+// The chars whose lowercase has many chars.
+const LOWERCASE_SPECIAL_TABLE: &[(char, &str)] = &[
+    ('\u{0130}', "i\u{307}"),
+];
+
+// The chars whose uppercase is one other char, in runs of chars with the same
+// delta: the first char, the last char, the delta and the step of the run.
+const UPPERCASE_TABLE: &[(u32, u32, i32, u32)] = &[
+    (0x0061, 0x007A, -32, 1), (0x00B5, 0x00B5, 743, 1), (0x00E0, 0x00F6, -32, 1), (0x00F8, 0x00FE, -32, 1),
+    (0x00FF, 0x00FF, 121, 1), (0x0101, 0x012F, -1, 2), (0x0131, 0x0131, -232, 1), (0x0133, 0x0137, -1, 2),
+    (0x013A, 0x0148, -1, 2), (0x014B, 0x0177, -1, 2), (0x017A, 0x017E, -1, 2), (0x017F, 0x017F, -300, 1),
+    (0x0180, 0x0180, 195, 1), (0x0183, 0x0185, -1, 2), (0x0188, 0x0188, -1, 1), (0x018C, 0x018C, -1, 1),
+    (0x0192, 0x0192, -1, 1), (0x0195, 0x0195, 97, 1), (0x0199, 0x0199, -1, 1), (0x019A, 0x019A, 163, 1),
+    (0x019E, 0x019E, 130, 1), (0x01A1, 0x01A5, -1, 2), (0x01A8, 0x01A8, -1, 1), (0x01AD, 0x01AD, -1, 1),
+    (0x01B0, 0x01B0, -1, 1), (0x01B4, 0x01B6, -1, 2), (0x01B9, 0x01B9, -1, 1), (0x01BD, 0x01BD, -1, 1),
+    (0x01BF, 0x01BF, 56, 1), (0x01C5, 0x01C5, -1, 1), (0x01C6, 0x01C6, -2, 1), (0x01C8, 0x01C8, -1, 1),
+    (0x01C9, 0x01C9, -2, 1), (0x01CB, 0x01CB, -1, 1), (0x01CC, 0x01CC, -2, 1), (0x01CE, 0x01DC, -1, 2),
+    (0x01DD, 0x01DD, -79, 1), (0x01DF, 0x01EF, -1, 2), (0x01F2, 0x01F2, -1, 1), (0x01F3, 0x01F3, -2, 1),
+    (0x01F5, 0x01F5, -1, 1), (0x01F9, 0x021F, -1, 2), (0x0223, 0x0233, -1, 2), (0x023C, 0x023C, -1, 1),
+    (0x023F, 0x0240, 10815, 1), (0x0242, 0x0242, -1, 1), (0x0247, 0x024F, -1, 2),
+    (0x0250, 0x0250, 10783, 1), (0x0251, 0x0251, 10780, 1), (0x0252, 0x0252, 10782, 1),
+    (0x0253, 0x0253, -210, 1), (0x0254, 0x0254, -206, 1), (0x0256, 0x0257, -205, 1),
+    (0x0259, 0x0259, -202, 1), (0x025B, 0x025B, -203, 1), (0x025C, 0x025C, 42319, 1),
+    (0x0260, 0x0260, -205, 1), (0x0261, 0x0261, 42315, 1), (0x0263, 0x0263, -207, 1),
+    (0x0265, 0x0265, 42280, 1), (0x0266, 0x0266, 42308, 1), (0x0268, 0x0268, -209, 1),
+    (0x0269, 0x0269, -211, 1), (0x026A, 0x026A, 42308, 1), (0x026B, 0x026B, 10743, 1),
+    (0x026C, 0x026C, 42305, 1), (0x026F, 0x026F, -211, 1), (0x0271, 0x0271, 10749, 1),
+    (0x0272, 0x0272, -213, 1), (0x0275, 0x0275, -214, 1), (0x027D, 0x027D, 10727, 1),
+    (0x0280, 0x0280, -218, 1), (0x0282, 0x0282, 42307, 1), (0x0283, 0x0283, -218, 1),
+    (0x0287, 0x0287, 42282, 1), (0x0288, 0x0288, -218, 1), (0x0289, 0x0289, -69, 1),
+    (0x028A, 0x028B, -217, 1), (0x028C, 0x028C, -71, 1), (0x0292, 0x0292, -219, 1),
+    (0x029D, 0x029D, 42261, 1), (0x029E, 0x029E, 42258, 1), (0x0345, 0x0345, 84, 1),
+    (0x0371, 0x0373, -1, 2), (0x0377, 0x0377, -1, 1), (0x037B, 0x037D, 130, 1), (0x03AC, 0x03AC, -38, 1),
+    (0x03AD, 0x03AF, -37, 1), (0x03B1, 0x03C1, -32, 1), (0x03C2, 0x03C2, -31, 1), (0x03C3, 0x03CB, -32, 1),
+    (0x03CC, 0x03CC, -64, 1), (0x03CD, 0x03CE, -63, 1), (0x03D0, 0x03D0, -62, 1), (0x03D1, 0x03D1, -57, 1),
+    (0x03D5, 0x03D5, -47, 1), (0x03D6, 0x03D6, -54, 1), (0x03D7, 0x03D7, -8, 1), (0x03D9, 0x03EF, -1, 2),
+    (0x03F0, 0x03F0, -86, 1), (0x03F1, 0x03F1, -80, 1), (0x03F2, 0x03F2, 7, 1), (0x03F3, 0x03F3, -116, 1),
+    (0x03F5, 0x03F5, -96, 1), (0x03F8, 0x03F8, -1, 1), (0x03FB, 0x03FB, -1, 1), (0x0430, 0x044F, -32, 1),
+    (0x0450, 0x045F, -80, 1), (0x0461, 0x0481, -1, 2), (0x048B, 0x04BF, -1, 2), (0x04C2, 0x04CE, -1, 2),
+    (0x04CF, 0x04CF, -15, 1), (0x04D1, 0x052F, -1, 2), (0x0561, 0x0586, -48, 1), (0x10D0, 0x10FA, 3008, 1),
+    (0x10FD, 0x10FF, 3008, 1), (0x13F8, 0x13FD, -8, 1), (0x1C80, 0x1C80, -6254, 1),
+    (0x1C81, 0x1C81, -6253, 1), (0x1C82, 0x1C82, -6244, 1), (0x1C83, 0x1C84, -6242, 1),
+    (0x1C85, 0x1C85, -6243, 1), (0x1C86, 0x1C86, -6236, 1), (0x1C87, 0x1C87, -6181, 1),
+    (0x1C88, 0x1C88, 35266, 1), (0x1D79, 0x1D79, 35332, 1), (0x1D7D, 0x1D7D, 3814, 1),
+    (0x1D8E, 0x1D8E, 35384, 1), (0x1E01, 0x1E95, -1, 2), (0x1E9B, 0x1E9B, -59, 1), (0x1EA1, 0x1EFF, -1, 2),
+    (0x1F00, 0x1F07, 8, 1), (0x1F10, 0x1F15, 8, 1), (0x1F20, 0x1F27, 8, 1), (0x1F30, 0x1F37, 8, 1),
+    (0x1F40, 0x1F45, 8, 1), (0x1F51, 0x1F57, 8, 2), (0x1F60, 0x1F67, 8, 1), (0x1F70, 0x1F71, 74, 1),
+    (0x1F72, 0x1F75, 86, 1), (0x1F76, 0x1F77, 100, 1), (0x1F78, 0x1F79, 128, 1), (0x1F7A, 0x1F7B, 112, 1),
+    (0x1F7C, 0x1F7D, 126, 1), (0x1FB0, 0x1FB1, 8, 1), (0x1FBE, 0x1FBE, -7205, 1), (0x1FD0, 0x1FD1, 8, 1),
+    (0x1FE0, 0x1FE1, 8, 1), (0x1FE5, 0x1FE5, 7, 1), (0x214E, 0x214E, -28, 1), (0x2170, 0x217F, -16, 1),
+    (0x2184, 0x2184, -1, 1), (0x24D0, 0x24E9, -26, 1), (0x2C30, 0x2C5F, -48, 1), (0x2C61, 0x2C61, -1, 1),
+    (0x2C65, 0x2C65, -10795, 1), (0x2C66, 0x2C66, -10792, 1), (0x2C68, 0x2C6C, -1, 2),
+    (0x2C73, 0x2C73, -1, 1), (0x2C76, 0x2C76, -1, 1), (0x2C81, 0x2CE3, -1, 2), (0x2CEC, 0x2CEE, -1, 2),
+    (0x2CF3, 0x2CF3, -1, 1), (0x2D00, 0x2D25, -7264, 1), (0x2D27, 0x2D27, -7264, 1),
+    (0x2D2D, 0x2D2D, -7264, 1), (0xA641, 0xA66D, -1, 2), (0xA681, 0xA69B, -1, 2), (0xA723, 0xA72F, -1, 2),
+    (0xA733, 0xA76F, -1, 2), (0xA77A, 0xA77C, -1, 2), (0xA77F, 0xA787, -1, 2), (0xA78C, 0xA78C, -1, 1),
+    (0xA791, 0xA793, -1, 2), (0xA794, 0xA794, 48, 1), (0xA797, 0xA7A9, -1, 2), (0xA7B5, 0xA7C3, -1, 2),
+    (0xA7C8, 0xA7CA, -1, 2), (0xA7D1, 0xA7D1, -1, 1), (0xA7D7, 0xA7D9, -1, 2), (0xA7F6, 0xA7F6, -1, 1),
+    (0xAB53, 0xAB53, -928, 1), (0xAB70, 0xABBF, -38864, 1), (0xFF41, 0xFF5A, -32, 1),
+    (0x10428, 0x1044F, -40, 1), (0x104D8, 0x104FB, -40, 1), (0x10597, 0x105A1, -39, 1),
+    (0x105A3, 0x105B1, -39, 1), (0x105B3, 0x105B9, -39, 1), (0x105BB, 0x105BC, -39, 1),
+    (0x10CC0, 0x10CF2, -64, 1), (0x118C0, 0x118DF, -32, 1), (0x16E60, 0x16E7F, -32, 1),
+    (0x1E922, 0x1E943, -34, 1),
+];
+
+// The chars whose uppercase has many chars.
+const UPPERCASE_SPECIAL_TABLE: &[(char, &str)] = &[
+    ('\u{00DF}', "SS"), ('\u{0149}', "\u{2BC}N"), ('\u{01F0}', "J\u{30C}"),
+    ('\u{0390}', "\u{399}\u{308}\u{301}"), ('\u{03B0}', "\u{3A5}\u{308}\u{301}"),
+    ('\u{0587}', "\u{535}\u{552}"), ('\u{1E96}', "H\u{331}"), ('\u{1E97}', "T\u{308}"),
+    ('\u{1E98}', "W\u{30A}"), ('\u{1E99}', "Y\u{30A}"), ('\u{1E9A}', "A\u{2BE}"),
+    ('\u{1F50}', "\u{3A5}\u{313}"), ('\u{1F52}', "\u{3A5}\u{313}\u{300}"),
+    ('\u{1F54}', "\u{3A5}\u{313}\u{301}"), ('\u{1F56}', "\u{3A5}\u{313}\u{342}"),
+    ('\u{1F80}', "\u{1F08}\u{399}"), ('\u{1F81}', "\u{1F09}\u{399}"), ('\u{1F82}', "\u{1F0A}\u{399}"),
+    ('\u{1F83}', "\u{1F0B}\u{399}"), ('\u{1F84}', "\u{1F0C}\u{399}"), ('\u{1F85}', "\u{1F0D}\u{399}"),
+    ('\u{1F86}', "\u{1F0E}\u{399}"), ('\u{1F87}', "\u{1F0F}\u{399}"), ('\u{1F88}', "\u{1F08}\u{399}"),
+    ('\u{1F89}', "\u{1F09}\u{399}"), ('\u{1F8A}', "\u{1F0A}\u{399}"), ('\u{1F8B}', "\u{1F0B}\u{399}"),
+    ('\u{1F8C}', "\u{1F0C}\u{399}"), ('\u{1F8D}', "\u{1F0D}\u{399}"), ('\u{1F8E}', "\u{1F0E}\u{399}"),
+    ('\u{1F8F}', "\u{1F0F}\u{399}"), ('\u{1F90}', "\u{1F28}\u{399}"), ('\u{1F91}', "\u{1F29}\u{399}"),
+    ('\u{1F92}', "\u{1F2A}\u{399}"), ('\u{1F93}', "\u{1F2B}\u{399}"), ('\u{1F94}', "\u{1F2C}\u{399}"),
+    ('\u{1F95}', "\u{1F2D}\u{399}"), ('\u{1F96}', "\u{1F2E}\u{399}"), ('\u{1F97}', "\u{1F2F}\u{399}"),
+    ('\u{1F98}', "\u{1F28}\u{399}"), ('\u{1F99}', "\u{1F29}\u{399}"), ('\u{1F9A}', "\u{1F2A}\u{399}"),
+    ('\u{1F9B}', "\u{1F2B}\u{399}"), ('\u{1F9C}', "\u{1F2C}\u{399}"), ('\u{1F9D}', "\u{1F2D}\u{399}"),
+    ('\u{1F9E}', "\u{1F2E}\u{399}"), ('\u{1F9F}', "\u{1F2F}\u{399}"), ('\u{1FA0}', "\u{1F68}\u{399}"),
+    ('\u{1FA1}', "\u{1F69}\u{399}"), ('\u{1FA2}', "\u{1F6A}\u{399}"), ('\u{1FA3}', "\u{1F6B}\u{399}"),
+    ('\u{1FA4}', "\u{1F6C}\u{399}"), ('\u{1FA5}', "\u{1F6D}\u{399}"), ('\u{1FA6}', "\u{1F6E}\u{399}"),
+    ('\u{1FA7}', "\u{1F6F}\u{399}"), ('\u{1FA8}', "\u{1F68}\u{399}"), ('\u{1FA9}', "\u{1F69}\u{399}"),
+    ('\u{1FAA}', "\u{1F6A}\u{399}"), ('\u{1FAB}', "\u{1F6B}\u{399}"), ('\u{1FAC}', "\u{1F6C}\u{399}"),
+    ('\u{1FAD}', "\u{1F6D}\u{399}"), ('\u{1FAE}', "\u{1F6E}\u{399}"), ('\u{1FAF}', "\u{1F6F}\u{399}"),
+    ('\u{1FB2}', "\u{1FBA}\u{399}"), ('\u{1FB3}', "\u{391}\u{399}"), ('\u{1FB4}', "\u{386}\u{399}"),
+    ('\u{1FB6}', "\u{391}\u{342}"), ('\u{1FB7}', "\u{391}\u{342}\u{399}"), ('\u{1FBC}', "\u{391}\u{399}"),
+    ('\u{1FC2}', "\u{1FCA}\u{399}"), ('\u{1FC3}', "\u{397}\u{399}"), ('\u{1FC4}', "\u{389}\u{399}"),
+    ('\u{1FC6}', "\u{397}\u{342}"), ('\u{1FC7}', "\u{397}\u{342}\u{399}"), ('\u{1FCC}', "\u{397}\u{399}"),
+    ('\u{1FD2}', "\u{399}\u{308}\u{300}"), ('\u{1FD3}', "\u{399}\u{308}\u{301}"),
+    ('\u{1FD6}', "\u{399}\u{342}"), ('\u{1FD7}', "\u{399}\u{308}\u{342}"),
+    ('\u{1FE2}', "\u{3A5}\u{308}\u{300}"), ('\u{1FE3}', "\u{3A5}\u{308}\u{301}"),
+    ('\u{1FE4}', "\u{3A1}\u{313}"), ('\u{1FE6}', "\u{3A5}\u{342}"), ('\u{1FE7}', "\u{3A5}\u{308}\u{342}"),
+    ('\u{1FF2}', "\u{1FFA}\u{399}"), ('\u{1FF3}', "\u{3A9}\u{399}"), ('\u{1FF4}', "\u{38F}\u{399}"),
+    ('\u{1FF6}', "\u{3A9}\u{342}"), ('\u{1FF7}', "\u{3A9}\u{342}\u{399}"), ('\u{1FFC}', "\u{3A9}\u{399}"),
+    ('\u{FB00}', "FF"), ('\u{FB01}', "FI"), ('\u{FB02}', "FL"), ('\u{FB03}', "FFI"), ('\u{FB04}', "FFL"),
+    ('\u{FB05}', "ST"), ('\u{FB06}', "ST"), ('\u{FB13}', "\u{544}\u{546}"), ('\u{FB14}', "\u{544}\u{535}"),
+    ('\u{FB15}', "\u{544}\u{53B}"), ('\u{FB16}', "\u{54E}\u{546}"), ('\u{FB17}', "\u{544}\u{53D}"),
+];
+
+// The lowercase chars that are not Ll, like the modifier letters.
+const OTHER_LOWERCASE_RANGES: &[(u32, u32)] = &[
+    (0x00AA, 0x00AA), (0x00BA, 0x00BA), (0x02B0, 0x02B8), (0x02C0, 0x02C1), (0x02E0, 0x02E4),
+    (0x0345, 0x0345), (0x037A, 0x037A), (0x1D2C, 0x1D6A), (0x1D78, 0x1D78), (0x1D9B, 0x1DBF),
+    (0x2071, 0x2071), (0x207F, 0x207F), (0x2090, 0x209C), (0x2170, 0x217F), (0x24D0, 0x24E9),
+    (0x2C7C, 0x2C7D), (0xA69C, 0xA69D), (0xA770, 0xA770), (0xA7F8, 0xA7F9), (0xAB5C, 0xAB5F),
+    (0x10780, 0x10780), (0x10783, 0x10785), (0x10787, 0x107B0), (0x107B2, 0x107BA),
+];
+
+// The uppercase chars that are not Lu, like the modifier letters.
+const OTHER_UPPERCASE_RANGES: &[(u32, u32)] = &[
+    (0x2160, 0x216F), (0x24B6, 0x24CF), (0x1F130, 0x1F149), (0x1F150, 0x1F169), (0x1F170, 0x1F189),
+];
+
+// The chars whose titlecase is not the uppercase.
+const TITLECASE_TABLE: &[(char, &str)] = &[
+    ('\u{00DF}', "Ss"), ('\u{01C4}', "\u{1C5}"), ('\u{01C5}', "\u{1C5}"), ('\u{01C6}', "\u{1C5}"),
+    ('\u{01C7}', "\u{1C8}"), ('\u{01C8}', "\u{1C8}"), ('\u{01C9}', "\u{1C8}"),
+    ('\u{01CA}', "\u{1CB}"), ('\u{01CB}', "\u{1CB}"), ('\u{01CC}', "\u{1CB}"),
+    ('\u{01F1}', "\u{1F2}"), ('\u{01F2}', "\u{1F2}"), ('\u{01F3}', "\u{1F2}"),
+    ('\u{0587}', "\u{535}\u{582}"), ('\u{10D0}', "\u{10D0}"), ('\u{10D1}', "\u{10D1}"),
+    ('\u{10D2}', "\u{10D2}"), ('\u{10D3}', "\u{10D3}"), ('\u{10D4}', "\u{10D4}"),
+    ('\u{10D5}', "\u{10D5}"), ('\u{10D6}', "\u{10D6}"), ('\u{10D7}', "\u{10D7}"),
+    ('\u{10D8}', "\u{10D8}"), ('\u{10D9}', "\u{10D9}"), ('\u{10DA}', "\u{10DA}"),
+    ('\u{10DB}', "\u{10DB}"), ('\u{10DC}', "\u{10DC}"), ('\u{10DD}', "\u{10DD}"),
+    ('\u{10DE}', "\u{10DE}"), ('\u{10DF}', "\u{10DF}"), ('\u{10E0}', "\u{10E0}"),
+    ('\u{10E1}', "\u{10E1}"), ('\u{10E2}', "\u{10E2}"), ('\u{10E3}', "\u{10E3}"),
+    ('\u{10E4}', "\u{10E4}"), ('\u{10E5}', "\u{10E5}"), ('\u{10E6}', "\u{10E6}"),
+    ('\u{10E7}', "\u{10E7}"), ('\u{10E8}', "\u{10E8}"), ('\u{10E9}', "\u{10E9}"),
+    ('\u{10EA}', "\u{10EA}"), ('\u{10EB}', "\u{10EB}"), ('\u{10EC}', "\u{10EC}"),
+    ('\u{10ED}', "\u{10ED}"), ('\u{10EE}', "\u{10EE}"), ('\u{10EF}', "\u{10EF}"),
+    ('\u{10F0}', "\u{10F0}"), ('\u{10F1}', "\u{10F1}"), ('\u{10F2}', "\u{10F2}"),
+    ('\u{10F3}', "\u{10F3}"), ('\u{10F4}', "\u{10F4}"), ('\u{10F5}', "\u{10F5}"),
+    ('\u{10F6}', "\u{10F6}"), ('\u{10F7}', "\u{10F7}"), ('\u{10F8}', "\u{10F8}"),
+    ('\u{10F9}', "\u{10F9}"), ('\u{10FA}', "\u{10FA}"), ('\u{10FD}', "\u{10FD}"),
+    ('\u{10FE}', "\u{10FE}"), ('\u{10FF}', "\u{10FF}"), ('\u{1F80}', "\u{1F88}"),
+    ('\u{1F81}', "\u{1F89}"), ('\u{1F82}', "\u{1F8A}"), ('\u{1F83}', "\u{1F8B}"),
+    ('\u{1F84}', "\u{1F8C}"), ('\u{1F85}', "\u{1F8D}"), ('\u{1F86}', "\u{1F8E}"),
+    ('\u{1F87}', "\u{1F8F}"), ('\u{1F88}', "\u{1F88}"), ('\u{1F89}', "\u{1F89}"),
+    ('\u{1F8A}', "\u{1F8A}"), ('\u{1F8B}', "\u{1F8B}"), ('\u{1F8C}', "\u{1F8C}"),
+    ('\u{1F8D}', "\u{1F8D}"), ('\u{1F8E}', "\u{1F8E}"), ('\u{1F8F}', "\u{1F8F}"),
+    ('\u{1F90}', "\u{1F98}"), ('\u{1F91}', "\u{1F99}"), ('\u{1F92}', "\u{1F9A}"),
+    ('\u{1F93}', "\u{1F9B}"), ('\u{1F94}', "\u{1F9C}"), ('\u{1F95}', "\u{1F9D}"),
+    ('\u{1F96}', "\u{1F9E}"), ('\u{1F97}', "\u{1F9F}"), ('\u{1F98}', "\u{1F98}"),
+    ('\u{1F99}', "\u{1F99}"), ('\u{1F9A}', "\u{1F9A}"), ('\u{1F9B}', "\u{1F9B}"),
+    ('\u{1F9C}', "\u{1F9C}"), ('\u{1F9D}', "\u{1F9D}"), ('\u{1F9E}', "\u{1F9E}"),
+    ('\u{1F9F}', "\u{1F9F}"), ('\u{1FA0}', "\u{1FA8}"), ('\u{1FA1}', "\u{1FA9}"),
+    ('\u{1FA2}', "\u{1FAA}"), ('\u{1FA3}', "\u{1FAB}"), ('\u{1FA4}', "\u{1FAC}"),
+    ('\u{1FA5}', "\u{1FAD}"), ('\u{1FA6}', "\u{1FAE}"), ('\u{1FA7}', "\u{1FAF}"),
+    ('\u{1FA8}', "\u{1FA8}"), ('\u{1FA9}', "\u{1FA9}"), ('\u{1FAA}', "\u{1FAA}"),
+    ('\u{1FAB}', "\u{1FAB}"), ('\u{1FAC}', "\u{1FAC}"), ('\u{1FAD}', "\u{1FAD}"),
+    ('\u{1FAE}', "\u{1FAE}"), ('\u{1FAF}', "\u{1FAF}"), ('\u{1FB2}', "\u{1FBA}\u{345}"),
+    ('\u{1FB3}', "\u{1FBC}"), ('\u{1FB4}', "\u{386}\u{345}"), ('\u{1FB7}', "\u{391}\u{342}\u{345}"),
+    ('\u{1FBC}', "\u{1FBC}"), ('\u{1FC2}', "\u{1FCA}\u{345}"), ('\u{1FC3}', "\u{1FCC}"),
+    ('\u{1FC4}', "\u{389}\u{345}"), ('\u{1FC7}', "\u{397}\u{342}\u{345}"), ('\u{1FCC}', "\u{1FCC}"),
+    ('\u{1FF2}', "\u{1FFA}\u{345}"), ('\u{1FF3}', "\u{1FFC}"), ('\u{1FF4}', "\u{38F}\u{345}"),
+    ('\u{1FF7}', "\u{3A9}\u{342}\u{345}"), ('\u{1FFC}', "\u{1FFC}"), ('\u{FB00}', "Ff"),
+    ('\u{FB01}', "Fi"), ('\u{FB02}', "Fl"), ('\u{FB03}', "Ffi"), ('\u{FB04}', "Ffl"),
+    ('\u{FB05}', "St"), ('\u{FB06}', "St"), ('\u{FB13}', "\u{544}\u{576}"),
+    ('\u{FB14}', "\u{544}\u{565}"), ('\u{FB15}', "\u{544}\u{56B}"), ('\u{FB16}', "\u{54E}\u{576}"),
+    ('\u{FB17}', "\u{544}\u{56D}"),
+];
+
+// The chars whose full case folding is not the lowercase.
+const CASEFOLD_TABLE: &[(char, &str)] = &[
+    ('\u{00B5}', "\u{3BC}"), ('\u{00DF}', "ss"), ('\u{0149}', "\u{2BC}n"), ('\u{017F}', "s"),
+    ('\u{01F0}', "j\u{30C}"), ('\u{0345}', "\u{3B9}"), ('\u{0390}', "\u{3B9}\u{308}\u{301}"),
+    ('\u{03B0}', "\u{3C5}\u{308}\u{301}"), ('\u{03C2}', "\u{3C3}"), ('\u{03D0}', "\u{3B2}"),
+    ('\u{03D1}', "\u{3B8}"), ('\u{03D5}', "\u{3C6}"), ('\u{03D6}', "\u{3C0}"),
+    ('\u{03F0}', "\u{3BA}"), ('\u{03F1}', "\u{3C1}"), ('\u{03F5}', "\u{3B5}"),
+    ('\u{0587}', "\u{565}\u{582}"), ('\u{13A0}', "\u{13A0}"), ('\u{13A1}', "\u{13A1}"),
+    ('\u{13A2}', "\u{13A2}"), ('\u{13A3}', "\u{13A3}"), ('\u{13A4}', "\u{13A4}"),
+    ('\u{13A5}', "\u{13A5}"), ('\u{13A6}', "\u{13A6}"), ('\u{13A7}', "\u{13A7}"),
+    ('\u{13A8}', "\u{13A8}"), ('\u{13A9}', "\u{13A9}"), ('\u{13AA}', "\u{13AA}"),
+    ('\u{13AB}', "\u{13AB}"), ('\u{13AC}', "\u{13AC}"), ('\u{13AD}', "\u{13AD}"),
+    ('\u{13AE}', "\u{13AE}"), ('\u{13AF}', "\u{13AF}"), ('\u{13B0}', "\u{13B0}"),
+    ('\u{13B1}', "\u{13B1}"), ('\u{13B2}', "\u{13B2}"), ('\u{13B3}', "\u{13B3}"),
+    ('\u{13B4}', "\u{13B4}"), ('\u{13B5}', "\u{13B5}"), ('\u{13B6}', "\u{13B6}"),
+    ('\u{13B7}', "\u{13B7}"), ('\u{13B8}', "\u{13B8}"), ('\u{13B9}', "\u{13B9}"),
+    ('\u{13BA}', "\u{13BA}"), ('\u{13BB}', "\u{13BB}"), ('\u{13BC}', "\u{13BC}"),
+    ('\u{13BD}', "\u{13BD}"), ('\u{13BE}', "\u{13BE}"), ('\u{13BF}', "\u{13BF}"),
+    ('\u{13C0}', "\u{13C0}"), ('\u{13C1}', "\u{13C1}"), ('\u{13C2}', "\u{13C2}"),
+    ('\u{13C3}', "\u{13C3}"), ('\u{13C4}', "\u{13C4}"), ('\u{13C5}', "\u{13C5}"),
+    ('\u{13C6}', "\u{13C6}"), ('\u{13C7}', "\u{13C7}"), ('\u{13C8}', "\u{13C8}"),
+    ('\u{13C9}', "\u{13C9}"), ('\u{13CA}', "\u{13CA}"), ('\u{13CB}', "\u{13CB}"),
+    ('\u{13CC}', "\u{13CC}"), ('\u{13CD}', "\u{13CD}"), ('\u{13CE}', "\u{13CE}"),
+    ('\u{13CF}', "\u{13CF}"), ('\u{13D0}', "\u{13D0}"), ('\u{13D1}', "\u{13D1}"),
+    ('\u{13D2}', "\u{13D2}"), ('\u{13D3}', "\u{13D3}"), ('\u{13D4}', "\u{13D4}"),
+    ('\u{13D5}', "\u{13D5}"), ('\u{13D6}', "\u{13D6}"), ('\u{13D7}', "\u{13D7}"),
+    ('\u{13D8}', "\u{13D8}"), ('\u{13D9}', "\u{13D9}"), ('\u{13DA}', "\u{13DA}"),
+    ('\u{13DB}', "\u{13DB}"), ('\u{13DC}', "\u{13DC}"), ('\u{13DD}', "\u{13DD}"),
+    ('\u{13DE}', "\u{13DE}"), ('\u{13DF}', "\u{13DF}"), ('\u{13E0}', "\u{13E0}"),
+    ('\u{13E1}', "\u{13E1}"), ('\u{13E2}', "\u{13E2}"), ('\u{13E3}', "\u{13E3}"),
+    ('\u{13E4}', "\u{13E4}"), ('\u{13E5}', "\u{13E5}"), ('\u{13E6}', "\u{13E6}"),
+    ('\u{13E7}', "\u{13E7}"), ('\u{13E8}', "\u{13E8}"), ('\u{13E9}', "\u{13E9}"),
+    ('\u{13EA}', "\u{13EA}"), ('\u{13EB}', "\u{13EB}"), ('\u{13EC}', "\u{13EC}"),
+    ('\u{13ED}', "\u{13ED}"), ('\u{13EE}', "\u{13EE}"), ('\u{13EF}', "\u{13EF}"),
+    ('\u{13F0}', "\u{13F0}"), ('\u{13F1}', "\u{13F1}"), ('\u{13F2}', "\u{13F2}"),
+    ('\u{13F3}', "\u{13F3}"), ('\u{13F4}', "\u{13F4}"), ('\u{13F5}', "\u{13F5}"),
+    ('\u{13F8}', "\u{13F0}"), ('\u{13F9}', "\u{13F1}"), ('\u{13FA}', "\u{13F2}"),
+    ('\u{13FB}', "\u{13F3}"), ('\u{13FC}', "\u{13F4}"), ('\u{13FD}', "\u{13F5}"),
+    ('\u{1C80}', "\u{432}"), ('\u{1C81}', "\u{434}"), ('\u{1C82}', "\u{43E}"),
+    ('\u{1C83}', "\u{441}"), ('\u{1C84}', "\u{442}"), ('\u{1C85}', "\u{442}"),
+    ('\u{1C86}', "\u{44A}"), ('\u{1C87}', "\u{463}"), ('\u{1C88}', "\u{A64B}"),
+    ('\u{1E96}', "h\u{331}"), ('\u{1E97}', "t\u{308}"), ('\u{1E98}', "w\u{30A}"),
+    ('\u{1E99}', "y\u{30A}"), ('\u{1E9A}', "a\u{2BE}"), ('\u{1E9B}', "\u{1E61}"),
+    ('\u{1E9E}', "ss"), ('\u{1F50}', "\u{3C5}\u{313}"), ('\u{1F52}', "\u{3C5}\u{313}\u{300}"),
+    ('\u{1F54}', "\u{3C5}\u{313}\u{301}"), ('\u{1F56}', "\u{3C5}\u{313}\u{342}"),
+    ('\u{1F80}', "\u{1F00}\u{3B9}"), ('\u{1F81}', "\u{1F01}\u{3B9}"),
+    ('\u{1F82}', "\u{1F02}\u{3B9}"), ('\u{1F83}', "\u{1F03}\u{3B9}"),
+    ('\u{1F84}', "\u{1F04}\u{3B9}"), ('\u{1F85}', "\u{1F05}\u{3B9}"),
+    ('\u{1F86}', "\u{1F06}\u{3B9}"), ('\u{1F87}', "\u{1F07}\u{3B9}"),
+    ('\u{1F88}', "\u{1F00}\u{3B9}"), ('\u{1F89}', "\u{1F01}\u{3B9}"),
+    ('\u{1F8A}', "\u{1F02}\u{3B9}"), ('\u{1F8B}', "\u{1F03}\u{3B9}"),
+    ('\u{1F8C}', "\u{1F04}\u{3B9}"), ('\u{1F8D}', "\u{1F05}\u{3B9}"),
+    ('\u{1F8E}', "\u{1F06}\u{3B9}"), ('\u{1F8F}', "\u{1F07}\u{3B9}"),
+    ('\u{1F90}', "\u{1F20}\u{3B9}"), ('\u{1F91}', "\u{1F21}\u{3B9}"),
+    ('\u{1F92}', "\u{1F22}\u{3B9}"), ('\u{1F93}', "\u{1F23}\u{3B9}"),
+    ('\u{1F94}', "\u{1F24}\u{3B9}"), ('\u{1F95}', "\u{1F25}\u{3B9}"),
+    ('\u{1F96}', "\u{1F26}\u{3B9}"), ('\u{1F97}', "\u{1F27}\u{3B9}"),
+    ('\u{1F98}', "\u{1F20}\u{3B9}"), ('\u{1F99}', "\u{1F21}\u{3B9}"),
+    ('\u{1F9A}', "\u{1F22}\u{3B9}"), ('\u{1F9B}', "\u{1F23}\u{3B9}"),
+    ('\u{1F9C}', "\u{1F24}\u{3B9}"), ('\u{1F9D}', "\u{1F25}\u{3B9}"),
+    ('\u{1F9E}', "\u{1F26}\u{3B9}"), ('\u{1F9F}', "\u{1F27}\u{3B9}"),
+    ('\u{1FA0}', "\u{1F60}\u{3B9}"), ('\u{1FA1}', "\u{1F61}\u{3B9}"),
+    ('\u{1FA2}', "\u{1F62}\u{3B9}"), ('\u{1FA3}', "\u{1F63}\u{3B9}"),
+    ('\u{1FA4}', "\u{1F64}\u{3B9}"), ('\u{1FA5}', "\u{1F65}\u{3B9}"),
+    ('\u{1FA6}', "\u{1F66}\u{3B9}"), ('\u{1FA7}', "\u{1F67}\u{3B9}"),
+    ('\u{1FA8}', "\u{1F60}\u{3B9}"), ('\u{1FA9}', "\u{1F61}\u{3B9}"),
+    ('\u{1FAA}', "\u{1F62}\u{3B9}"), ('\u{1FAB}', "\u{1F63}\u{3B9}"),
+    ('\u{1FAC}', "\u{1F64}\u{3B9}"), ('\u{1FAD}', "\u{1F65}\u{3B9}"),
+    ('\u{1FAE}', "\u{1F66}\u{3B9}"), ('\u{1FAF}', "\u{1F67}\u{3B9}"),
+    ('\u{1FB2}', "\u{1F70}\u{3B9}"), ('\u{1FB3}', "\u{3B1}\u{3B9}"), ('\u{1FB4}', "\u{3AC}\u{3B9}"),
+    ('\u{1FB6}', "\u{3B1}\u{342}"), ('\u{1FB7}', "\u{3B1}\u{342}\u{3B9}"),
+    ('\u{1FBC}', "\u{3B1}\u{3B9}"), ('\u{1FBE}', "\u{3B9}"), ('\u{1FC2}', "\u{1F74}\u{3B9}"),
+    ('\u{1FC3}', "\u{3B7}\u{3B9}"), ('\u{1FC4}', "\u{3AE}\u{3B9}"), ('\u{1FC6}', "\u{3B7}\u{342}"),
+    ('\u{1FC7}', "\u{3B7}\u{342}\u{3B9}"), ('\u{1FCC}', "\u{3B7}\u{3B9}"),
+    ('\u{1FD2}', "\u{3B9}\u{308}\u{300}"), ('\u{1FD3}', "\u{3B9}\u{308}\u{301}"),
+    ('\u{1FD6}', "\u{3B9}\u{342}"), ('\u{1FD7}', "\u{3B9}\u{308}\u{342}"),
+    ('\u{1FE2}', "\u{3C5}\u{308}\u{300}"), ('\u{1FE3}', "\u{3C5}\u{308}\u{301}"),
+    ('\u{1FE4}', "\u{3C1}\u{313}"), ('\u{1FE6}', "\u{3C5}\u{342}"),
+    ('\u{1FE7}', "\u{3C5}\u{308}\u{342}"), ('\u{1FF2}', "\u{1F7C}\u{3B9}"),
+    ('\u{1FF3}', "\u{3C9}\u{3B9}"), ('\u{1FF4}', "\u{3CE}\u{3B9}"), ('\u{1FF6}', "\u{3C9}\u{342}"),
+    ('\u{1FF7}', "\u{3C9}\u{342}\u{3B9}"), ('\u{1FFC}', "\u{3C9}\u{3B9}"), ('\u{AB70}', "\u{13A0}"),
+    ('\u{AB71}', "\u{13A1}"), ('\u{AB72}', "\u{13A2}"), ('\u{AB73}', "\u{13A3}"),
+    ('\u{AB74}', "\u{13A4}"), ('\u{AB75}', "\u{13A5}"), ('\u{AB76}', "\u{13A6}"),
+    ('\u{AB77}', "\u{13A7}"), ('\u{AB78}', "\u{13A8}"), ('\u{AB79}', "\u{13A9}"),
+    ('\u{AB7A}', "\u{13AA}"), ('\u{AB7B}', "\u{13AB}"), ('\u{AB7C}', "\u{13AC}"),
+    ('\u{AB7D}', "\u{13AD}"), ('\u{AB7E}', "\u{13AE}"), ('\u{AB7F}', "\u{13AF}"),
+    ('\u{AB80}', "\u{13B0}"), ('\u{AB81}', "\u{13B1}"), ('\u{AB82}', "\u{13B2}"),
+    ('\u{AB83}', "\u{13B3}"), ('\u{AB84}', "\u{13B4}"), ('\u{AB85}', "\u{13B5}"),
+    ('\u{AB86}', "\u{13B6}"), ('\u{AB87}', "\u{13B7}"), ('\u{AB88}', "\u{13B8}"),
+    ('\u{AB89}', "\u{13B9}"), ('\u{AB8A}', "\u{13BA}"), ('\u{AB8B}', "\u{13BB}"),
+    ('\u{AB8C}', "\u{13BC}"), ('\u{AB8D}', "\u{13BD}"), ('\u{AB8E}', "\u{13BE}"),
+    ('\u{AB8F}', "\u{13BF}"), ('\u{AB90}', "\u{13C0}"), ('\u{AB91}', "\u{13C1}"),
+    ('\u{AB92}', "\u{13C2}"), ('\u{AB93}', "\u{13C3}"), ('\u{AB94}', "\u{13C4}"),
+    ('\u{AB95}', "\u{13C5}"), ('\u{AB96}', "\u{13C6}"), ('\u{AB97}', "\u{13C7}"),
+    ('\u{AB98}', "\u{13C8}"), ('\u{AB99}', "\u{13C9}"), ('\u{AB9A}', "\u{13CA}"),
+    ('\u{AB9B}', "\u{13CB}"), ('\u{AB9C}', "\u{13CC}"), ('\u{AB9D}', "\u{13CD}"),
+    ('\u{AB9E}', "\u{13CE}"), ('\u{AB9F}', "\u{13CF}"), ('\u{ABA0}', "\u{13D0}"),
+    ('\u{ABA1}', "\u{13D1}"), ('\u{ABA2}', "\u{13D2}"), ('\u{ABA3}', "\u{13D3}"),
+    ('\u{ABA4}', "\u{13D4}"), ('\u{ABA5}', "\u{13D5}"), ('\u{ABA6}', "\u{13D6}"),
+    ('\u{ABA7}', "\u{13D7}"), ('\u{ABA8}', "\u{13D8}"), ('\u{ABA9}', "\u{13D9}"),
+    ('\u{ABAA}', "\u{13DA}"), ('\u{ABAB}', "\u{13DB}"), ('\u{ABAC}', "\u{13DC}"),
+    ('\u{ABAD}', "\u{13DD}"), ('\u{ABAE}', "\u{13DE}"), ('\u{ABAF}', "\u{13DF}"),
+    ('\u{ABB0}', "\u{13E0}"), ('\u{ABB1}', "\u{13E1}"), ('\u{ABB2}', "\u{13E2}"),
+    ('\u{ABB3}', "\u{13E3}"), ('\u{ABB4}', "\u{13E4}"), ('\u{ABB5}', "\u{13E5}"),
+    ('\u{ABB6}', "\u{13E6}"), ('\u{ABB7}', "\u{13E7}"), ('\u{ABB8}', "\u{13E8}"),
+    ('\u{ABB9}', "\u{13E9}"), ('\u{ABBA}', "\u{13EA}"), ('\u{ABBB}', "\u{13EB}"),
+    ('\u{ABBC}', "\u{13EC}"), ('\u{ABBD}', "\u{13ED}"), ('\u{ABBE}', "\u{13EE}"),
+    ('\u{ABBF}', "\u{13EF}"), ('\u{FB00}', "ff"), ('\u{FB01}', "fi"), ('\u{FB02}', "fl"),
+    ('\u{FB03}', "ffi"), ('\u{FB04}', "ffl"), ('\u{FB05}', "st"), ('\u{FB06}', "st"),
+    ('\u{FB13}', "\u{574}\u{576}"), ('\u{FB14}', "\u{574}\u{565}"), ('\u{FB15}', "\u{574}\u{56B}"),
+    ('\u{FB16}', "\u{57E}\u{576}"), ('\u{FB17}', "\u{574}\u{56D}"),
+];
@@ -0,0 +1,987 @@
+//! The `CharString` and `CharStr` types.
+//!
+//! `CharString` is an owned string of chars, a newtype over `Vec<char>`, and
+//! `CharStr` is the borrowed unsized slice of it, a newtype over `[char]`,
+//! like `String` and `str` or `PathBuf` and `Path` in the Std.
+//! They carry all the methods of `StringUtilsVecCharsV2` as inherent
+//! methods, so they don't collide with the inherent methods of `Vec` and of
+//! `[T]` ( like `ends_with()` ) and the `to_string()` comes from `Display`.
+
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Rev;
+use std::ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, RangeBounds};
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::slice;
+
+use crate::aho_corasick::MatchKind;
+use crate::error::StringUtilsError;
+use crate::fuzzy::FuzzyMatch;
+use crate::glob::CharGlob;
+use crate::match_options::MatchOptions;
+use crate::normalization::Normalization;
+use crate::pattern::{self, CharPattern, CharSearcher};
+use crate::regex::{CharCaptures, CharRegex, CharRegexMatches};
+use crate::slices::StringUtilsSlices;
+use crate::split::{RSplitN, SkipEmptyExt, Split, SplitInclusive, SplitN, SplitWhitespace};
+use crate::str_utils::StringUtils;
+use crate::translation::TranslationTable;
+use crate::vec_chars::{self, StringUtilsVecCharsV2};
+
+/// A borrowed slice of chars.
+#[repr(transparent)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharStr {
+    chars: [char],
+}
+
+/// An owned, growable string of chars with O(1) random access.
+#[derive(Clone, Default)]
+pub struct CharString {
+    chars: Vec<char>,
+    // The form of the patterns of the *_str methods.
+    form: Normalization,
+}
+
+impl CharStr {
+    pub fn from_chars(chars: &[char]) -> &CharStr {
+        // SAFETY: CharStr is a repr(transparent) wrapper over [char].
+        unsafe { &*(chars as *const [char] as *const CharStr) }
+    }
+
+    pub fn from_chars_mut(chars: &mut [char]) -> &mut CharStr {
+        // SAFETY: CharStr is a repr(transparent) wrapper over [char].
+        unsafe { &mut *(chars as *mut [char] as *mut CharStr) }
+    }
+
+    #[inline]
+    pub fn as_chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    #[inline]
+    pub fn as_mut_chars(&mut self) -> &mut [char] {
+        &mut self.chars
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, char> {
+        self.chars.iter()
+    }
+
+    pub fn to_char_string(&self) -> CharString {
+        CharString::from_vec(self.chars.to_vec())
+    }
+
+    pub fn to_vec_chars(&self) -> Vec<char> {
+        self.chars.to_vec()
+    }
+
+    pub fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        buf.extend(self.chars.iter());
+        buf
+    }
+
+    #[inline]
+    pub fn eq_vec(&self, other: &[char]) -> bool {
+        self.chars == *other
+    }
+
+    #[inline]
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.chars.iter().copied().eq(p_str.chars())
+    }
+
+    /// Like `eq_str()`, see `MatchOptions`.
+    pub fn eq_str_with(&self, p_str: &str, options: MatchOptions) -> bool {
+        vec_chars::eq_str_with_chars(&self.chars, p_str, options)
+    }
+
+    pub fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        vec_chars::find_chars(&self.chars, p_vec_chars, start_pos, end_pos)
+    }
+
+    pub fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        vec_chars::try_find_chars(&self.chars, p_vec_chars, start_pos, end_pos)
+    }
+
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_vec(&p_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    pub fn contains_vec(& self, p_vec_chars: &[char]) -> bool {
+        self.find_vec(p_vec_chars, 0, None).is_some()
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.contains_vec(&p_str.get_vec_chars())
+    }
+
+    pub fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        self.chars.starts_with(pattern_vec_chars)
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        self.start_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    pub fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        self.chars.ends_with(pattern_vec_chars)
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        self.ends_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    pub fn split_vec(& self, at_pattern_vec: &[char]) -> Vec<&CharStr> {
+        vec_chars::split_chars(&self.chars, at_pattern_vec)
+            .into_iter()
+            .map(CharStr::from_chars)
+            .collect()
+    }
+
+    pub fn split_str(& self, at_pattern_str: &str) -> Vec<&CharStr> {
+        self.split_vec(&at_pattern_str.get_vec_chars())
+    }
+
+    // The generic methods, with a `CharPattern` that can be a char, a
+    // sequence of chars, a set of chars or a predicate.
+
+    /// Returns a None or the index of the first match.
+    pub fn find<P: CharPattern>(&self, pattern: P) -> Option<usize> {
+        pattern.into_searcher().find_at(&self.chars, 0).map(|(start, _)| start)
+    }
+
+    /// Returns a None or the index of the start of the last match.
+    pub fn rfind<P: CharPattern>(&self, pattern: P) -> Option<usize> {
+        pattern.into_searcher().rfind_at(&self.chars, self.len()).map(|(start, _)| start)
+    }
+
+    pub fn contains<P: CharPattern>(&self, pattern: P) -> bool {
+        self.find(pattern).is_some()
+    }
+
+    pub fn starts_with<P: CharPattern>(&self, pattern: P) -> bool {
+        pattern.into_searcher().is_prefix_of(&self.chars)
+    }
+
+    pub fn ends_with<P: CharPattern>(&self, pattern: P) -> bool {
+        pattern.into_searcher().is_suffix_of(&self.chars)
+    }
+
+    /// Returns the non overlapping matches, from left to right.
+    pub fn matches<P: CharPattern>(&self, pattern: P) -> Vec<&CharStr> {
+        self.match_ranges(pattern)
+            .into_iter()
+            .map(|(start, end)| &self[start..end])
+            .collect()
+    }
+
+    // The split family, lazy iterators with the semantics of `str`, see the
+    // module `split`.
+
+    /// Splits at each match, like `str::split()` the empty pieces are kept,
+    /// so n matches give n + 1 pieces.
+    pub fn split<P: CharPattern>(&self, pattern: P) -> Split<'_, P::Searcher> {
+        Split::new(&self.chars, pattern.into_searcher(), true)
+    }
+
+    /// Like `split()`, but each piece ends with its match.
+    pub fn split_inclusive<P: CharPattern>(&self, pattern: P) -> SplitInclusive<'_, P::Searcher> {
+        SplitInclusive::new(&self.chars, pattern.into_searcher())
+    }
+
+    /// Like `split()`, but a last empty piece is skipped.
+    pub fn split_terminator<P: CharPattern>(&self, pattern: P) -> Split<'_, P::Searcher> {
+        Split::new(&self.chars, pattern.into_searcher(), false)
+    }
+
+    /// Like `split()`, but from right to left.
+    pub fn rsplit<P: CharPattern>(&self, pattern: P) -> Rev<Split<'_, P::Searcher>> {
+        self.split(pattern).rev()
+    }
+
+    /// Returns at most count pieces, the last one has the rest of the chars.
+    pub fn splitn<P: CharPattern>(&self, count: usize, pattern: P) -> SplitN<'_, P::Searcher> {
+        SplitN::new(&self.chars, pattern.into_searcher(), count)
+    }
+
+    /// Like `splitn()`, but from right to left.
+    pub fn rsplitn<P: CharPattern>(&self, count: usize, pattern: P) -> RSplitN<'_, P::Searcher> {
+        RSplitN::new(&self.chars, pattern.into_searcher(), count)
+    }
+
+    /// Splits at the whitespace, without empty pieces.
+    pub fn split_whitespace(&self) -> SplitWhitespace<'_> {
+        self.split(char::is_whitespace as fn(char) -> bool).skip_empty()
+    }
+
+    /// Returns a None or the pieces before and after the first match.
+    pub fn split_once<P: CharPattern>(&self, pattern: P) -> Option<(&CharStr, &CharStr)> {
+        let (start, end) = pattern.into_searcher().find_at(&self.chars, 0)?;
+        Some((&self[..start], &self[end..]))
+    }
+
+    /// Returns a None or the pieces before and after the last match.
+    pub fn rsplit_once<P: CharPattern>(&self, pattern: P) -> Option<(&CharStr, &CharStr)> {
+        let (start, end) = pattern.into_searcher().rfind_at(&self.chars, self.len())?;
+        Some((&self[..start], &self[end..]))
+    }
+
+    /// Returns the sub string without the repeated matches at the start and
+    /// at the end.
+    pub fn trim_matches<P: CharPattern>(&self, pattern: P) -> &CharStr {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), &self.chars, true, true)]
+    }
+
+    pub fn trim_start_matches<P: CharPattern>(&self, pattern: P) -> &CharStr {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), &self.chars, true, false)]
+    }
+
+    pub fn trim_end_matches<P: CharPattern>(&self, pattern: P) -> &CharStr {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), &self.chars, false, true)]
+    }
+
+    /// Returns a new CharString with all the matches replaced by to.
+    pub fn replace<P: CharPattern, T: Into<CharString>>(&self, pattern: P, to: T) -> CharString {
+        self.replacen(pattern, to, usize::MAX)
+    }
+
+    /// Returns a new CharString with the first count matches replaced by to.
+    pub fn replacen<P: CharPattern, T: Into<CharString>>(&self, pattern: P, to: T, count: usize) -> CharString {
+        let to: CharString = to.into();
+        let mut res = CharString::with_capacity(self.len());
+        let mut last_index = 0_usize;
+        for (start, end) in self.match_ranges(pattern).into_iter().take(count) {
+            res.push_vec(&self.chars[last_index..start]);
+            res.push_vec(&to.chars);
+            last_index = end;
+        }
+        res.push_vec(&self.chars[last_index..]);
+        res
+    }
+
+    fn match_ranges<P: CharPattern>(&self, pattern: P) -> Vec<(usize, usize)> {
+        let mut searcher = pattern.into_searcher();
+        let mut res_vec: Vec<(usize, usize)> = Vec::new();
+        let mut next_start_pos = 0_usize;
+        while let Some((start, end)) = searcher.find_at(&self.chars, next_start_pos) {
+            res_vec.push((start, end));
+            next_start_pos = end;
+        }
+        res_vec
+    }
+
+    /// Returns a None or the index of the first match inside of range.
+    pub fn find_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        vec_chars::find_in_chars(&self.chars, pattern, range)
+    }
+
+    /// Returns the number of non overlapping matches inside of range.
+    pub fn count_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> usize {
+        vec_chars::count_in_chars(&self.chars, pattern, range)
+    }
+
+    pub fn split_in(& self, at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<&CharStr> {
+        vec_chars::split_in_chars(&self.chars, at_pattern, range)
+            .into_iter()
+            .map(CharStr::from_chars)
+            .collect()
+    }
+
+    // The variants with options, see `MatchOptions`, the positions are of
+    // self.
+
+    /// Like `find_str()` but returns the range of the match in self.
+    pub fn find_str_with(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>, options: MatchOptions) -> Option<Range<usize>> {
+        vec_chars::find_str_with_chars(&self.chars, p_str, start_pos, end_pos, options)
+    }
+
+    pub fn contains_str_with(& self, p_str: &str, options: MatchOptions) -> bool {
+        self.find_str_with(p_str, 0, None, options).is_some()
+    }
+
+    pub fn split_str_with(& self, at_pattern_str: &str, options: MatchOptions) -> Vec<&CharStr> {
+        vec_chars::split_str_with_chars(&self.chars, at_pattern_str, options)
+            .into_iter()
+            .map(CharStr::from_chars)
+            .collect()
+    }
+
+    // The regex variants, with a `CharRegex` compiled once for many searches.
+
+    /// Returns a None or the range of the first match that starts at or
+    /// after start_pos. Panics if start_pos is greater then the len.
+    pub fn find_regex(& self, regex: &CharRegex, start_pos: usize) -> Option<Range<usize>> {
+        vec_chars::find_regex_chars(&self.chars, regex, start_pos)
+    }
+
+    /// Iterator over the ranges of the non overlapping matches.
+    pub fn find_iter_regex<'a>(& 'a self, regex: & 'a CharRegex) -> CharRegexMatches<'a, 'a> {
+        regex.find_iter(&self.chars)
+    }
+
+    /// Returns a None or the capture groups of the first match.
+    pub fn captures<'a>(& self, regex: & 'a CharRegex) -> Option<CharCaptures<'a>> {
+        regex.captures(&self.chars)
+    }
+
+    /// Like `split_vec()`, the pieces between the matches that have chars.
+    pub fn split_regex(& self, regex: &CharRegex) -> Vec<&CharStr> {
+        vec_chars::split_regex_chars(&self.chars, regex)
+            .into_iter()
+            .map(CharStr::from_chars)
+            .collect()
+    }
+
+    // The glob variants, with a `CharGlob` compiled once for many searches.
+
+    /// Returns true if the glob matches all the chars of self.
+    pub fn matches_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches(&self.chars)
+    }
+
+    pub fn start_with_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches_start(&self.chars)
+    }
+
+    pub fn ends_with_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches_end(&self.chars)
+    }
+
+    pub fn contains_glob(& self, glob: &CharGlob) -> bool {
+        glob.find(&self.chars).is_some()
+    }
+
+    /// Returns a None or the range of the first match that starts at or
+    /// after start_pos. Panics if start_pos is greater then the len.
+    pub fn find_glob(& self, glob: &CharGlob, start_pos: usize) -> Option<Range<usize>> {
+        vec_chars::find_glob_chars(&self.chars, glob, start_pos)
+    }
+
+    /// Returns the ranges of the non overlapping matches.
+    pub fn find_all_glob(& self, glob: &CharGlob) -> Vec<Range<usize>> {
+        glob.find_all(&self.chars)
+    }
+
+    // The fuzzy variants, the match can have up to max_edits inserted,
+    // deleted or substituted chars, see `CharFuzzyFinder`.
+
+    /// Returns a None or the best match, with the fewest edits, that starts
+    /// between start_pos and end_pos inclusive.
+    pub fn find_fuzzy(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        vec_chars::find_fuzzy_chars(&self.chars, pattern, max_edits, start_pos, end_pos)
+    }
+
+    /// Returns the non overlapping matches that start between start_pos
+    /// and end_pos inclusive.
+    pub fn find_fuzzy_all(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Vec<FuzzyMatch> {
+        vec_chars::find_fuzzy_all_chars(&self.chars, pattern, max_edits, start_pos, end_pos)
+    }
+
+    pub fn find_fuzzy_str(& self, p_str: &str, max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        self.find_fuzzy(&p_str.get_vec_chars(), max_edits, start_pos, end_pos)
+    }
+
+    /// Returns a new CharString without the accents, like
+    /// `StringUtilsSlices::strip_diacritics()`.
+    pub fn strip_diacritics(&self) -> CharString {
+        CharString::from_vec(self.chars.strip_diacritics())
+    }
+
+    /// Returns a new CharString with each char translated by the table,
+    /// like `str.translate()` of Python.
+    pub fn translate(&self, table: &TranslationTable) -> CharString {
+        CharString::from_vec(table.translate(&self.chars))
+    }
+
+    /// Like `StringUtilsSlices::py_slice()`, borrowed for a step of 1,
+    /// owned otherwise. Panics if step is 0.
+    pub fn py_slice(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, CharStr> {
+        match self.chars.py_slice(start, stop, step) {
+            Cow::Borrowed(chars) => Cow::Borrowed(CharStr::from_chars(chars)),
+            Cow::Owned(chars) => Cow::Owned(CharString::from_vec(chars)),
+        }
+    }
+
+    /// Like `StringUtilsSlices::py_index()`.
+    pub fn py_index(&self, index: isize) -> Option<char> {
+        self.chars.py_index(index)
+    }
+}
+
+impl CharString {
+    pub fn new() -> CharString {
+        CharString::from_vec(Vec::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> CharString {
+        CharString::from_vec(Vec::with_capacity(capacity))
+    }
+
+    pub fn from_vec(chars: Vec<char>) -> CharString {
+        CharString { chars, form: Normalization::NFC }
+    }
+
+    /// The chars of p_str are normalized with form, and so are the
+    /// patterns of the find, split, push, insert and replace `*_str`
+    /// methods. `map_str()` and the `*_str_with()` methods stay in NFC.
+    pub fn from_str_with(p_str: &str, form: Normalization) -> CharString {
+        CharString { chars: p_str.get_vec_chars_with(form), form }
+    }
+
+    /// The form of the patterns of the `*_str` methods, NFC unless the
+    /// CharString was made with `from_str_with()`. The form is lost by the
+    /// methods that return a new CharString.
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.form
+    }
+
+    pub fn into_vec(self) -> Vec<char> {
+        self.chars
+    }
+
+    pub fn as_char_str(&self) -> &CharStr {
+        CharStr::from_chars(&self.chars)
+    }
+
+    pub fn as_mut_char_str(&mut self) -> &mut CharStr {
+        CharStr::from_chars_mut(&mut self.chars)
+    }
+
+    pub fn as_vec(&self) -> &Vec<char> {
+        &self.chars
+    }
+
+    pub fn as_mut_vec(&mut self) -> &mut Vec<char> {
+        &mut self.chars
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.chars.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.chars.clear();
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.chars.push(c);
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        self.chars.pop()
+    }
+
+    pub fn join_vec(p_vec_vec_chars: &[&[char]]) -> CharString {
+        CharString::from_vec(Vec::join_vec(p_vec_vec_chars))
+    }
+
+    pub fn join_str(p_vec_str: &[&str]) -> CharString {
+        CharString::from_vec(Vec::join_str(p_vec_str))
+    }
+
+    pub fn push_vec(& mut self, p_vec_chars: &[char]) {
+        self.chars.push_vec(p_vec_chars);
+    }
+
+    pub fn push_str(& mut self, p_str: &str) {
+        self.chars.extend(self.form.chars(p_str));
+    }
+
+    pub fn push_str_start(& mut self, p_str: &str) {
+        self.chars.push_vec_start(&p_str.get_vec_chars_with(self.form));
+    }
+
+    pub fn push_vec_start(& mut self, other_vec: &[char]) {
+        self.chars.push_vec_start(other_vec);
+    }
+
+    pub fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError> {
+        self.chars.insert_vec(&p_str.get_vec_chars_with(self.form), at_pos)
+    }
+
+    pub fn insert_vec(& mut self, other_vec: &[char], at_pos: usize) -> Result<(), StringUtilsError> {
+        self.chars.insert_vec(other_vec, at_pos)
+    }
+
+    pub fn trim_start(& mut self) {
+        self.chars.trim_start();
+    }
+
+    pub fn trim_end(& mut self) {
+        self.chars.trim_end();
+    }
+
+    pub fn trim(& mut self) {
+        self.chars.trim();
+    }
+
+    // The *_str methods of CharStr, with the patterns in the form of the
+    // CharString.
+
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_vec(&p_str.get_vec_chars_with(self.form), start_pos, end_pos)
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.contains_vec(&p_str.get_vec_chars_with(self.form))
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        self.start_with_vec(&pattern_str.get_vec_chars_with(self.form))
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        self.ends_with_vec(&pattern_str.get_vec_chars_with(self.form))
+    }
+
+    pub fn split_str(& self, at_pattern_str: &str) -> Vec<&CharStr> {
+        self.split_vec(&at_pattern_str.get_vec_chars_with(self.form))
+    }
+
+    pub fn find_fuzzy_str(& self, p_str: &str, max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        self.find_fuzzy(&p_str.get_vec_chars_with(self.form), max_edits, start_pos, end_pos)
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.chars.replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos)
+    }
+
+    pub fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        self.chars.try_replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos)
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.chars.replace_vec(&match_pattern_str.get_vec_chars_with(self.form), &replace_pattern_str.get_vec_chars_with(self.form), start_pos, end_pos)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize> {
+        self.chars.replace_vec_all(match_pattern_vec, replace_pattern_vec)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        self.chars.replace_vec_all(&match_pattern_str.get_vec_chars_with(self.form), &replace_pattern_str.get_vec_chars_with(self.form))
+    }
+
+    /// Returns a None or the index of the first replace inside of range.
+    pub fn replace_in(& mut self, match_pattern: &[char], replace_pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        self.chars.replace_in(match_pattern, replace_pattern, range)
+    }
+
+    pub fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize> {
+        self.chars.map_str(map)
+    }
+
+    pub fn map_str_with(& mut self, map: &[(&str, &str)], match_kind: MatchKind) -> HashMap<String, usize> {
+        self.chars.map_str_with(map, match_kind)
+    }
+
+    /// Returns a None or the number of replaces, see `MatchOptions`.
+    pub fn replace_str_all_with(& mut self, match_pattern_str: &str, replace_pattern_str: &str, options: MatchOptions) -> Option<usize> {
+        self.chars.replace_str_all_with(match_pattern_str, replace_pattern_str, options)
+    }
+
+    /// Replaces all the matches, with the groups expanded like in
+    /// `CharCaptures::expand()`. Returns a None or the number of replaces.
+    pub fn replace_regex(& mut self, regex: &CharRegex, replacement: &str) -> Option<usize> {
+        self.chars.replace_regex(regex, replacement)
+    }
+
+    /// Removes the accents in place.
+    pub fn strip_diacritics_in_place(& mut self) {
+        self.chars.strip_diacritics_in_place();
+    }
+
+    /// Translates each char by the table, in place and in one pass.
+    pub fn translate_in_place(& mut self, table: &TranslationTable) {
+        table.translate_in_place(&mut self.chars);
+    }
+}
+
+
+// Conversions.
+
+impl From<&str> for CharString {
+    /// The chars are normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> CharString {
+        CharString::from_vec(p_str.get_vec_chars())
+    }
+}
+
+impl From<String> for CharString {
+    fn from(string: String) -> CharString {
+        CharString::from(string.as_str())
+    }
+}
+
+impl From<&[char]> for CharString {
+    fn from(chars: &[char]) -> CharString {
+        CharString::from_vec(chars.to_vec())
+    }
+}
+
+impl From<&CharStr> for CharString {
+    fn from(char_str: &CharStr) -> CharString {
+        char_str.to_char_string()
+    }
+}
+
+impl From<Vec<char>> for CharString {
+    fn from(chars: Vec<char>) -> CharString {
+        CharString::from_vec(chars)
+    }
+}
+
+impl From<CharString> for Vec<char> {
+    fn from(char_string: CharString) -> Vec<char> {
+        char_string.chars
+    }
+}
+
+impl From<CharString> for String {
+    fn from(char_string: CharString) -> String {
+        char_string.chars.iter().collect()
+    }
+}
+
+impl<'a> From<&'a [char]> for &'a CharStr {
+    fn from(chars: &'a [char]) -> &'a CharStr {
+        CharStr::from_chars(chars)
+    }
+}
+
+impl FromIterator<char> for CharString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> CharString {
+        CharString::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<'a> FromIterator<&'a char> for CharString {
+    fn from_iter<I: IntoIterator<Item = &'a char>>(iter: I) -> CharString {
+        CharString::from_vec(iter.into_iter().copied().collect())
+    }
+}
+
+impl Extend<char> for CharString {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        self.chars.extend(iter);
+    }
+}
+
+impl<'a> Extend<&'a char> for CharString {
+    fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
+        self.chars.extend(iter);
+    }
+}
+
+impl<'a> IntoIterator for &'a CharStr {
+    type Item = &'a char;
+    type IntoIter = slice::Iter<'a, char>;
+
+    fn into_iter(self) -> slice::Iter<'a, char> {
+        self.chars.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CharString {
+    type Item = &'a char;
+    type IntoIter = slice::Iter<'a, char>;
+
+    fn into_iter(self) -> slice::Iter<'a, char> {
+        self.chars.iter()
+    }
+}
+
+impl IntoIterator for CharString {
+    type Item = char;
+    type IntoIter = std::vec::IntoIter<char>;
+
+    fn into_iter(self) -> std::vec::IntoIter<char> {
+        self.chars.into_iter()
+    }
+}
+
+
+// Borrowing.
+
+impl Deref for CharString {
+    type Target = CharStr;
+
+    #[inline]
+    fn deref(&self) -> &CharStr {
+        self.as_char_str()
+    }
+}
+
+impl DerefMut for CharString {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut CharStr {
+        self.as_mut_char_str()
+    }
+}
+
+// The form is not compared, like in `GraphemeString`, and the hash is the
+// one of the CharStr, for the `Borrow`.
+
+impl PartialEq for CharString {
+    fn eq(&self, other: &CharString) -> bool {
+        self.chars == other.chars
+    }
+}
+
+impl Eq for CharString {}
+
+impl PartialOrd for CharString {
+    fn partial_cmp(&self, other: &CharString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CharString {
+    fn cmp(&self, other: &CharString) -> Ordering {
+        self.chars.cmp(&other.chars)
+    }
+}
+
+impl Hash for CharString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_char_str().hash(state);
+    }
+}
+
+impl Borrow<CharStr> for CharString {
+    fn borrow(&self) -> &CharStr {
+        self.as_char_str()
+    }
+}
+
+impl ToOwned for CharStr {
+    type Owned = CharString;
+
+    fn to_owned(&self) -> CharString {
+        self.to_char_string()
+    }
+}
+
+impl AsRef<[char]> for CharStr {
+    fn as_ref(&self) -> &[char] {
+        &self.chars
+    }
+}
+
+impl AsRef<[char]> for CharString {
+    fn as_ref(&self) -> &[char] {
+        &self.chars
+    }
+}
+
+impl AsRef<CharStr> for CharString {
+    fn as_ref(&self) -> &CharStr {
+        self.as_char_str()
+    }
+}
+
+
+// Indexing, by a char position or by a range of char positions.
+
+impl Index<usize> for CharStr {
+    type Output = char;
+
+    #[inline]
+    fn index(&self, index: usize) -> &char {
+        &self.chars[index]
+    }
+}
+
+impl IndexMut<usize> for CharStr {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut char {
+        &mut self.chars[index]
+    }
+}
+
+impl Index<usize> for CharString {
+    type Output = char;
+
+    #[inline]
+    fn index(&self, index: usize) -> &char {
+        &self.chars[index]
+    }
+}
+
+impl IndexMut<usize> for CharString {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut char {
+        &mut self.chars[index]
+    }
+}
+
+macro_rules! impl_index_range {
+    ($($range:ty),*) => {
+        $(
+            impl Index<$range> for CharStr {
+                type Output = CharStr;
+
+                #[inline]
+                fn index(&self, range: $range) -> &CharStr {
+                    CharStr::from_chars(&self.chars[range])
+                }
+            }
+
+            impl IndexMut<$range> for CharStr {
+                #[inline]
+                fn index_mut(&mut self, range: $range) -> &mut CharStr {
+                    CharStr::from_chars_mut(&mut self.chars[range])
+                }
+            }
+
+            impl Index<$range> for CharString {
+                type Output = CharStr;
+
+                #[inline]
+                fn index(&self, range: $range) -> &CharStr {
+                    CharStr::from_chars(&self.chars[range])
+                }
+            }
+
+            impl IndexMut<$range> for CharString {
+                #[inline]
+                fn index_mut(&mut self, range: $range) -> &mut CharStr {
+                    CharStr::from_chars_mut(&mut self.chars[range])
+                }
+            }
+        )*
+    };
+}
+
+impl_index_range!(Range<usize>, RangeFrom<usize>, RangeFull,
+                  RangeInclusive<usize>, RangeTo<usize>, RangeToInclusive<usize>);
+
+
+// Formatting.
+
+impl fmt::Display for CharStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let string: String = self.chars.iter().collect();
+        f.pad(&string)
+    }
+}
+
+impl fmt::Debug for CharStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        f.write_char('"')?;
+        for c in self.chars.iter() {
+            for c_esc in c.escape_debug() {
+                f.write_char(c_esc)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl fmt::Display for CharString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_char_str(), f)
+    }
+}
+
+impl fmt::Debug for CharString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_char_str(), f)
+    }
+}
+
+
+// Concatenation.
+
+impl Add<&CharStr> for CharString {
+    type Output = CharString;
+
+    fn add(mut self, other: &CharStr) -> CharString {
+        self.chars.extend_from_slice(&other.chars);
+        self
+    }
+}
+
+impl Add<&str> for CharString {
+    type Output = CharString;
+
+    fn add(mut self, other: &str) -> CharString {
+        self.push_str(other);
+        self
+    }
+}
+
+impl AddAssign<&CharStr> for CharString {
+    fn add_assign(&mut self, other: &CharStr) {
+        self.chars.extend_from_slice(&other.chars);
+    }
+}
+
+impl AddAssign<&str> for CharString {
+    fn add_assign(&mut self, other: &str) {
+        self.push_str(other);
+    }
+}
+
+impl AddAssign<char> for CharString {
+    fn add_assign(&mut self, c: char) {
+        self.chars.push(c);
+    }
+}
+
+
+// Comparisons with the Std strings and with slices of chars.
+// The comparison is made char by char, without normalization.
+
+macro_rules! impl_partial_eq {
+    ($lhs:ty, $rhs:ty, |$a:ident, $b:ident| $body:expr) => {
+        impl PartialEq<$rhs> for $lhs {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl PartialEq<$lhs> for $rhs {
+            #[inline]
+            fn eq(&self, other: &$lhs) -> bool {
+                let ($a, $b) = (other, self);
+                $body
+            }
+        }
+    };
+}
+
+impl_partial_eq!(CharStr, str, |a, b| a.eq_str(b));
+impl_partial_eq!(CharStr, &str, |a, b| a.eq_str(b));
+impl_partial_eq!(CharStr, String, |a, b| a.eq_str(b));
+impl_partial_eq!(CharStr, [char], |a, b| a.eq_vec(b));
+impl_partial_eq!(CharString, str, |a, b| a.eq_str(b));
+impl_partial_eq!(CharString, &str, |a, b| a.eq_str(b));
+impl_partial_eq!(CharString, String, |a, b| a.eq_str(b));
+impl_partial_eq!(CharString, [char], |a, b| a.eq_vec(b));
+impl_partial_eq!(CharString, Vec<char>, |a, b| a.eq_vec(b));
+impl_partial_eq!(CharString, CharStr, |a, b| a.eq_vec(&b.chars));
+impl_partial_eq!(CharString, &CharStr, |a, b| a.eq_vec(&b.chars));
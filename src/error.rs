@@ -0,0 +1,42 @@
+//! The error type of the crate.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StringUtilsError {
+    /// A char position is outside of the string with len chars.
+    IndexOutOfBounds { index: usize, len: usize },
+    /// A range of char positions that ends before it starts.
+    InvalidRange { start: usize, end: usize },
+    /// An empty pattern, that can't be searched.
+    EmptyPattern,
+    /// Two sequences that have to be of the same len, like the from and
+    /// the to of `TranslationTable::maketrans()`.
+    UnequalLengths { left: usize, right: usize },
+    /// A regex with a syntax error at the char position, or too big.
+    InvalidRegex { position: usize, reason: &'static str },
+    /// A glob with a syntax error at the char position.
+    InvalidGlob { position: usize, reason: &'static str },
+}
+
+impl fmt::Display for StringUtilsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StringUtilsError::IndexOutOfBounds { index, len } =>
+                write!(f, "index {} is out of bounds for a string of len {}", index, len),
+            StringUtilsError::InvalidRange { start, end } =>
+                write!(f, "invalid range, start {} is greater then end {}", start, end),
+            StringUtilsError::EmptyPattern =>
+                write!(f, "the pattern is empty"),
+            StringUtilsError::UnequalLengths { left, right } =>
+                write!(f, "the lengths {} and {} are not equal", left, right),
+            StringUtilsError::InvalidRegex { position, reason } =>
+                write!(f, "invalid regex at char {}, {}", position, reason),
+            StringUtilsError::InvalidGlob { position, reason } =>
+                write!(f, "invalid glob at char {}, {}", position, reason),
+        }
+    }
+}
+
+impl Error for StringUtilsError {}
@@ -0,0 +1,260 @@
+//! Substring search in a slice of chars with the Two-Way algorithm.
+//!
+//! The Two-Way algorithm of Crochemore and Perrin, the same one that the Std
+//! uses for `str::find()`, searches in linear time O(n + m) and in constant
+//! extra memory. The pattern is preprocessed once into a `CharFinder`, that
+//! can be reused for many searches of the same pattern, in the same or in
+//! different haystacks.
+
+use std::cmp;
+
+use crate::str_utils::StringUtils;
+
+/// A unit of storage of one char, a char or a narrower integer for a text
+/// whose chars all fit in it.
+pub(crate) trait CharUnit: Copy {
+    fn to_char(self) -> char;
+    /// The char has to fit in the unit.
+    fn from_char(c: char) -> Self;
+}
+
+impl CharUnit for char {
+    #[inline]
+    fn to_char(self) -> char {
+        self
+    }
+
+    #[inline]
+    fn from_char(c: char) -> char {
+        c
+    }
+}
+
+impl CharUnit for u8 {
+    #[inline]
+    fn to_char(self) -> char {
+        char::from(self)
+    }
+
+    #[inline]
+    fn from_char(c: char) -> u8 {
+        c as u8
+    }
+}
+
+impl CharUnit for u16 {
+    #[inline]
+    fn to_char(self) -> char {
+        // A u16 unit never has a surrogate, the chars are all in the BMP.
+        char::from_u32(u32::from(self)).unwrap_or(char::REPLACEMENT_CHARACTER)
+    }
+
+    #[inline]
+    fn from_char(c: char) -> u16 {
+        c as u16
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CharFinder {
+    needle: Vec<char>,
+    // Critical position, the needle is matched from here to the end and
+    // then from here to the start.
+    crit_pos: usize,
+    // Shift after a full match of the right part, when it's periodic, or
+    // after a mismatch in the left part.
+    period: usize,
+    // The needle is periodic, so the matched prefix can be remembered
+    // between shifts, to keep the search linear.
+    periodic: bool,
+}
+
+impl CharFinder {
+    pub fn new(needle: &[char]) -> CharFinder {
+        if needle.is_empty() {
+            return CharFinder { needle: Vec::new(), crit_pos: 0, period: 1, periodic: false };
+        }
+        let (crit_pos_false, period_false) = maximal_suffix(needle, false);
+        let (crit_pos_true, period_true) = maximal_suffix(needle, true);
+        let (crit_pos, period) = if crit_pos_false > crit_pos_true {
+            (crit_pos_false, period_false)
+        } else {
+            (crit_pos_true, period_true)
+        };
+
+        if needle[..crit_pos] == needle[period..period + crit_pos] {
+            CharFinder { needle: needle.to_vec(), crit_pos, period, periodic: true }
+        } else {
+            // The period is long, so a lower bound is used.
+            let period = cmp::max(crit_pos, needle.len() - crit_pos) + 1;
+            CharFinder { needle: needle.to_vec(), crit_pos, period, periodic: false }
+        }
+    }
+
+    /// The pattern is normalized like in `StringUtils::get_vec_chars()`.
+    pub fn new_str(p_str: &str) -> CharFinder {
+        CharFinder::new(&p_str.get_vec_chars())
+    }
+
+    #[inline]
+    pub fn needle(&self) -> &[char] {
+        &self.needle
+    }
+
+    /// Returns a None or the index of the first match.
+    pub fn find(&self, haystack: &[char]) -> Option<usize> {
+        self.find_at(haystack, 0, None)
+    }
+
+    /// Returns a None or the index of the first match that starts between
+    /// start_pos and end_pos inclusive, like `StringUtilsVecCharsV2::find_vec()`
+    /// but without panics, a start_pos after the end returns None.
+    /// An empty pattern never matches.
+    pub fn find_at(&self, haystack: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_units_at(haystack, start_pos, end_pos)
+    }
+
+    // Like `find_at()` over a haystack of chars stored in other units, like
+    // the bytes of a Latin-1 text.
+    pub(crate) fn find_units_at<T: CharUnit>(&self, haystack: &[T], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let needle = &self.needle[..];
+        if needle.is_empty() || needle.len() > haystack.len() {
+            return None;
+        }
+        let mut last_start = haystack.len() - needle.len();
+        if let Some(end_pos_val) = end_pos {
+            last_start = cmp::min(last_start, end_pos_val);
+        }
+
+        let m = needle.len();
+        let crit_pos = self.crit_pos;
+        let mut pos = start_pos;
+        let mut memory = 0_usize;
+        while pos <= last_start {
+            let window = &haystack[pos..pos + m];
+
+            // Match the right part.
+            let start = if self.periodic { cmp::max(crit_pos, memory) } else { crit_pos };
+            let mut i = start;
+            while i < m && needle[i] == window[i].to_char() {
+                i += 1;
+            }
+            if i < m {
+                pos += i - crit_pos + 1;
+                memory = 0;
+                continue;
+            }
+
+            // Match the left part.
+            let memory_limit = if self.periodic { memory } else { 0 };
+            let mut j = crit_pos;
+            while j > memory_limit && needle[j - 1] == window[j - 1].to_char() {
+                j -= 1;
+            }
+            if j <= memory_limit {
+                return Some(pos);
+            }
+
+            pos += self.period;
+            if self.periodic {
+                memory = m - self.period;
+            }
+        }
+        None
+    }
+
+    /// Iterator over the indexes of the non overlapping matches.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [char]) -> CharFindIter<'a> {
+        CharFindIter { finder: self, haystack, next_start_pos: 0 }
+    }
+
+    // Returns a None or the index of the first match in a haystack split in
+    // chunks, the first chunk starts at start_pos and the match has to start
+    // at or before max_start. Each chunk is searched in place, only the
+    // matches that cross two chunks are searched in a small buffer with the
+    // last chars of the previous chunks.
+    pub(crate) fn find_in_chunks<'a>(&self, chunks: impl Iterator<Item = &'a [char]>, start_pos: usize, max_start: usize) -> Option<usize> {
+        let carry_len = self.needle.len().saturating_sub(1);
+        let mut carry: Vec<char> = Vec::with_capacity(2 * carry_len);
+        let mut pos = start_pos;
+        for chunk in chunks {
+            if !carry.is_empty() {
+                let take = carry_len.min(chunk.len());
+                let carry_start = pos - carry.len();
+                carry.extend_from_slice(&chunk[..take]);
+                if let Some(index) = self.find(&carry) {
+                    let index = carry_start + index;
+                    return if index <= max_start { Some(index) } else { None };
+                }
+                carry.truncate(carry.len() - take);
+            }
+            if pos > max_start {
+                return None;
+            }
+            if let Some(index) = self.find(chunk) {
+                let index = pos + index;
+                return if index <= max_start { Some(index) } else { None };
+            }
+            if chunk.len() >= carry_len {
+                carry.clear();
+                carry.extend_from_slice(&chunk[(chunk.len() - carry_len)..]);
+            } else {
+                carry.extend_from_slice(chunk);
+                let excess = carry.len().saturating_sub(carry_len);
+                carry.drain(..excess);
+            }
+            pos += chunk.len();
+        }
+        None
+    }
+}
+
+pub struct CharFindIter<'a> {
+    finder: &'a CharFinder,
+    haystack: &'a [char],
+    next_start_pos: usize,
+}
+
+impl Iterator for CharFindIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = self.finder.find_at(self.haystack, self.next_start_pos, None)?;
+        self.next_start_pos = index + self.finder.needle.len();
+        Some(index)
+    }
+}
+
+// Computes the critical position and the period of the maximal suffix of
+// arr, in the order < ( order_greater == false ) or in the order > .
+fn maximal_suffix(arr: &[char], order_greater: bool) -> (usize, usize) {
+    let mut left = 0;
+    let mut right = 1;
+    let mut offset = 0;
+    let mut period = 1;
+
+    while let Some(&a) = arr.get(right + offset) {
+        let b = arr[left + offset];
+        if (a < b && !order_greater) || (a > b && order_greater) {
+            // The suffix is smaller, the period is the entire prefix so far.
+            right += offset + 1;
+            offset = 0;
+            period = right - left;
+        } else if a == b {
+            // Advance through the repetition of the current period.
+            if offset + 1 == period {
+                right += offset + 1;
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+        } else {
+            // The suffix is larger, start over from the current location.
+            left = right;
+            right += 1;
+            offset = 0;
+            period = 1;
+        }
+    }
+    (left, period)
+}
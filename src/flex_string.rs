@@ -0,0 +1,532 @@
+//! The `FlexString` type, chars stored in 1, 2 or 4 bytes each.
+//!
+//! Like the strings of CPython since PEP 393, the width of the storage is
+//! the width of the widest char of the text: 1 byte per char when all the
+//! chars are Latin-1 ( up to U+00FF ), 2 bytes when they are all in the BMP
+//! ( up to U+FFFF ) and 4 bytes otherwise. So an ASCII text takes 1/4 of the
+//! memory of a `Vec<char>`, with the same O(1) random access by char index.
+//! The width is upgraded when a wider char is inserted, all the positions
+//! in the methods are char positions.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::ops::{Range, RangeBounds};
+
+use crate::aho_corasick::MatchKind;
+use crate::error::StringUtilsError;
+use crate::finder::{CharFinder, CharUnit};
+use crate::str_utils::{self, StringUtils};
+use crate::vec_chars::{self, StringUtilsVecCharsV2};
+
+#[derive(Clone)]
+enum FlexData {
+    Latin1(Vec<u8>),
+    Ucs2(Vec<u16>),
+    Ucs4(Vec<char>),
+}
+
+#[derive(Clone)]
+pub struct FlexString {
+    data: FlexData,
+}
+
+// Runs the body with units bound to the Vec of units of the data, of any
+// width.
+macro_rules! match_units {
+    ($data:expr, $units:ident => $body:expr) => {
+        match $data {
+            FlexData::Latin1($units) => $body,
+            FlexData::Ucs2($units) => $body,
+            FlexData::Ucs4($units) => $body,
+        }
+    };
+}
+
+// The number of bytes needed to store the char.
+#[inline]
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        _ => 4,
+    }
+}
+
+fn max_width(chars: &[char]) -> usize {
+    chars.iter().map(|c| char_width(*c)).max().unwrap_or(1)
+}
+
+fn convert_units<T: CharUnit, U: CharUnit>(units: &[T]) -> Vec<U> {
+    units.iter().map(|unit| U::from_char(unit.to_char())).collect()
+}
+
+fn splice_units<T: CharUnit>(units: &mut Vec<T>, range: Range<usize>, chars: &[char]) {
+    units.splice(range, chars.iter().map(|c| T::from_char(*c)));
+}
+
+// The units with the match_len units at each index replaced by chars.
+fn replace_units<T: CharUnit>(units: &[T], indexes: &[usize], match_len: usize, chars: &[char]) -> Vec<T> {
+    let mut target_units: Vec<T> = Vec::with_capacity(units.len() + indexes.len() * chars.len());
+    let mut last_index = 0_usize;
+    for index in indexes {
+        target_units.extend_from_slice(&units[last_index..*index]);
+        target_units.extend(chars.iter().map(|c| T::from_char(*c)));
+        last_index = index + match_len;
+    }
+    target_units.extend_from_slice(&units[last_index..]);
+    target_units
+}
+
+impl FlexString {
+    pub fn new() -> FlexString {
+        FlexString { data: FlexData::Latin1(Vec::new()) }
+    }
+
+    /// The chars are stored with the width of the widest one.
+    pub fn from_chars(chars: &[char]) -> FlexString {
+        let data = match max_width(chars) {
+            1 => FlexData::Latin1(convert_units(chars)),
+            2 => FlexData::Ucs2(convert_units(chars)),
+            _ => FlexData::Ucs4(chars.to_vec()),
+        };
+        FlexString { data }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        match_units!(&self.data, units => units.len())
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of bytes per char, 1, 2 or 4.
+    #[inline]
+    pub fn width(&self) -> usize {
+        match self.data {
+            FlexData::Latin1(_) => 1,
+            FlexData::Ucs2(_) => 2,
+            FlexData::Ucs4(_) => 4,
+        }
+    }
+
+    /// The number of bytes of the chars.
+    pub fn size_in_bytes(&self) -> usize {
+        self.len() * self.width()
+    }
+
+    /// Returns the char at the position index, at O(1).
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<char> {
+        match_units!(&self.data, units => units.get(index).map(|unit| unit.to_char()))
+    }
+
+    /// Like `get()` but panics if index is out of bounds.
+    pub fn char_at(&self, index: usize) -> char {
+        match self.get(index) {
+            Some(c) => c,
+            None => panic!("Error: In char_at() {}.", StringUtilsError::IndexOutOfBounds { index, len: self.len() }),
+        }
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        (0..self.len()).map(move |index| self.char_at(index))
+    }
+
+    pub fn to_vec_chars(&self) -> Vec<char> {
+        match_units!(&self.data, units => convert_units(units))
+    }
+
+    pub fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        buf.extend(self.chars());
+        buf
+    }
+
+    /// Returns a new FlexString with the chars of range, with the same width.
+    /// Panics if the range is invalid or out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> FlexString {
+        let range = self.checked_range(range, "slice");
+        let data = match &self.data {
+            FlexData::Latin1(units) => FlexData::Latin1(units[range].to_vec()),
+            FlexData::Ucs2(units) => FlexData::Ucs2(units[range].to_vec()),
+            FlexData::Ucs4(units) => FlexData::Ucs4(units[range].to_vec()),
+        };
+        FlexString { data }
+    }
+
+    // Upgrades the storage to at least width bytes per char.
+    fn widen(& mut self, width: usize) {
+        if width <= self.width() {
+            return;
+        }
+        let data = mem::replace(&mut self.data, FlexData::Latin1(Vec::new()));
+        self.data = match (data, width) {
+            (FlexData::Latin1(units), 2) => FlexData::Ucs2(convert_units(&units)),
+            (FlexData::Latin1(units), _) => FlexData::Ucs4(convert_units(&units)),
+            (FlexData::Ucs2(units), _) => FlexData::Ucs4(convert_units(&units)),
+            (data, _) => data,
+        };
+    }
+
+    // Replaces the chars of range with chars, upgrading the width if needed.
+    fn splice(& mut self, range: Range<usize>, chars: &[char]) {
+        self.widen(max_width(chars));
+        match_units!(&mut self.data, units => splice_units(units, range, chars))
+    }
+
+    fn checked_range(&self, range: impl RangeBounds<usize>, method_name: &str) -> Range<usize> {
+        match str_utils::check_range(&range, self.len()) {
+            Ok(range) => range,
+            Err(error) => panic!("Error: In {}() {}.", method_name, error),
+        }
+    }
+
+    // Like `CharFinder::find_at()` over the units. A pattern with a char
+    // wider than the storage can't match.
+    fn find_units(&self, finder: &CharFinder, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        if max_width(finder.needle()) > self.width() {
+            return None;
+        }
+        match_units!(&self.data, units => finder.find_units_at(units, start_pos, end_pos))
+    }
+
+    // The start of the non overlapping matches that are all inside of range.
+    fn find_all_in(&self, pattern: &[char], range: Range<usize>) -> Vec<usize> {
+        let mut indexes_vec: Vec<usize> = Vec::new();
+        if pattern.is_empty() || pattern.len() > range.len() {
+            return indexes_vec;
+        }
+        let finder = CharFinder::new(pattern);
+        let last_start = range.end - pattern.len();
+        let mut start_pos = range.start;
+        while let Some(index) = self.find_units(&finder, start_pos, Some(last_start)) {
+            indexes_vec.push(index);
+            start_pos = index + pattern.len();
+        }
+        indexes_vec
+    }
+
+    // The pieces between the matches, like `split_vec()` .
+    fn split_pieces(&self, at_pattern: &[char], range: Range<usize>) -> Vec<FlexString> {
+        let indexes_vec = self.find_all_in(at_pattern, range.clone());
+        let mut res_vec: Vec<FlexString> = Vec::new();
+        if indexes_vec.is_empty() {
+            return res_vec;
+        }
+        let mut last_index = range.start;
+        for index in indexes_vec.iter().copied().chain(std::iter::once(range.end)) {
+            if index > last_index {
+                res_vec.push(self.slice(last_index..index));
+            }
+            last_index = index + at_pattern.len();
+        }
+        res_vec
+    }
+
+
+    // The methods of `StringUtilsVecCharsV2`, with the same semantics.
+
+    pub fn join_vec(p_vec_vec_chars: &[&[char]]) -> FlexString {
+        FlexString::from_chars(&p_vec_vec_chars.concat())
+    }
+
+    pub fn join_str(p_vec_str: &[&str]) -> FlexString {
+        let mut flex_string = FlexString::new();
+        for str_tmp in p_vec_str {
+            flex_string.push_str(str_tmp);
+        }
+        flex_string
+    }
+
+    pub fn eq_vec(&self, other: &[char]) -> bool {
+        self.len() == other.len() && self.chars().eq(other.iter().copied())
+    }
+
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.chars().eq(p_str.chars())
+    }
+
+    pub fn push_vec(& mut self, p_vec_chars: &[char]) {
+        self.splice(self.len()..self.len(), p_vec_chars);
+    }
+
+    pub fn push_str(& mut self, p_str: &str) {
+        self.push_vec(&p_str.get_vec_chars());
+    }
+
+    pub fn push_str_start(& mut self, p_str: &str) {
+        self.push_vec_start(&p_str.get_vec_chars());
+    }
+
+    pub fn push_vec_start(& mut self, other_vec: &[char]) {
+        self.splice(0..0, other_vec);
+    }
+
+    pub fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError> {
+        self.insert_vec(&p_str.get_vec_chars(), at_pos)
+    }
+
+    pub fn insert_vec(& mut self, other_vec: &[char], at_pos: usize) -> Result<(), StringUtilsError> {
+        if at_pos >= self.len() {
+            return Err(StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        self.splice(at_pos..at_pos, other_vec);
+        Ok(())
+    }
+
+    pub fn trim_start(& mut self) {
+        let count = self.chars().take_while(|c| c.is_whitespace()).count();
+        self.splice(0..count, &[]);
+    }
+
+    pub fn trim_end(& mut self) {
+        let count = (0..self.len()).rev().take_while(|index| self.char_at(*index).is_whitespace()).count();
+        self.splice((self.len() - count)..self.len(), &[]);
+    }
+
+    pub fn trim(& mut self) {
+        self.trim_end();
+        self.trim_start();
+    }
+
+    /// Panics if start_pos or end_pos are out of bounds or if end_pos < start_pos.
+    pub fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_find_vec(p_vec_chars, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In find_vec() {}.", error),
+        }
+    }
+
+    /// Like `find_vec()` but returns an error instead of panicking.
+    pub fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let Some(end_pos_val) = vec_chars::check_find_args(self.len(), p_vec_chars, start_pos, end_pos)? else {
+            return Ok(None);
+        };
+        Ok(self.find_units(&CharFinder::new(p_vec_chars), start_pos, Some(end_pos_val)))
+    }
+
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_vec(&p_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    pub fn contains_vec(& self, p_vec_chars: &[char]) -> bool {
+        self.find_vec(p_vec_chars, 0, None).is_some()
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.contains_vec(&p_str.get_vec_chars())
+    }
+
+    pub fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        pattern_vec_chars.len() <= self.len()
+            && pattern_vec_chars.iter().enumerate().all(|(index, c)| self.char_at(index) == *c)
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        self.start_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    pub fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        let Some(offset) = self.len().checked_sub(pattern_vec_chars.len()) else {
+            return false;
+        };
+        pattern_vec_chars.iter().enumerate().all(|(index, c)| self.char_at(offset + index) == *c)
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        self.ends_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In replace_vec() {}.", error),
+        }
+    }
+
+    /// Like `replace_vec()` but returns an error instead of panicking.
+    pub fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let res = self.try_find_vec(match_pattern_vec, start_pos, end_pos)?;
+        if let Some(index) = res {
+            self.splice(index..(index + match_pattern_vec.len()), replace_pattern_vec);
+        }
+        Ok(res)
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.replace_vec(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize> {
+        let indexes_vec = self.find_all_in(match_pattern_vec, 0..self.len());
+        if indexes_vec.is_empty() {
+            return None;
+        }
+        self.widen(max_width(replace_pattern_vec));
+        let match_len = match_pattern_vec.len();
+        match_units!(&mut self.data, units => {
+            *units = replace_units(units, &indexes_vec, match_len, replace_pattern_vec);
+        });
+        Some(indexes_vec.len())
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        self.replace_vec_all(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars())
+    }
+
+    /// Like `StringUtilsVecCharsV2::split_vec()`, the pieces are new
+    /// FlexStrings.
+    pub fn split_vec(& self, at_pattern_vec: &[char]) -> Vec<FlexString> {
+        self.split_pieces(at_pattern_vec, 0..self.len())
+    }
+
+    pub fn split_str(& self, at_pattern_str: &str) -> Vec<FlexString> {
+        self.split_vec(&at_pattern_str.get_vec_chars())
+    }
+
+    /// Returns a None or the index of the first match inside of range.
+    pub fn find_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        let range = self.checked_range(range, "find_in");
+        if pattern.is_empty() || pattern.len() > range.len() {
+            return None;
+        }
+        let last_start = range.end - pattern.len();
+        self.find_units(&CharFinder::new(pattern), range.start, Some(last_start))
+    }
+
+    /// Returns the number of non overlapping matches inside of range.
+    pub fn count_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> usize {
+        let range = self.checked_range(range, "count_in");
+        self.find_all_in(pattern, range).len()
+    }
+
+    /// Returns a None or the index of the first replace inside of range.
+    pub fn replace_in(& mut self, match_pattern: &[char], replace_pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        let index = self.find_in(match_pattern, range)?;
+        self.splice(index..(index + match_pattern.len()), replace_pattern);
+        Some(index)
+    }
+
+    /// Like `split_vec()` but only of the chars inside of range.
+    pub fn split_in(& self, at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<FlexString> {
+        let range = self.checked_range(range, "split_in");
+        self.split_pieces(at_pattern, range)
+    }
+
+    /// Like `StringUtilsVecCharsV2::map_str()`, the result is stored with the
+    /// width of its widest char.
+    pub fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize> {
+        let mut vec_chars = self.to_vec_chars();
+        let res = vec_chars.map_str(map);
+        *self = FlexString::from_chars(&vec_chars);
+        res
+    }
+
+    /// Like `StringUtilsVecCharsV2::map_str_with()` .
+    pub fn map_str_with(& mut self, map: &[(&str, &str)], match_kind: MatchKind) -> HashMap<String, usize> {
+        let mut vec_chars = self.to_vec_chars();
+        let res = vec_chars.map_str_with(map, match_kind);
+        *self = FlexString::from_chars(&vec_chars);
+        res
+    }
+}
+
+impl Default for FlexString {
+    fn default() -> FlexString {
+        FlexString::new()
+    }
+}
+
+
+// Conversions.
+
+impl From<&str> for FlexString {
+    /// The chars are normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> FlexString {
+        FlexString::from_chars(&p_str.get_vec_chars())
+    }
+}
+
+impl From<String> for FlexString {
+    fn from(string: String) -> FlexString {
+        FlexString::from(string.as_str())
+    }
+}
+
+impl From<&[char]> for FlexString {
+    fn from(chars: &[char]) -> FlexString {
+        FlexString::from_chars(chars)
+    }
+}
+
+impl From<Vec<char>> for FlexString {
+    fn from(vec_chars: Vec<char>) -> FlexString {
+        FlexString::from_chars(&vec_chars)
+    }
+}
+
+impl From<&FlexString> for Vec<char> {
+    fn from(flex_string: &FlexString) -> Vec<char> {
+        flex_string.to_vec_chars()
+    }
+}
+
+impl From<&FlexString> for String {
+    fn from(flex_string: &FlexString) -> String {
+        flex_string.chars().collect()
+    }
+}
+
+impl PartialEq for FlexString {
+    /// Equal chars, even if stored with different widths.
+    fn eq(&self, other: &FlexString) -> bool {
+        self.len() == other.len() && self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for FlexString {}
+
+impl PartialEq<str> for FlexString {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for FlexString {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl fmt::Display for FlexString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FlexString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        f.write_char('"')?;
+        for c in self.chars() {
+            for c_esc in c.escape_debug() {
+                f.write_char(c_esc)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
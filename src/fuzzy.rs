@@ -0,0 +1,242 @@
+//! Approximate substring search, with a max number of edits.
+//!
+//! The `CharFuzzyFinder` uses the bit-parallel algorithm of Myers, with the
+//! pattern in blocks of 64 chars, so the edit distance of the pattern to the
+//! best substring that ends at each position of the text is computed in
+//! O(n * m / 64) for n chars of text and m chars of pattern. The edits are
+//! the ones of Levenshtein, to insert, to delete and to substitute a char.
+//! The start of a match is found afterwards, with a small dynamic program
+//! over the chars before its end.
+
+use std::collections::HashMap;
+
+use crate::str_utils::StringUtils;
+
+/// An approximate match at `start..end`, with the number of edits from the
+/// pattern to the chars of the match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub start: usize,
+    pub end: usize,
+    pub distance: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct CharFuzzyFinder {
+    pattern: Vec<char>,
+    max_edits: usize,
+    // The bit mask of the positions of each char in the pattern, with 64
+    // positions in each block.
+    peq: HashMap<char, Vec<u64>>,
+    num_blocks: usize,
+    // The bit of the last position of the pattern in the last block.
+    last_bit: u64,
+}
+
+// The vertical deltas of the column of the Myers algorithm, as bit masks of
+// +1 and -1, and the distance at the last row.
+#[derive(Clone)]
+struct Column {
+    pv: Vec<u64>,
+    mv: Vec<u64>,
+    score: usize,
+}
+
+impl CharFuzzyFinder {
+    pub fn new(pattern: &[char], max_edits: usize) -> CharFuzzyFinder {
+        let num_blocks = pattern.len().div_ceil(64);
+        let mut peq: HashMap<char, Vec<u64>> = HashMap::new();
+        for (index, c) in pattern.iter().enumerate() {
+            peq.entry(*c).or_insert_with(|| vec![0; num_blocks])[index / 64] |= 1 << (index % 64);
+        }
+        let last_bit = if pattern.is_empty() { 0 } else { 1 << ((pattern.len() - 1) % 64) };
+        CharFuzzyFinder { pattern: pattern.to_vec(), max_edits, peq, num_blocks, last_bit }
+    }
+
+    /// The pattern is normalized like in `StringUtils::get_vec_chars()`.
+    pub fn new_str(p_str: &str, max_edits: usize) -> CharFuzzyFinder {
+        CharFuzzyFinder::new(&p_str.get_vec_chars(), max_edits)
+    }
+
+    #[inline]
+    pub fn pattern(&self) -> &[char] {
+        &self.pattern
+    }
+
+    #[inline]
+    pub fn max_edits(&self) -> usize {
+        self.max_edits
+    }
+
+    /// Returns a None or the best match, with the fewest edits, and from
+    /// those the one that ends first.
+    pub fn find(&self, haystack: &[char]) -> Option<FuzzyMatch> {
+        self.find_at(haystack, 0, None)
+    }
+
+    /// Like `find()` but the match starts between start_pos and end_pos
+    /// inclusive, without panics, a start_pos after the end returns None.
+    /// An empty pattern never matches.
+    pub fn find_at(&self, haystack: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        if self.pattern.is_empty() {
+            return None;
+        }
+        let end_pos = end_pos.unwrap_or(usize::MAX);
+        let mut column = self.new_column();
+        let mut best: Option<FuzzyMatch> = None;
+        for index in start_pos..haystack.len() {
+            if self.starts_after(index + 1, end_pos) {
+                break;
+            }
+            self.advance(&mut column, haystack[index]);
+            if column.score <= self.max_edits && best.is_none_or(|best| column.score < best.distance) {
+                let fuzzy_match = self.alignment(haystack, start_pos, index + 1, column.score);
+                if fuzzy_match.start <= end_pos {
+                    best = Some(fuzzy_match);
+                    if column.score == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Returns the non overlapping matches, from the start to the end. Each
+    /// match is the first one that ends with at most max_edits, extended
+    /// while the edits don't increase.
+    pub fn find_all(&self, haystack: &[char]) -> Vec<FuzzyMatch> {
+        self.find_all_at(haystack, 0, None)
+    }
+
+    /// Like `find_all()` but the matches start between start_pos and
+    /// end_pos inclusive.
+    pub fn find_all_at(&self, haystack: &[char], start_pos: usize, end_pos: Option<usize>) -> Vec<FuzzyMatch> {
+        let mut res_matches = Vec::new();
+        if self.pattern.is_empty() {
+            return res_matches;
+        }
+        let end_pos = end_pos.unwrap_or(usize::MAX);
+        let mut pos = start_pos;
+        'search: while pos < haystack.len() {
+            // A new search after each match, so they don't overlap.
+            let mut column = self.new_column();
+            let mut index = pos;
+            let mut found: Option<(usize, usize)> = None;
+            while index < haystack.len() {
+                if self.starts_after(index + 1, end_pos) {
+                    break 'search;
+                }
+                self.advance(&mut column, haystack[index]);
+                index += 1;
+                if column.score <= self.max_edits {
+                    let mut best_end = (index, column.score);
+                    while index < haystack.len() {
+                        let mut next_column = column.clone();
+                        self.advance(&mut next_column, haystack[index]);
+                        if next_column.score > best_end.1 {
+                            break;
+                        }
+                        column = next_column;
+                        index += 1;
+                        best_end = (index, column.score);
+                    }
+                    found = Some(best_end);
+                    break;
+                }
+            }
+            let Some((end, distance)) = found else {
+                break;
+            };
+            let fuzzy_match = self.alignment(haystack, pos, end, distance);
+            if fuzzy_match.start > end_pos {
+                break;
+            }
+            res_matches.push(fuzzy_match);
+            pos = end;
+        }
+        res_matches
+    }
+
+    fn new_column(&self) -> Column {
+        Column { pv: vec![!0; self.num_blocks], mv: vec![0; self.num_blocks], score: self.pattern.len() }
+    }
+
+    // Any match that ends at end starts after end_pos, it has at most the
+    // len of the pattern plus max_edits chars.
+    #[inline]
+    fn starts_after(&self, end: usize, end_pos: usize) -> bool {
+        end.saturating_sub(self.pattern.len() + self.max_edits) > end_pos
+    }
+
+    // Advances the column by the char c of the text, the row 0 is always 0,
+    // so a match can start anywhere.
+    fn advance(&self, column: &mut Column, c: char) {
+        let eq_blocks = self.peq.get(&c);
+        let mut h_in: i8 = 0;
+        for block in 0..self.num_blocks {
+            let eq = eq_blocks.map_or(0, |eq_blocks| eq_blocks[block]);
+            let high_bit = if block + 1 == self.num_blocks { self.last_bit } else { 1 << 63 };
+            h_in = advance_block(&mut column.pv[block], &mut column.mv[block], eq, h_in, high_bit);
+        }
+        column.score = column.score.wrapping_add_signed(isize::from(h_in));
+    }
+
+    // The match that ends at end with distance edits, with the start
+    // between min_start and end with the same distance, and from those the
+    // one with the len closest to the len of the pattern, the longest one
+    // when tied.
+    fn alignment(&self, haystack: &[char], min_start: usize, end: usize, distance: usize) -> FuzzyMatch {
+        let m = self.pattern.len();
+        let window_start = end.saturating_sub(m + distance).max(min_start);
+        let n = end - window_start;
+        // The distances of the reversed pattern to the reversed chars that
+        // end at end, one for each len.
+        let mut row: Vec<usize> = (0..=n).collect();
+        for (i, pattern_c) in self.pattern.iter().rev().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for len in 1..=n {
+                let cost = usize::from(*pattern_c != haystack[end - len]);
+                let value = (diagonal + cost).min(row[len] + 1).min(row[len - 1] + 1);
+                diagonal = row[len];
+                row[len] = value;
+            }
+        }
+        let len = (0..=n).rev()
+            .filter(|len| row[*len] == distance)
+            .min_by_key(|len| len.abs_diff(m))
+            .unwrap_or(0);
+        FuzzyMatch { start: end - len, end, distance }
+    }
+}
+
+// Advances a block of 64 rows by one column, with the horizontal delta h_in
+// at the top. Returns the horizontal delta at the row of high_bit.
+fn advance_block(pv: &mut u64, mv: &mut u64, eq: u64, h_in: i8, high_bit: u64) -> i8 {
+    let mut eq = eq;
+    let xv = eq | *mv;
+    if h_in < 0 {
+        eq |= 1;
+    }
+    let xh = ((eq & *pv).wrapping_add(*pv) ^ *pv) | eq;
+    let mut ph = *mv | !(xh | *pv);
+    let mut mh = *pv & xh;
+    let h_out = if ph & high_bit != 0 {
+        1
+    } else if mh & high_bit != 0 {
+        -1
+    } else {
+        0
+    };
+    ph <<= 1;
+    mh <<= 1;
+    if h_in < 0 {
+        mh |= 1;
+    } else if h_in > 0 {
+        ph |= 1;
+    }
+    *pv = mh | !(xv | ph);
+    *mv = ph & xv;
+    h_out
+}
@@ -0,0 +1,461 @@
+//! The `CharGapBuffer` type, a string of chars for editing at a cursor.
+//!
+//! The chars are kept in one `Vec<char>` with a gap of free space at the
+//! cursor, so an insert or a delete at the cursor is O(1) amortized, and
+//! moving the cursor costs only the chars between the old and the new
+//! position. The indexes skip over the gap, so they are the same as the
+//! indexes of the `Vec<char>` with the same chars.
+
+use std::fmt;
+use std::ops::{Index, IndexMut, Range, RangeBounds};
+
+use crate::error::StringUtilsError;
+use crate::finder::CharFinder;
+use crate::str_utils::{self, StringUtils};
+use crate::vec_chars;
+
+// The min size of the gap after the buffer grows.
+const MIN_GAP_LEN: usize = 64;
+
+// The char in the unused positions of the gap.
+const GAP_CHAR: char = '\0';
+
+#[derive(Clone, Default)]
+pub struct CharGapBuffer {
+    buf: Vec<char>,
+    // The gap is buf[gap_start..gap_end], the cursor is at gap_start.
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl CharGapBuffer {
+    pub fn new() -> CharGapBuffer {
+        CharGapBuffer { buf: Vec::new(), gap_start: 0, gap_end: 0 }
+    }
+
+    pub fn with_capacity(capacity: usize) -> CharGapBuffer {
+        CharGapBuffer { buf: vec![GAP_CHAR; capacity], gap_start: 0, gap_end: capacity }
+    }
+
+    /// A new buffer with the chars, and the cursor at the end.
+    pub fn from_chars(chars: &[char]) -> CharGapBuffer {
+        let mut gap_buffer = CharGapBuffer::with_capacity(chars.len() + MIN_GAP_LEN);
+        gap_buffer.insert_vec(chars);
+        gap_buffer
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.gap_len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    #[inline]
+    fn buf_index(&self, index: usize) -> usize {
+        if index < self.gap_start { index } else { index + self.gap_len() }
+    }
+
+    /// The chars before and the chars after the cursor.
+    pub fn as_slices(&self) -> (&[char], &[char]) {
+        (&self.buf[..self.gap_start], &self.buf[self.gap_end..])
+    }
+
+    pub fn get(&self, index: usize) -> Option<char> {
+        if index < self.len() {
+            Some(self.buf[self.buf_index(index)])
+        } else {
+            None
+        }
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        let (before, after) = self.as_slices();
+        before.iter().chain(after.iter()).copied()
+    }
+
+    pub fn to_vec_chars(&self) -> Vec<char> {
+        let (before, after) = self.as_slices();
+        [before, after].concat()
+    }
+
+    pub fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        buf.extend(self.chars());
+        buf
+    }
+
+
+    // The cursor.
+
+    /// The position of the cursor, between 0 and len.
+    #[inline]
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// Moves the cursor to pos, in O(distance of the move).
+    /// Panics if pos > len.
+    pub fn set_cursor(& mut self, pos: usize) {
+        if pos > self.len() {
+            panic!("Error: In set_cursor() {}.", StringUtilsError::IndexOutOfBounds { index: pos, len: self.len() });
+        }
+        if pos < self.gap_start {
+            let count = self.gap_start - pos;
+            self.buf.copy_within(pos..self.gap_start, self.gap_end - count);
+            self.gap_start -= count;
+            self.gap_end -= count;
+        } else if pos > self.gap_start {
+            let count = pos - self.gap_start;
+            self.buf.copy_within(self.gap_end..(self.gap_end + count), self.gap_start);
+            self.gap_start += count;
+            self.gap_end += count;
+        }
+    }
+
+    /// Moves the cursor one char to the left, returns false at the start.
+    pub fn cursor_left(& mut self) -> bool {
+        if self.gap_start == 0 {
+            return false;
+        }
+        self.set_cursor(self.gap_start - 1);
+        true
+    }
+
+    /// Moves the cursor one char to the right, returns false at the end.
+    pub fn cursor_right(& mut self) -> bool {
+        if self.gap_start == self.len() {
+            return false;
+        }
+        self.set_cursor(self.gap_start + 1);
+        true
+    }
+
+
+    // The edits at the cursor.
+
+    // Grows the buffer so that the gap has at least len chars.
+    fn reserve_gap(& mut self, len: usize) {
+        if self.gap_len() >= len {
+            return;
+        }
+        let new_gap_len = len.max(self.len()).max(MIN_GAP_LEN);
+        let after_len = self.buf.len() - self.gap_end;
+        let mut new_buf: Vec<char> = Vec::with_capacity(self.len() + new_gap_len);
+        new_buf.extend_from_slice(&self.buf[..self.gap_start]);
+        new_buf.resize(self.gap_start + new_gap_len, GAP_CHAR);
+        new_buf.extend_from_slice(&self.buf[self.gap_end..]);
+        self.buf = new_buf;
+        self.gap_end = self.buf.len() - after_len;
+    }
+
+    /// Inserts c at the cursor, the cursor goes after it.
+    pub fn insert(& mut self, c: char) {
+        self.reserve_gap(1);
+        self.buf[self.gap_start] = c;
+        self.gap_start += 1;
+    }
+
+    /// Inserts the chars at the cursor, the cursor goes after them.
+    pub fn insert_vec(& mut self, chars: &[char]) {
+        self.reserve_gap(chars.len());
+        self.buf[self.gap_start..(self.gap_start + chars.len())].copy_from_slice(chars);
+        self.gap_start += chars.len();
+    }
+
+    /// The chars are normalized like in `StringUtils::get_vec_chars()`.
+    pub fn insert_str(& mut self, p_str: &str) {
+        self.insert_vec(&p_str.get_vec_chars());
+    }
+
+    /// Deletes the char before the cursor, like a backspace.
+    pub fn delete_back(& mut self) -> Option<char> {
+        if self.gap_start == 0 {
+            return None;
+        }
+        self.gap_start -= 1;
+        Some(self.buf[self.gap_start])
+    }
+
+    /// Deletes the char after the cursor, like a delete.
+    pub fn delete_forward(& mut self) -> Option<char> {
+        if self.gap_end == self.buf.len() {
+            return None;
+        }
+        self.gap_end += 1;
+        Some(self.buf[self.gap_end - 1])
+    }
+
+    /// Replaces the chars of range with chars. The cursor keeps its place in
+    /// the text, or goes to the start of the range if it was inside of it.
+    /// Panics if the range is invalid or out of bounds.
+    pub fn replace_range(& mut self, range: impl RangeBounds<usize>, chars: &[char]) {
+        let range = self.checked_range(range, "replace_range");
+        let new_cursor = moved_cursor(self.cursor(), &range, chars.len());
+        self.splice_at(range, chars);
+        self.set_cursor(new_cursor);
+    }
+
+    // Replaces the chars of range, the cursor goes after the new chars.
+    fn splice_at(& mut self, range: Range<usize>, chars: &[char]) {
+        self.set_cursor(range.end);
+        self.gap_start = range.start;
+        self.insert_vec(chars);
+    }
+
+    fn checked_range(&self, range: impl RangeBounds<usize>, method_name: &str) -> Range<usize> {
+        match str_utils::check_range(&range, self.len()) {
+            Ok(range) => range,
+            Err(error) => panic!("Error: In {}() {}.", method_name, error),
+        }
+    }
+
+
+    // The methods of `StringUtilsVecCharsV2`, with the same semantics.
+    // The search goes over the two sides of the gap, without moving it.
+
+    pub fn eq_vec(&self, other: &[char]) -> bool {
+        let (before, after) = self.as_slices();
+        self.len() == other.len() && other.starts_with(before) && other.ends_with(after)
+    }
+
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.chars().eq(p_str.chars())
+    }
+
+    /// Panics if start_pos or end_pos are out of bounds or if end_pos < start_pos.
+    pub fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_find_vec(p_vec_chars, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In find_vec() {}.", error),
+        }
+    }
+
+    /// Like `find_vec()` but returns an error instead of panicking.
+    pub fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let Some(end_pos_val) = vec_chars::check_find_args(self.len(), p_vec_chars, start_pos, end_pos)? else {
+            return Ok(None);
+        };
+        Ok(self.find_from(&CharFinder::new(p_vec_chars), start_pos, end_pos_val))
+    }
+
+    // The chars from start_pos, as the one or two slices around the gap.
+    fn find_from(&self, finder: &CharFinder, start_pos: usize, max_start: usize) -> Option<usize> {
+        let (before, after) = self.as_slices();
+        let chunks: [&[char]; 2] = if start_pos < before.len() {
+            [&before[start_pos..], after]
+        } else {
+            [&after[(start_pos - before.len())..], &[]]
+        };
+        finder.find_in_chunks(chunks.into_iter(), start_pos, max_start)
+    }
+
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_vec(&p_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    pub fn contains_vec(& self, p_vec_chars: &[char]) -> bool {
+        self.find_vec(p_vec_chars, 0, None).is_some()
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.contains_vec(&p_str.get_vec_chars())
+    }
+
+    pub fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        pattern_vec_chars.len() <= self.len()
+            && self.chars().zip(pattern_vec_chars.iter()).all(|(c, p)| c == *p)
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        self.start_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    pub fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        pattern_vec_chars.len() <= self.len()
+            && self.chars().skip(self.len() - pattern_vec_chars.len()).eq(pattern_vec_chars.iter().copied())
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        self.ends_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In replace_vec() {}.", error),
+        }
+    }
+
+    /// Like `replace_vec()` but returns an error instead of panicking.
+    pub fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let res = self.try_find_vec(match_pattern_vec, start_pos, end_pos)?;
+        if let Some(index) = res {
+            self.replace_range(index..(index + match_pattern_vec.len()), replace_pattern_vec);
+        }
+        Ok(res)
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.replace_vec(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize> {
+        if match_pattern_vec.is_empty() {
+            return None;
+        }
+        let finder = CharFinder::new(match_pattern_vec);
+        let mut indexes_vec: Vec<usize> = Vec::new();
+        let mut start_pos = 0_usize;
+        while start_pos < self.len() {
+            match self.find_from(&finder, start_pos, self.len()) {
+                Some(index) => {
+                    indexes_vec.push(index);
+                    start_pos = index + match_pattern_vec.len();
+                },
+                None => break,
+            }
+        }
+        if indexes_vec.is_empty() {
+            return None;
+        }
+        // From the end, so the gap moves only once over the text.
+        let mut new_cursor = self.cursor();
+        for index in indexes_vec.iter().rev() {
+            let range = *index..(index + match_pattern_vec.len());
+            new_cursor = moved_cursor(new_cursor, &range, replace_pattern_vec.len());
+            self.splice_at(range, replace_pattern_vec);
+        }
+        self.set_cursor(new_cursor);
+        Some(indexes_vec.len())
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        self.replace_vec_all(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars())
+    }
+}
+
+
+// The position of the cursor after the chars of range are replaced with
+// new_len chars.
+fn moved_cursor(cursor: usize, range: &Range<usize>, new_len: usize) -> usize {
+    if cursor >= range.end {
+        cursor - range.len() + new_len
+    } else {
+        cursor.min(range.start)
+    }
+}
+
+
+// Conversions.
+
+impl From<&str> for CharGapBuffer {
+    /// The chars are normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> CharGapBuffer {
+        CharGapBuffer::from_chars(&p_str.get_vec_chars())
+    }
+}
+
+impl From<&[char]> for CharGapBuffer {
+    fn from(chars: &[char]) -> CharGapBuffer {
+        CharGapBuffer::from_chars(chars)
+    }
+}
+
+impl From<Vec<char>> for CharGapBuffer {
+    /// Reuses the allocation of the Vec, the cursor is at the end.
+    fn from(vec_chars: Vec<char>) -> CharGapBuffer {
+        let len = vec_chars.len();
+        CharGapBuffer { buf: vec_chars, gap_start: len, gap_end: len }
+    }
+}
+
+impl From<&CharGapBuffer> for Vec<char> {
+    fn from(gap_buffer: &CharGapBuffer) -> Vec<char> {
+        gap_buffer.to_vec_chars()
+    }
+}
+
+impl From<&CharGapBuffer> for String {
+    fn from(gap_buffer: &CharGapBuffer) -> String {
+        gap_buffer.chars().collect()
+    }
+}
+
+impl Index<usize> for CharGapBuffer {
+    type Output = char;
+
+    fn index(&self, index: usize) -> &char {
+        if index >= self.len() {
+            panic!("Error: In index() {}.", StringUtilsError::IndexOutOfBounds { index, len: self.len() });
+        }
+        &self.buf[self.buf_index(index)]
+    }
+}
+
+impl IndexMut<usize> for CharGapBuffer {
+    fn index_mut(&mut self, index: usize) -> &mut char {
+        if index >= self.len() {
+            panic!("Error: In index_mut() {}.", StringUtilsError::IndexOutOfBounds { index, len: self.len() });
+        }
+        let buf_index = self.buf_index(index);
+        &mut self.buf[buf_index]
+    }
+}
+
+impl PartialEq for CharGapBuffer {
+    fn eq(&self, other: &CharGapBuffer) -> bool {
+        self.len() == other.len() && self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for CharGapBuffer {}
+
+impl PartialEq<str> for CharGapBuffer {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for CharGapBuffer {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl fmt::Display for CharGapBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CharGapBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        f.write_char('"')?;
+        for c in self.chars() {
+            for c_esc in c.escape_debug() {
+                f.write_char(c_esc)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
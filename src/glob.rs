@@ -0,0 +1,220 @@
+//! Glob patterns, the wildcards of the file names, matched on chars.
+//!
+//! A `CharGlob` is translated into a `CharRegex`, so it's matched in linear
+//! time without backtracking, and each `?` is one char, even when it takes
+//! many bytes in UTF-8.
+//!
+//! The syntax:
+//!
+//! * `*` - Any sequence of chars, even an empty one.
+//! * `?` - Any char.
+//! * `[abc]`, `[a-z]` and `[!a-z]` or `[^a-z]` - A char of the class, or
+//!   not of the class, a `]` at the start is a char of the class.
+//! * `{a,b*,c}` - One of the alternatives, that can have wildcards and
+//!   other alternatives inside.
+//! * `\*` - The next char without a special meaning.
+
+use std::ops::Range;
+
+use crate::error::StringUtilsError;
+use crate::regex::CharRegex;
+use crate::str_utils::StringUtils;
+
+#[derive(Clone, Debug)]
+pub struct CharGlob {
+    glob: String,
+    case_insensitive: bool,
+    // The regex of a whole text, of a search, of a prefix and of a suffix.
+    whole_regex: CharRegex,
+    search_regex: CharRegex,
+    prefix_regex: CharRegex,
+    suffix_regex: CharRegex,
+}
+
+impl CharGlob {
+    /// Compiles the glob, normalized like in `StringUtils::get_vec_chars()`.
+    /// Returns an error with the position of the char of a syntax error.
+    pub fn new(glob: &str) -> Result<CharGlob, StringUtilsError> {
+        CharGlob::compile(glob, false)
+    }
+
+    /// Like `new()`, the chars are compared with the simple case folding,
+    /// so "*.TXT" matches "notes.txt".
+    pub fn new_case_insensitive(glob: &str) -> Result<CharGlob, StringUtilsError> {
+        CharGlob::compile(glob, true)
+    }
+
+    fn compile(glob: &str, case_insensitive: bool) -> Result<CharGlob, StringUtilsError> {
+        let glob_chars = glob.get_vec_chars();
+        let mut translator = Translator { chars: &glob_chars, pos: 0, regex: String::new() };
+        translator.translate(false)?;
+        let flags = if case_insensitive { "(?is)" } else { "(?s)" };
+        // A valid glob can still give a regex over the limits of size or of
+        // nesting of the regex crate.
+        let new_regex = |prefix: &str, suffix: &str| {
+            CharRegex::new(&format!("{}{}(?:{}){}", flags, prefix, translator.regex, suffix))
+                .map_err(|_| StringUtilsError::InvalidGlob { position: 0, reason: "glob too big or nested too deeply" })
+        };
+        Ok(CharGlob {
+            glob: glob.to_string(),
+            case_insensitive,
+            whole_regex: new_regex(r"\A", r"\z")?,
+            search_regex: new_regex("", "")?,
+            prefix_regex: new_regex(r"\A", "")?,
+            suffix_regex: new_regex("", r"\z")?,
+        })
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.glob
+    }
+
+    #[inline]
+    pub fn is_case_insensitive(&self) -> bool {
+        self.case_insensitive
+    }
+
+    /// Returns true if the glob matches all the chars.
+    pub fn matches(&self, chars: &[char]) -> bool {
+        self.whole_regex.is_match(chars)
+    }
+
+    /// Like `matches()`, p_str is normalized like in `StringUtils::get_vec_chars()`.
+    pub fn matches_str(&self, p_str: &str) -> bool {
+        self.matches(&p_str.get_vec_chars())
+    }
+
+    /// Returns true if the glob matches a prefix of the chars.
+    pub fn matches_start(&self, chars: &[char]) -> bool {
+        self.prefix_regex.is_match(chars)
+    }
+
+    /// Returns true if the glob matches a suffix of the chars.
+    pub fn matches_end(&self, chars: &[char]) -> bool {
+        self.suffix_regex.is_match(chars)
+    }
+
+    /// Returns a None or the range of the first match, the leftmost one,
+    /// with each `*` as long as possible.
+    pub fn find(&self, haystack: &[char]) -> Option<Range<usize>> {
+        self.search_regex.find(haystack)
+    }
+
+    /// Like `find()` but the match starts at or after start_pos.
+    pub fn find_at(&self, haystack: &[char], start_pos: usize) -> Option<Range<usize>> {
+        self.search_regex.find_at(haystack, start_pos)
+    }
+
+    /// Returns the ranges of the non overlapping matches, like `find()`.
+    pub fn find_all(&self, haystack: &[char]) -> Vec<Range<usize>> {
+        self.search_regex.find_iter(haystack).collect()
+    }
+}
+
+// Translates the chars of a glob into the syntax of `CharRegex`.
+struct Translator<'a> {
+    chars: &'a [char],
+    pos: usize,
+    regex: String,
+}
+
+impl Translator<'_> {
+    fn error(&self, reason: &'static str) -> StringUtilsError {
+        StringUtilsError::InvalidGlob { position: self.pos, reason }
+    }
+
+    // Translates until the end, or until the '}' of the alternatives.
+    fn translate(& mut self, in_alternatives: bool) -> Result<(), StringUtilsError> {
+        while let Some(&c) = self.chars.get(self.pos) {
+            match c {
+                '*' => {
+                    self.regex.push_str(".*");
+                    // Many '*' are the same as one.
+                    while self.chars.get(self.pos + 1) == Some(&'*') {
+                        self.pos += 1;
+                    }
+                }
+                '?' => self.regex.push('.'),
+                '[' => {
+                    self.translate_class()?;
+                    continue;
+                }
+                '{' => {
+                    let open_pos = self.pos;
+                    self.pos += 1;
+                    self.regex.push_str("(?:");
+                    self.translate(true)?;
+                    if self.chars.get(self.pos) != Some(&'}') {
+                        return Err(StringUtilsError::InvalidGlob { position: open_pos, reason: "unclosed '{'" });
+                    }
+                    self.regex.push(')');
+                }
+                ',' if in_alternatives => self.regex.push('|'),
+                '}' if in_alternatives => return Ok(()),
+                '\\' => {
+                    self.pos += 1;
+                    let escaped_c = *self.chars.get(self.pos).ok_or_else(|| self.error("incomplete escape"))?;
+                    push_literal(&mut self.regex, escaped_c);
+                }
+                c => push_literal(&mut self.regex, c),
+            }
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn translate_class(& mut self) -> Result<(), StringUtilsError> {
+        let open_pos = self.pos;
+        let unclosed = StringUtilsError::InvalidGlob { position: open_pos, reason: "unclosed '['" };
+        self.pos += 1;
+        self.regex.push('[');
+        if matches!(self.chars.get(self.pos), Some('!' | '^')) {
+            self.regex.push('^');
+            self.pos += 1;
+        }
+        let mut first = true;
+        loop {
+            let c = *self.chars.get(self.pos).ok_or(unclosed.clone())?;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let start = if c == '\\' {
+                self.pos += 1;
+                *self.chars.get(self.pos).ok_or(unclosed.clone())?
+            } else {
+                c
+            };
+            push_literal(&mut self.regex, start);
+            self.pos += 1;
+            // A range, unless the '-' is the last char of the class.
+            if self.chars.get(self.pos) == Some(&'-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                self.pos += 1;
+                let mut end = self.chars[self.pos];
+                if end == '\\' {
+                    self.pos += 1;
+                    end = *self.chars.get(self.pos).ok_or(unclosed.clone())?;
+                }
+                if end < start {
+                    return Err(self.error("range with the start greater then the end"));
+                }
+                self.regex.push('-');
+                push_literal(&mut self.regex, end);
+                self.pos += 1;
+            }
+        }
+        self.regex.push(']');
+        self.pos += 1;
+        Ok(())
+    }
+}
+
+// Pushes a char that matches only itself, the chars that are not letters
+// or digits are escaped.
+fn push_literal(regex: &mut String, c: char) {
+    if !c.is_alphanumeric() && !c.is_whitespace() {
+        regex.push('\\');
+    }
+    regex.push(c);
+}
@@ -0,0 +1,343 @@
+//! The `GraphemeString` type.
+//!
+//! A `Vec<char>` gives O(1) random access to chars, but a char is not always
+//! what the user sees as one character. The NFC normalization of
+//! `get_vec_chars()` joins the combining accents with their base letter, but
+//! flags ( regional indicators ), ZWJ emoji sequences, Hangul jamo and skin
+//! tone modifiers are still made of many chars, so reversing or slicing a
+//! `Vec<char>` can split them.
+//!
+//! `GraphemeString` segments the text in extended grapheme clusters, as in
+//! UAX #29, and keeps the byte offset of each cluster, so it gives O(1)
+//! random access in grapheme units. All the positions in the methods are
+//! grapheme positions.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, RangeBounds};
+
+use unic_segment::GraphemeIndices;
+
+use crate::error::StringUtilsError;
+use crate::normalization::Normalization;
+use crate::str_utils;
+
+#[derive(Clone)]
+pub struct GraphemeString {
+    text: String,
+    // Byte offset of the start of each grapheme plus the final text.len(),
+    // so the grapheme i is text[bounds[i]..bounds[i + 1]] .
+    bounds: Vec<usize>,
+    // The form of the text, and of the patterns of the *_str methods.
+    form: Normalization,
+}
+
+impl GraphemeString {
+    pub fn new() -> GraphemeString {
+        GraphemeString { text: String::new(), bounds: vec![0], form: Normalization::NFC }
+    }
+
+    /// The text is normalized with form, and so are the patterns of the
+    /// `*_str` methods.
+    pub fn from_str_with(p_str: &str, form: Normalization) -> GraphemeString {
+        GraphemeString::from_string(form.normalize(p_str), form)
+    }
+
+    /// The text is normalized like in `StringUtils::get_vec_chars()`.
+    fn from_str_normalized(p_str: &str) -> GraphemeString {
+        GraphemeString::from_str_with(p_str, Normalization::NFC)
+    }
+
+    fn from_string(text: String, form: Normalization) -> GraphemeString {
+        let mut bounds: Vec<usize> = GraphemeIndices::new(&text)
+            .map(|(byte_index, _)| byte_index)
+            .collect();
+        bounds.push(text.len());
+        GraphemeString { text, bounds, form }
+    }
+
+    /// The form of the text, NFC unless it was made with `from_str_with()`.
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.form
+    }
+
+    /// Number of graphemes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bounds.len() - 1
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns the grapheme at the position index, at O(1).
+    pub fn get(&self, index: usize) -> Option<&str> {
+        if index < self.len() {
+            Some(&self.text[self.bounds[index]..self.bounds[index + 1]])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.bounds.windows(2).map(move |w| &self.text[w[0]..w[1]])
+    }
+
+    pub fn get_vec_graphemes(&self) -> Vec<&str> {
+        self.iter().collect()
+    }
+
+    /// Like `StringUtils::substring()` but in graphemes and at O(1).
+    pub fn substring(&self, start: usize, len: usize) -> &str {
+        let start = start.min(self.len());
+        let end = start.saturating_add(len).min(self.len());
+        &self.text[self.bounds[start]..self.bounds[end]]
+    }
+
+    /// Like `StringUtils::slice()` but in graphemes and at O(1).
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> &str {
+        let (start, end) = str_utils::range_start_end(&range, self.len());
+        self.substring(start, end.saturating_sub(start))
+    }
+
+    /// Reverses the order of the graphemes, each grapheme keeps its chars.
+    pub fn reverse(& mut self) {
+        let mut text_tmp = String::with_capacity(self.text.len());
+        let mut bounds_tmp = Vec::with_capacity(self.bounds.len());
+        for w in self.bounds.windows(2).rev() {
+            bounds_tmp.push(text_tmp.len());
+            text_tmp.push_str(&self.text[w[0]..w[1]]);
+        }
+        bounds_tmp.push(text_tmp.len());
+        self.text = text_tmp;
+        self.bounds = bounds_tmp;
+    }
+
+    #[inline]
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.text == p_str
+    }
+
+    pub fn push_str(& mut self, p_str: &str) {
+        // A grapheme can continue over the end of the old text, like an
+        // extending accent that composes with the last char, so the joined
+        // text is normalized and segmented again.
+        let mut text_tmp = std::mem::take(&mut self.text);
+        text_tmp.push_str(p_str);
+        *self = GraphemeString::from_string(self.form.normalize(&text_tmp), self.form);
+    }
+
+    pub fn push_str_start(& mut self, p_str: &str) {
+        let mut text_tmp = p_str.to_string();
+        text_tmp.push_str(&self.text);
+        *self = GraphemeString::from_string(self.form.normalize(&text_tmp), self.form);
+    }
+
+    pub fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError> {
+        if at_pos >= self.len() {
+            return Err(StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        let byte_pos = self.bounds[at_pos];
+        let mut text_tmp = String::with_capacity(self.text.len() + p_str.len());
+        text_tmp.push_str(&self.text[..byte_pos]);
+        text_tmp.push_str(p_str);
+        text_tmp.push_str(&self.text[byte_pos..]);
+        *self = GraphemeString::from_string(self.form.normalize(&text_tmp), self.form);
+        Ok(())
+    }
+
+    /// Returns a None or the grapheme index of the first match, that starts
+    /// between start_pos and end_pos inclusive. The match has to begin and
+    /// end at grapheme boundaries, so "e" doesn't match inside "é".
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let pattern = GraphemeString::from_str_with(p_str, self.form);
+        self.find_graphemes(&pattern, start_pos, end_pos)
+    }
+
+    fn find_graphemes(& self, pattern: &GraphemeString, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        if pattern.is_empty() || pattern.len() > self.len() {
+            return None;
+        }
+        let last_pos = self.len() - pattern.len();
+        let end_pos_val = end_pos.unwrap_or(last_pos).min(last_pos);
+        if start_pos > end_pos_val {
+            return None;
+        }
+        let pattern_bytes = pattern.text.as_bytes();
+        (start_pos..=end_pos_val).find(|&i| {
+            let bytes = &self.text.as_bytes()[self.bounds[i]..self.bounds[i + pattern.len()]];
+            bytes == pattern_bytes
+        })
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.find_str(p_str, 0, None).is_some()
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        let pattern = GraphemeString::from_str_with(pattern_str, self.form);
+        pattern.len() <= self.len()
+            && self.text[..self.bounds[pattern.len()]] == pattern.text
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        let pattern = GraphemeString::from_str_with(pattern_str, self.form);
+        pattern.len() <= self.len()
+            && self.text[self.bounds[self.len() - pattern.len()]..] == pattern.text
+    }
+
+    /// Returns a None or the grapheme index of the first replace.
+    pub fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let pattern = GraphemeString::from_str_with(match_pattern_str, self.form);
+        let index = self.find_graphemes(&pattern, start_pos, end_pos)?;
+        let byte_start = self.bounds[index];
+        let byte_end = self.bounds[index + pattern.len()];
+        let mut text_tmp = String::with_capacity(self.text.len() + replace_pattern_str.len());
+        text_tmp.push_str(&self.text[..byte_start]);
+        text_tmp.extend(self.form.chars(replace_pattern_str));
+        text_tmp.push_str(&self.text[byte_end..]);
+        *self = GraphemeString::from_string(text_tmp, self.form);
+        Some(index)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        let pattern = GraphemeString::from_str_with(match_pattern_str, self.form);
+        let replace_str: String = self.form.chars(replace_pattern_str).collect();
+        let mut text_tmp = String::with_capacity(self.text.len());
+        let mut num_matches = 0_usize;
+        let mut last_index = 0_usize;
+        while let Some(index) = self.find_graphemes(&pattern, last_index, None) {
+            text_tmp.push_str(&self.text[self.bounds[last_index]..self.bounds[index]]);
+            text_tmp.push_str(&replace_str);
+            last_index = index + pattern.len();
+            num_matches += 1;
+        }
+        if num_matches == 0 {
+            return None;
+        }
+        text_tmp.push_str(&self.text[self.bounds[last_index]..]);
+        *self = GraphemeString::from_string(text_tmp, self.form);
+        Some(num_matches)
+    }
+
+    /// Splits at the pattern, like `StringUtilsVecCharsV2::split_vec()` the
+    /// empty pieces are not returned.
+    pub fn split_str(& self, at_pattern_str: &str) -> Vec<&str> {
+        let pattern = GraphemeString::from_str_with(at_pattern_str, self.form);
+        let mut res_vec: Vec<&str> = Vec::new();
+        let mut last_index = 0_usize;
+        let mut flag_found = false;
+        while let Some(index) = self.find_graphemes(&pattern, last_index, None) {
+            flag_found = true;
+            if index > last_index {
+                res_vec.push(&self.text[self.bounds[last_index]..self.bounds[index]]);
+            }
+            last_index = index + pattern.len();
+        }
+        if flag_found && last_index < self.len() {
+            res_vec.push(&self.text[self.bounds[last_index]..]);
+        }
+        res_vec
+    }
+}
+
+// The form is not compared, the text is the same whatever the form it was
+// normalized with.
+
+impl PartialEq for GraphemeString {
+    fn eq(&self, other: &GraphemeString) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for GraphemeString {}
+
+impl PartialOrd for GraphemeString {
+    fn partial_cmp(&self, other: &GraphemeString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GraphemeString {
+    fn cmp(&self, other: &GraphemeString) -> Ordering {
+        self.text.cmp(&other.text)
+    }
+}
+
+impl Hash for GraphemeString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+    }
+}
+
+impl Default for GraphemeString {
+    fn default() -> GraphemeString {
+        GraphemeString::new()
+    }
+}
+
+impl From<&str> for GraphemeString {
+    /// The text is normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> GraphemeString {
+        GraphemeString::from_str_normalized(p_str)
+    }
+}
+
+impl From<String> for GraphemeString {
+    fn from(string: String) -> GraphemeString {
+        GraphemeString::from_str_normalized(&string)
+    }
+}
+
+impl From<GraphemeString> for String {
+    fn from(grapheme_string: GraphemeString) -> String {
+        grapheme_string.text
+    }
+}
+
+impl Index<usize> for GraphemeString {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &str {
+        &self.text[self.bounds[index]..self.bounds[index + 1]]
+    }
+}
+
+impl fmt::Display for GraphemeString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.text)
+    }
+}
+
+impl fmt::Debug for GraphemeString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.text, f)
+    }
+}
+
+impl PartialEq<str> for GraphemeString {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for GraphemeString {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
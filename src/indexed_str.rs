@@ -0,0 +1,363 @@
+//! The `IndexedStr` type.
+//!
+//! A `Vec<char>` gives O(1) random access to chars at 4 bytes per char, 4
+//! times the memory of an ASCII text in UTF-8. `IndexedStr` keeps the text
+//! in UTF-8 and a sparse index with the byte offset of one char in every
+//! 64, so the byte offset of any char is found from the nearest checkpoint
+//! by walking at most 63 chars. That is O(1) with a small constant, for
+//! 1/8 of a byte of index per char. When the text is pure ASCII the char
+//! positions are the byte positions and the index is empty.
+//!
+//! All the positions in the methods are char positions.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Index, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use unic_ucd_normal::CanonicalCombiningClass;
+
+use crate::error::StringUtilsError;
+use crate::normalization::Normalization;
+use crate::str_utils;
+use crate::vec_chars;
+
+// The number of chars between two checkpoints of the index.
+const CHECKPOINT_INTERVAL: usize = 64;
+
+#[derive(Clone, Default)]
+pub struct IndexedStr {
+    text: String,
+    // Byte offset of the chars 0, 64, 128, ... , empty for an ASCII text.
+    checkpoints: Vec<usize>,
+    chars_len: usize,
+    // The form of the text, and of the patterns of the *_str methods.
+    form: Normalization,
+}
+
+// The number of bytes of the UTF-8 char that starts with the byte.
+#[inline]
+fn utf8_char_len(first_byte: u8) -> usize {
+    match first_byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+impl IndexedStr {
+    pub fn new() -> IndexedStr {
+        IndexedStr { text: String::new(), checkpoints: Vec::new(), chars_len: 0, form: Normalization::NFC }
+    }
+
+    /// The text is normalized with form, and so are the patterns of the
+    /// `*_str` methods.
+    pub fn from_str_with(p_str: &str, form: Normalization) -> IndexedStr {
+        IndexedStr::from_string(form.normalize(p_str), form)
+    }
+
+    /// The text is normalized like in `StringUtils::get_vec_chars()`.
+    fn from_str_normalized(p_str: &str) -> IndexedStr {
+        IndexedStr::from_str_with(p_str, Normalization::NFC)
+    }
+
+    fn from_string(text: String, form: Normalization) -> IndexedStr {
+        let mut indexed_str = IndexedStr { text: String::new(), checkpoints: Vec::new(), chars_len: 0, form };
+        indexed_str.append(&text);
+        indexed_str
+    }
+
+    // Appends the text and extends the index.
+    fn append(& mut self, p_str: &str) {
+        let old_byte_len = self.text.len();
+        if self.is_ascii() && p_str.is_ascii() {
+            self.text.push_str(p_str);
+            self.chars_len = self.text.len();
+            return;
+        }
+        if self.is_ascii() {
+            // The checkpoints of the ASCII text before are at byte == char.
+            self.checkpoints = (0..self.chars_len).step_by(CHECKPOINT_INTERVAL).collect();
+        }
+        self.text.push_str(p_str);
+        for (byte_index, _) in p_str.char_indices() {
+            if self.chars_len.is_multiple_of(CHECKPOINT_INTERVAL) {
+                self.checkpoints.push(old_byte_len + byte_index);
+            }
+            self.chars_len += 1;
+        }
+    }
+
+    /// The form of the text, NFC unless it was made with `from_str_with()`.
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.form
+    }
+
+    /// Number of chars.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chars_len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chars_len == 0
+    }
+
+    /// All the chars are ASCII, so the char positions are byte positions.
+    #[inline]
+    pub fn is_ascii(&self) -> bool {
+        self.chars_len == self.text.len()
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Returns the byte offset of the char at index, or the len in bytes
+    /// for an index at or after the end.
+    pub fn byte_offset(&self, index: usize) -> usize {
+        if self.is_ascii() {
+            return index.min(self.text.len());
+        }
+        if index >= self.chars_len {
+            return self.text.len();
+        }
+        let bytes = self.text.as_bytes();
+        let mut byte_index = self.checkpoints[index / CHECKPOINT_INTERVAL];
+        for _ in 0..(index % CHECKPOINT_INTERVAL) {
+            byte_index += utf8_char_len(bytes[byte_index]);
+        }
+        byte_index
+    }
+
+    /// Returns the char index of the char that starts at the byte_offset.
+    pub fn char_index(&self, byte_offset: usize) -> usize {
+        if self.is_ascii() {
+            return byte_offset.min(self.text.len());
+        }
+        if byte_offset >= self.text.len() {
+            return self.chars_len;
+        }
+        let checkpoint = self.checkpoints.partition_point(|&byte_index| byte_index <= byte_offset) - 1;
+        checkpoint * CHECKPOINT_INTERVAL
+            + self.text[self.checkpoints[checkpoint]..byte_offset].chars().count()
+    }
+
+    /// Returns the char at the position index.
+    pub fn get(&self, index: usize) -> Option<char> {
+        if index < self.chars_len {
+            self.text[self.byte_offset(index)..].chars().next()
+        } else {
+            None
+        }
+    }
+
+    pub fn chars(&self) -> std::str::Chars<'_> {
+        self.text.chars()
+    }
+
+    pub fn get_vec_chars(&self) -> Vec<char> {
+        self.text.chars().collect()
+    }
+
+    /// Like `StringUtils::substring()` but at O(1).
+    pub fn substring(&self, start: usize, len: usize) -> &str {
+        let start = start.min(self.chars_len);
+        let end = start.saturating_add(len).min(self.chars_len);
+        &self.text[self.byte_offset(start)..self.byte_offset(end)]
+    }
+
+    /// Like `StringUtils::slice()` but at O(1).
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> &str {
+        let (start, end) = str_utils::range_start_end(&range, self.chars_len);
+        self.substring(start, end.saturating_sub(start))
+    }
+
+    /// Like `StringUtils::try_substring()` but at O(1).
+    pub fn try_substring(&self, start: usize, len: usize) -> Result<&str, StringUtilsError> {
+        match start.checked_add(len) {
+            Some(end) if end <= self.chars_len => Ok(self.substring(start, len)),
+            Some(end) => Err(StringUtilsError::IndexOutOfBounds { index: end, len: self.chars_len }),
+            None => Err(StringUtilsError::IndexOutOfBounds { index: usize::MAX, len: self.chars_len }),
+        }
+    }
+
+    /// Like `StringUtils::try_slice()` but at O(1).
+    pub fn try_slice(&self, range: impl RangeBounds<usize>) -> Result<&str, StringUtilsError> {
+        let range = str_utils::check_range(&range, self.chars_len)?;
+        self.try_substring(range.start, range.len())
+    }
+
+    #[inline]
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.text == p_str
+    }
+
+    pub fn push_str(& mut self, p_str: &str) {
+        if self.form == Normalization::None || (self.is_ascii() && p_str.is_ascii()) {
+            self.append(p_str);
+            return;
+        }
+        // The chars of p_str can compose with the last chars of the text,
+        // like an accent after a letter, or be reordered with them, so the
+        // tail from the last starter is normalized again with p_str.
+        let tail_start = self.text.char_indices().rev()
+            .find(|&(_, c)| CanonicalCombiningClass::of(c).is_not_reordered())
+            .map_or(0, |(byte_index, _)| byte_index);
+        self.chars_len = self.char_index(tail_start);
+        let mut tail = self.text.split_off(tail_start);
+        self.checkpoints.truncate(self.chars_len.div_ceil(CHECKPOINT_INTERVAL));
+        if self.is_ascii() {
+            self.checkpoints.clear();
+        }
+        tail.push_str(p_str);
+        self.append(&self.form.normalize(&tail));
+    }
+
+    /// Returns a None or the char index of the first match, that starts
+    /// between start_pos and end_pos inclusive.
+    /// Like `StringUtilsVecCharsV2::find_str()`, panics if start_pos or
+    /// end_pos are out of bounds.
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let pattern_vec_chars: Vec<char> = self.form.chars(p_str).collect();
+        let end_pos_val = match vec_chars::check_find_args(self.chars_len, &pattern_vec_chars, start_pos, end_pos) {
+            Ok(Some(end_pos_val)) => end_pos_val,
+            Ok(None) | Err(StringUtilsError::EmptyPattern) => return None,
+            Err(error) => panic!("Error: In find_str() {}.", error),
+        };
+        let pattern: String = pattern_vec_chars.into_iter().collect();
+        let byte_start = self.byte_offset(start_pos);
+        let index = self.char_index(byte_start + self.text[byte_start..].find(&pattern)?);
+        if index > end_pos_val { None } else { Some(index) }
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.find_str(p_str, 0, None).is_some()
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        let pattern: String = self.form.chars(pattern_str).collect();
+        self.text.starts_with(&pattern)
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        let pattern: String = self.form.chars(pattern_str).collect();
+        self.text.ends_with(&pattern)
+    }
+}
+
+// The form is not compared, the text is the same whatever the form it was
+// normalized with.
+
+impl PartialEq for IndexedStr {
+    fn eq(&self, other: &IndexedStr) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for IndexedStr {}
+
+impl PartialOrd for IndexedStr {
+    fn partial_cmp(&self, other: &IndexedStr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IndexedStr {
+    fn cmp(&self, other: &IndexedStr) -> Ordering {
+        self.text.cmp(&other.text)
+    }
+}
+
+impl Hash for IndexedStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+    }
+}
+
+impl From<&str> for IndexedStr {
+    /// The text is normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> IndexedStr {
+        IndexedStr::from_str_normalized(p_str)
+    }
+}
+
+impl From<String> for IndexedStr {
+    fn from(string: String) -> IndexedStr {
+        IndexedStr::from_str_normalized(&string)
+    }
+}
+
+impl From<IndexedStr> for String {
+    fn from(indexed_str: IndexedStr) -> String {
+        indexed_str.text
+    }
+}
+
+impl Index<usize> for IndexedStr {
+    type Output = str;
+
+    /// The char at index, as a `str`.
+    #[inline]
+    fn index(&self, index: usize) -> &str {
+        if index >= self.chars_len {
+            panic!("Error: In index() {}.", StringUtilsError::IndexOutOfBounds { index, len: self.chars_len });
+        }
+        let byte_start = self.byte_offset(index);
+        let byte_end = byte_start + utf8_char_len(self.text.as_bytes()[byte_start]);
+        &self.text[byte_start..byte_end]
+    }
+}
+
+macro_rules! impl_index_range {
+    ($($range:ty),*) => {
+        $(
+            impl Index<$range> for IndexedStr {
+                type Output = str;
+
+                /// Panics if the range is invalid or out of bounds.
+                fn index(&self, range: $range) -> &str {
+                    match self.try_slice(range) {
+                        Ok(slice) => slice,
+                        Err(error) => panic!("Error: In index() {}.", error),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_index_range!(Range<usize>, RangeFrom<usize>, RangeFull,
+                  RangeInclusive<usize>, RangeTo<usize>, RangeToInclusive<usize>);
+
+impl fmt::Display for IndexedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.text)
+    }
+}
+
+impl fmt::Debug for IndexedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.text, f)
+    }
+}
+
+impl PartialEq<str> for IndexedStr {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for IndexedStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
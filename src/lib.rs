@@ -0,0 +1,130 @@
+/******************************************************************************
+ * Project:     Random String Access in Rust - Vec<char>
+ * Author:      João Nuno Carvalho
+ * Date:        2022.05.19
+ * Description: This small project was made to ease Text Processing in Rust for
+ *              people coming from other Programming Languages like Python.
+ *              With this traits, implemented on a program you can access
+ *              Vec<char>  at O(1) performance for Random Access and with more
+ *              ease then with normal Strings in Rust, at least for the
+ *              beginner.
+ *              I implemented in here many functions as traits over Vec<char>,
+ *              &[char], String and &str. They are implemented with somewhat
+ *              medium eye, regarding performance. They should not be the
+ *              fastest in the FarWest, except for Random Access normal Rust
+ *              String should be faster, but for the many cases they are
+ *              alright.
+ * Note:        See the README.md of the project page on my github for the
+ *              details of a couple of small function that came from example
+ *              of other developer.
+ * License:     MIT Open Source License.
+ *
+ * Have fun!
+ *****************************************************************************/
+
+//! String utilities for random access text processing over `Vec<char>`.
+//!
+//! The crate is organized in the following modules:
+//!
+//! * [`error`] - The `StringUtilsError` returned by the fallible methods.
+//! * [`str_utils`] - Helpers on `str` ( substring, slice and the conversion
+//!   to a normalized `Vec<char>` ).
+//! * [`normalization`] - The `Normalization` forms used in the conversion of
+//!   a `&str` into chars, NFC by default.
+//! * [`vec_chars`] - Helpers on `Vec<char>` ( conversion to `String`, join,
+//!   push, insert, trim, find, replace, split and map ).
+//! * [`finder`] - The `CharFinder`, a precompiled pattern for the linear
+//!   time Two-Way substring search, used by all the find methods.
+//! * [`aho_corasick`] - The `CharAhoCorasick` automaton, to find and to
+//!   replace many patterns at the same time in one pass, used by `map_str()`.
+//! * [`pattern`] - The `CharPattern` trait of the generic search methods,
+//!   implemented for a char, a sequence, a set of chars and a predicate.
+//! * [`split`] - The lazy iterators of the split family of `CharStr`, with
+//!   the semantics of the Std, and the `skip_empty()` adaptor.
+//! * [`slices`] - Helpers on `[char]` slices ( conversion to `String` and to
+//!   `Vec<char>` ).
+//! * [`char_string`] - The owned `CharString` and the borrowed `CharStr`
+//!   types, a newtype over `Vec<char>` with all the methods as inherent
+//!   methods.
+//! * [`rope`] - The `CharRope` type, a balanced tree of chunks of chars,
+//!   with O(log n) insert, remove, slice and random access for large texts.
+//! * [`gap_buffer`] - The `CharGapBuffer` type, with a gap at the cursor
+//!   for O(1) amortized inserts and deletes where the edits happen.
+//! * [`indexed_str`] - The `IndexedStr` type, UTF-8 text with a sparse
+//!   index of char positions, for O(1) char access without `Vec<char>`.
+//! * [`flex_string`] - The `FlexString` type, chars stored in 1, 2 or 4
+//!   bytes each like the strings of CPython, with O(1) random access.
+//! * [`grapheme_string`] - The `GraphemeString` type, random access in
+//!   grapheme clusters, so flags and emoji sequences are never split.
+//! * [`general_category`] - The Unicode `GeneralCategory` of the chars.
+//! * [`case_mapping`] - The `StringUtilsCase` trait, the full Unicode
+//!   uppercase, lowercase, titlecase and case folding, with the tailoring
+//!   of a `CaseLocale`.
+//! * [`py_str`] - The `PyStrMethods` trait, the methods of the `str` of
+//!   Python ( count, center, partition, splitlines, title, isalpha, ... ).
+//! * [`translation`] - The `TranslationTable` of `translate()`, that maps
+//!   each char to a char, to many chars or deletes it, in one pass.
+//! * [`match_options`] - The `MatchOptions` of the `_with` search methods,
+//!   to match without regard to the case, to the normalization or to the
+//!   accents.
+//! * [`regex`] - The `CharRegex`, regular expressions compiled to a
+//!   Thompson NFA that match on chars and report char positions.
+//! * [`glob`] - The `CharGlob`, the wildcards `*`, `?`, `[a-z]` and `{a,b}`
+//!   of the file names, matched on chars.
+//! * [`fuzzy`] - The `CharFuzzyFinder`, the approximate search with a max
+//!   number of edits, with the bit-parallel algorithm of Myers.
+//! * [`similarity`] - The distances and similarities of two sequences,
+//!   Levenshtein, Damerau-Levenshtein, Hamming, Jaro-Winkler, the longest
+//!   common subsequence and substring and the n-gram similarities.
+//!
+//! All the traits and types are re-exported at the crate root, so a simple
+//! `use string_utils::*;` brings every method into scope.
+
+pub mod error;
+pub mod str_utils;
+pub mod normalization;
+pub mod vec_chars;
+pub mod finder;
+pub mod aho_corasick;
+pub mod pattern;
+pub mod split;
+pub mod slices;
+pub mod char_string;
+pub mod rope;
+pub mod gap_buffer;
+pub mod indexed_str;
+pub mod flex_string;
+pub mod grapheme_string;
+pub mod general_category;
+pub mod case_mapping;
+pub mod py_str;
+pub mod translation;
+pub mod match_options;
+pub mod regex;
+pub mod glob;
+pub mod fuzzy;
+pub mod similarity;
+
+pub use error::StringUtilsError;
+pub use str_utils::StringUtils;
+pub use normalization::Normalization;
+pub use vec_chars::{StringUtilsVecChars, StringUtilsVecCharsV2};
+pub use finder::CharFinder;
+pub use aho_corasick::{CharAhoCorasick, CharMatch, MatchKind};
+pub use pattern::{AnyOf, CharPattern};
+pub use split::SkipEmptyExt;
+pub use slices::StringUtilsSlices;
+pub use char_string::{CharStr, CharString};
+pub use rope::CharRope;
+pub use gap_buffer::CharGapBuffer;
+pub use indexed_str::IndexedStr;
+pub use flex_string::FlexString;
+pub use grapheme_string::GraphemeString;
+pub use general_category::GeneralCategory;
+pub use case_mapping::{CaseLocale, StringUtilsCase};
+pub use py_str::PyStrMethods;
+pub use translation::TranslationTable;
+pub use match_options::MatchOptions;
+pub use regex::{CharCaptures, CharCapturesIter, CharRegex, CharRegexMatches};
+pub use glob::CharGlob;
+pub use fuzzy::{CharFuzzyFinder, FuzzyMatch};
@@ -2,130 +2,34 @@
  * Project:     Random String Access in Rust - Vec<char>
  * Author:      João Nuno Carvalho
  * Date:        2022.05.19
- * Description: This small project was made to ease Text Processing in Rust for
- *              people coming from other Programming Languages like Python.
- *              With this traits, implemented on a program you can access
- *              Vec<char>  at O(1) performance for Random Access and with more
- *              ease then with normal Strings in Rust, at least for the
- *              beginner.
- *              I implemented in here many functions as traits over Vec<char>,
- *              &[char], String and &str. They are implemented with somewhat
- *              medium eye, regarding performance. They should not be the
- *              fastest in the FarWest, except for Random Access normal Rust
- *              String should be faster, but for the many cases they are
- *              alright.
- * Note:        See the README.md of the project page on my github for the 
- *              details of a couple of small function that came from example
- *              of other developer.
+ * Description: Demo and tests of the string_utils library. It shows the use
+ *              of the traits over Vec<char>, &[char], String and &str that
+ *              the library exports.
  * License:     MIT Open Source License.
- * 
- * Have fun! 
+ *
+ * Have fun!
  *****************************************************************************/
 
-use core::panic;
-use std::ops::{Bound, RangeBounds};
-use std::iter;
-use std::mem;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-extern crate unic_normal;
-use unic_normal::StrNormalForm;
-
-trait StringUtils {
-    fn substring(&self, start: usize, len: usize) -> &str;
-    fn slice(&self, range: impl RangeBounds<usize>) -> &str;
-    fn get_vec_chars(&self) -> Vec<char>;
-}
-
-impl StringUtils for str {
-    fn substring(&self, start: usize, len: usize) -> &str {
-        let mut char_pos = 0;
-        let mut byte_start = 0;
-        let mut it = self.chars();
-        loop {
-            if char_pos == start { break; }
-            if let Some(c) = it.next() {
-                char_pos += 1;
-                byte_start += c.len_utf8();
-            }
-            else { break; }
-        }
-        char_pos = 0;
-        let mut byte_end = byte_start;
-        loop {
-            if char_pos == len { break; }
-            if let Some(c) = it.next() {
-                char_pos += 1;
-                byte_end += c.len_utf8();
-            }
-            else { break; }
-        }
-        &self[byte_start..byte_end]
-    }
-
-    fn slice(&self, range: impl RangeBounds<usize>) -> &str {
-        let start = match range.start_bound() {
-            Bound::Included(bound) | Bound::Excluded(bound) => *bound,
-            Bound::Unbounded => 0,
-        };
-        let len = match range.end_bound() {
-            Bound::Included(bound) => *bound + 1,
-            Bound::Excluded(bound) => *bound,
-            Bound::Unbounded => self.len(),
-        } - start;
-        self.substring(start, len)
-    }
-
-    fn get_vec_chars(&self) -> Vec<char> {
-        // return self.chars().collect();
-        let tmp_str = self.nfc().collect::<String>();
-        tmp_str.chars().collect()
-    }
-}
-
-trait StringUtilsVecChars {
-    fn to_string(&self) -> String;
-    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
-}
-
-impl StringUtilsVecChars for Vec<char> {
-    fn to_string(&self) -> String { 
-        self.iter().collect()
-    }
-
-    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
-        buf.clear();
-        for c in self.iter() {
-            buf.push(*c);
-        }
-        buf
-    }
-}
-
-trait StringUtilsSlices {
-    fn to_string(&self) -> String;
-    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
-    fn to_vec_chars(&self) -> Vec<char>;
-}
-
-impl StringUtilsSlices for [char] {
-    fn to_string(&self) -> String {
-        self.iter().collect()
-    }
-
-    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
-        buf.clear();
-        for c in self.iter() {
-            buf.push(*c);
-        }
-        buf
-    }
-
-    fn to_vec_chars(&self) -> Vec<char> {
-        let vec_chars = self.iter().copied().collect();
-        vec_chars
-    }
-}
+use string_utils::{StringUtils, StringUtilsVecChars, StringUtilsSlices,
+                   StringUtilsVecCharsV2};
+use string_utils::{CharStr, CharString, GraphemeString, CharRope, CharGapBuffer};
+use string_utils::{IndexedStr, FlexString};
+use string_utils::Normalization;
+use string_utils::CharFinder;
+use string_utils::{CharAhoCorasick, CharMatch, MatchKind};
+use string_utils::StringUtilsError;
+use string_utils::AnyOf;
+use string_utils::SkipEmptyExt;
+use string_utils::{GeneralCategory, PyStrMethods};
+use string_utils::TranslationTable;
+use string_utils::{CaseLocale, StringUtilsCase};
+use string_utils::MatchOptions;
+use string_utils::{CharGlob, CharRegex};
+use string_utils::{CharFuzzyFinder, FuzzyMatch};
+use string_utils::similarity;
 
 fn main() {
     let s = "abcdèfghij";
@@ -160,13 +64,13 @@ fn main() {
     print!("{}, ",  vc[..5].to_string_buf(& mut buf));
     print!("{}, ",  vc[..].to_string_buf(& mut buf));
     print!("{}, ",  vc[3..8].to_string_buf(& mut buf));
-    print!("{}.\n", vc[3..].to_string_buf(& mut buf));
+    println!("{}.", vc[3..].to_string_buf(& mut buf));
     
     // Random access to the Vec<char>. 
     for i in 0..(vc.len() - 2) {
         print!("{} ", vc[i..i+3].to_string_buf(& mut buf));
     }
-    println!("");
+    println!();
     
     // Random modifications to the Vec<char>.
     for i in (0..(vc.len() / 3) + 1).rev() {
@@ -177,453 +81,92 @@ fn main() {
 
     // Test trait StringUtilsVecCharsV2 for Vec<char> .
     test_vec_char_methods();
-}
 
-/*
-    Output:
-       abcdè, abcdèfghij, dèfgh, dèfghij.
-       abcdè, abcdèfghij, dèfgh, dèfghij.
-       abcdè, abcdèfghij, dèfgh, dèfghij.
-       abcdè, abcdèfghij, dèfgh, dèfghij.
-       abcdè, abcdèfghij, dèfgh, dèfghij.
-       abc bcd cdè dèf èfg fgh ghi hij 
-       #abc#dèf#ghi#j
-       #abc#dèf#ghi#j
-*/
+    // Test the types CharString and CharStr .
+    test_char_string_methods();
 
+    // Test the type GraphemeString .
+    test_grapheme_string_methods();
 
-trait StringUtilsVecCharsV2 {
-    // fn to_string(&self) -> String;
-    // fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
-    
-    fn join_vec(p_vec_vec_chars: &[&[char]]) -> Vec<char>;
-    fn join_str(p_vec_str: &[&str]) -> Vec<char>;
- 
-    fn eq_vec(&self, other: &[char]) -> bool;
-    fn eq_str(&self, p_str: &str) -> bool;
-
-    fn push_vec(& mut self, p_vec_chars: &[char]);
-    fn push_str(& mut self, p_str: &str);
-    fn push_str_start(& mut self, p_str: &str);
-    fn push_vec_start(& mut self, other_vec: &Vec<char>);
-    fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), String>;
-    fn insert_vec(& mut self, other_vec: &Vec<char>, at_pos: usize) -> Result<(), String>;
-
-    fn trim_start(& mut self);
-    fn trim_end(& mut self);
-    fn trim(& mut self);
-
-    fn find_vec(& self, p_vec_chars: &Vec<char>, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
-    fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
-
-    fn contains_vec(& self, p_vec_chars: &Vec<char>) -> bool;
-    fn contains_str(& self, p_str: &str) -> bool;
-
-    fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool;
-    fn start_with_str(& self, pattern_str: &str) -> bool;
-    fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool;
-    fn ends_with_str(& self, pattern_str: &str) -> bool;
-
-    /// Returns a None or the index of the first replace.
-    fn replace_vec(& mut self, match_pattern_vec: &Vec<char>, replace_pattern_vec: &Vec<char>, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
-    /// Returns a None or the index of the first replace.
-    fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
-    
-    /// Returns a None or the number of replaces.
-    fn replace_vec_all(& mut self, match_pattern_vec: &Vec<char>, replace_pattern_vec: &Vec<char>) -> Option<usize>;
-    /// Returns a None or the number of replaces.
-    fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize>;
-    
-    fn split_vec(& self, at_pattern: &Vec<char>) -> Vec<&[char]>;        
-    fn split_str(& self, at_pattern_str: &str) -> Vec<&[char]>;
+    // Test the selectable Normalization forms.
+    test_normalization();
 
-    fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize>;
-}
+    // Test the Two-Way substring search of CharFinder .
+    test_char_finder();
 
-impl StringUtilsVecCharsV2 for Vec<char> {
-    
-/*
-    fn to_string(&self) -> String { 
-        self.iter().collect()
-    }
+    // Test the multi pattern replace of CharAhoCorasick .
+    test_aho_corasick();
 
-    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
-        buf.clear();
-        for c in self.iter() {
-            buf.push(*c);
-        }
-        buf
-    }
-*/
+    // Test the StringUtilsError and the try_ methods.
+    test_errors();
 
-    fn join_vec(p_vec_vec_chars: &[&[char]]) -> Vec<char> {
-        // Calculate the total length of the strings.
-        let mut capacity = 0_usize;
-        for src_vec_chars_tmp in p_vec_vec_chars {
-            capacity += src_vec_chars_tmp.len();
-        }
-        // Allocate the memory on the heap.
-        let mut vec_chars: Vec<char> = Vec::with_capacity(capacity);
-        for src_vec_chars_tmp in p_vec_vec_chars {
-            vec_chars.push_vec(src_vec_chars_tmp);
-        }
-        vec_chars
-    }
+    // Test the range variants find_in(), count_in(), replace_in() and split_in() .
+    test_range_methods();
 
-    fn join_str(p_vec_str: &[&str]) -> Vec<char> {
-        // Calculate the total length of the strings.
-        let mut capacity = 0_usize;
-        for str_tmp in p_vec_str {
-            for _ in str_tmp.chars() {
-                capacity += 1;
-            }
-        }
-        // Allocate the memory on the heap.
-        let mut vec_chars: Vec<char> = Vec::with_capacity(capacity);
-        for str_tmp in p_vec_str {
-            vec_chars.push_str(str_tmp);
-        }
-        vec_chars
-    } 
+    // Test the generic CharPattern methods.
+    test_char_pattern_methods();
 
-    #[inline]
-    fn eq_vec(&self, other: &[char]) -> bool {
-        self.len() == other.len() && iter::zip(self, other).all(|(a, b)| *a == *b)
-    }
+    // Test the lazy split iterators.
+    test_split_iterators();
 
-    #[inline]
-    fn eq_str(&self, p_str: &str) -> bool {
-        self.len() == p_str.chars().count() && iter::zip(self, p_str.chars()).all(|(a, b)| *a == b)
-    }
+    // Test the CharRope type.
+    test_char_rope();
 
-    fn push_vec(& mut self, p_vec_chars: &[char]) {
-        self.extend(p_vec_chars);
-    }
-  
-    fn push_str(& mut self, p_str: &str) {
-        let vec_chars = p_str.get_vec_chars();
-        self.extend(vec_chars);
-    }
-    
-    fn push_str_start(& mut self, p_str: &str) {              
-        let mut vec_chars = p_str.get_vec_chars();
-        vec_chars.extend(self.iter());
-        let _ = mem::replace(self, vec_chars);
-    }
+    // Test the CharGapBuffer type.
+    test_char_gap_buffer();
 
-    fn push_vec_start(& mut self, other_vec: &Vec<char>) {
-        let mut vec_tmp = other_vec.clone();
-        vec_tmp.extend(self.iter());
-        let _ = mem::replace(self, vec_tmp);
-    }
+    // Test the IndexedStr type.
+    test_indexed_str();
 
-    fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), String> {
-        if at_pos >= self.len() {
-            return Err("Error: In insert_str(), parameter at_pos is greater then sel.len() - 1".to_string());
-        }
-        let vec_t1: Vec<char> = p_str.get_vec_chars();
-        let mut vec_tmp: Vec<char> = Vec::with_capacity(self.len() + vec_t1.len());
-        vec_tmp.extend(self[..at_pos].iter());
-        vec_tmp.extend(vec_t1.iter());
-        vec_tmp.extend(self[at_pos..].iter());
-        let _ = mem::replace(self, vec_tmp);
-        Ok(())
-    }
+    // Test the FlexString type.
+    test_flex_string();
 
-    fn insert_vec(& mut self, other_vec: &Vec<char>, at_pos: usize) -> Result<(), String> {
-        
-        if at_pos >= self.len() {
-            return Err("Error: In insert_str(), parameter at_pos is greater then sel.len() - 1".to_string());
-        }
-        //let mut vec_t1: Vec<char> = p_str.chars().collect();
-        let mut vec_tmp: Vec<char> = Vec::with_capacity(self.len() + other_vec.len());
-        vec_tmp.extend(self[..at_pos].iter());
-        vec_tmp.extend(other_vec.iter());
-        vec_tmp.extend(self[at_pos..].iter());
-        let _ = mem::replace(self, vec_tmp);
-        Ok(())
-    }
+    // Test the Python like indexing and slicing.
+    test_py_slicing();
 
-    fn trim_start(& mut self) {
-        if self.is_empty() {
-            return;
-        }
-        let mut start_index = 0;
-        let mut i = 0;
-        while i < self.len() && self[i].is_whitespace() {
-            start_index = i;
-            i += 1;
-        }
-        if i < self.len() - 1 {
-            self.copy_within((start_index + 1).., 0);
-            for _ in 0..i {
-                self.pop();
-            }
-        } else {
-            self.clear();
-        }
-    }
+    // Test the methods of the Python str.
+    test_py_str_methods();
 
-    fn trim_end(& mut self) {
-        let mut i = self.len() as i32 - 1;
-        while i >= 0 && self[i as usize].is_whitespace() {
-            let _ = self.pop();
-            i -= 1;
-        }
-    }
+    // Test the TranslationTable type.
+    test_translation_table();
 
-    fn trim(& mut self) {
-        let mut vec_tmp: Vec<char> = Vec::with_capacity(self.len());
-        let mut flag_starting_white_spaces = true;
-        for c in self.iter() {
-            if !c.is_whitespace() {
-                flag_starting_white_spaces = false;
-            }
-            if !flag_starting_white_spaces {
-                vec_tmp.push(*c);
-            }
-        }
-        vec_tmp.trim_end();
-        let _ = mem::replace(self, vec_tmp);
-    }
-
-
-    fn find_vec(& self, p_vec_chars: &Vec<char>, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
-        if self.is_empty() {
-            return None;
-        }
-        if start_pos >= self.len() {
-            panic!("Error: In find_str() parameter start_pos must not be greater then Vec<char>.len() - 1 .");
-        }
-        let end_pos_val = if let Some(val) = end_pos {
-                if val >= self.len() {
-                    panic!("Error: In find_str() parameter end_pos must not be greater then Vec<char>.len() - 1 .");
-                }
-                if val < start_pos {
-                    panic!("Error: In find_str() parameter end_pos cannot be lower then parameter start_pos.");
-                } 
-                val
-            } else {
-                self.len() - 1
-            };
-        if p_vec_chars.is_empty() {
-            return None;
-        }
-        // let pattern_vec: Vec<char> = p_str.chars().collect(); 
-        let pattern_vec = p_vec_chars; 
-        if  pattern_vec.len() + start_pos > self.len() {
-            return None;
-        }
-
-        // Find pattern inside string.
-        let match_pos: usize;
-        // let mut flag_match = false;
-        for i in start_pos..=end_pos_val {
-            let mut counter = pattern_vec.len();
-            let mut offset = 0_usize;
-            for c in pattern_vec.iter() {
-                if self[i + offset] != *c {
-                    break;
-                }
-                offset += 1;
-                counter -= 1
-            }
-            if counter == 0 {
-                // flag_match = true;
-                match_pos = i;
-                return Some(match_pos);
-            }
-        }
-        None
-    }
-
-    fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
-        let pattern_vec_chars: Vec<char> = p_str.get_vec_chars(); 
-        self.find_vec(&pattern_vec_chars, start_pos, end_pos)
-    }
-
-    fn contains_vec(& self, p_vec_chars: &Vec<char>) -> bool {
-        if self.find_vec(p_vec_chars, 0, None).is_some() {
-            return true;
-        }
-        false
-    }
-
-    fn contains_str(& self, p_str: &str) -> bool {
-        let vec_chars = p_str.get_vec_chars();
-        self.contains_vec(&vec_chars)
-    }
-
-
-
-    fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
-        if pattern_vec_chars.len() > self.len() {
-            return false;
-        }
-        self.starts_with(pattern_vec_chars)
-    }
-
-
-    fn start_with_str(& self, pattern_str: &str) -> bool {
-        let pattern_vec_chars = pattern_str.get_vec_chars();
-        self.start_with_vec(&pattern_vec_chars)
-    }
-
-    fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
-        if pattern_vec_chars.len() > self.len() {
-            return false;
-        }
-        self.ends_with(pattern_vec_chars)
-    }
-
-    fn ends_with_str(& self, pattern_str: &str) -> bool {
-        let pattern_vec_chars = pattern_str.get_vec_chars();
-        self.ends_with_vec(&pattern_vec_chars)
-    }
-
-
-
-    /// Returns a None or the index of the first replace.
-    fn replace_vec(& mut self, match_pattern_vec: &Vec<char>, replace_pattern_vec: &Vec<char>, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
-        let res = self.find_vec(match_pattern_vec, start_pos, end_pos);
-        if let Some(index) = res {
-            for _ in 0..(match_pattern_vec.len()) {
-                self.remove(index);
-            }
-            let _ = self.insert_vec(replace_pattern_vec, index);
-            return Some(index);
-        }
-        None
-    }
+    // Test the trim family with patterns.
+    test_trim_matches();
 
-    /// Returns a None or the index of the first replace.
-    fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
-        let match_pattern_vec = match_pattern_str.get_vec_chars();
-        let replace_pattern_vec = replace_pattern_str.get_vec_chars();
-        self.replace_vec(&match_pattern_vec, &replace_pattern_vec, start_pos, end_pos)
-    }
-
-    /// Returns a None or the number of replaces.
-    fn replace_vec_all(& mut self, match_pattern_vec: &Vec<char>, replace_pattern_vec: &Vec<char>) -> Option<usize> {
-        let mut flag_ended_find = false;
-        let mut next_start_pos = 0_usize;
-        let mut indexes_vec: Vec<usize> = Vec::new(); 
-        // Find, from start to end, the indexes of the machs. Put's them on a Vec.
-        while !flag_ended_find {
-            if next_start_pos >= self.len() {
-                // flag_ended_find = true;
-                break;
-            }
-            let res = self.find_vec(match_pattern_vec, next_start_pos, None);
-            if let Some(index) = res {
-                indexes_vec.push(index);
-                next_start_pos = index + match_pattern_vec.len();                
-            } else {
-                flag_ended_find = true;
-            }
-        }
-        // Case where it didn't found any match, it will exit earlier.
-        if indexes_vec.is_empty() {
-            return None;
-        }
+    // Test the Unicode case conversions.
+    test_case_mapping();
 
-        // We will copy to a new Vec<char> the data and do the replacement when coping.
-        let num_matches = indexes_vec.len();
-        let capacity = self.len() - num_matches * match_pattern_vec.len() + num_matches * replace_pattern_vec.len(); 
-        let mut target_vec_chars: Vec<char> = Vec::with_capacity(capacity);
-        let mut last_index = 0_usize;
-        for (counter,index) in indexes_vec.iter().enumerate() {
-            // Copy the first chars before the first match.
-            if last_index < self.len() {
-                target_vec_chars.push_vec(&self[last_index..*index]);
-                target_vec_chars.push_vec(replace_pattern_vec);
-                last_index = index + match_pattern_vec.len();
-                if counter == indexes_vec.len() - 1 && last_index < self.len() {
-                    target_vec_chars.push_vec(&self[last_index..]);
-                }
-            }
-        }
+    // Test the search without regard to the case or to the normalization.
+    test_match_options();
 
-        // Copia a zona de memoria do src para o target self.
-        let _ = mem::replace(self, target_vec_chars);
+    // Test the search without regard to the accents.
+    test_diacritics();
 
-        Some(num_matches)
-    }
+    // Test the regular expressions on chars.
+    test_regex();
 
-    /// Returns a None or the number of replaces.
-    fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
-        self.replace_vec_all(&match_pattern_str.get_vec_chars(),
-                           &replace_pattern_str.get_vec_chars())
-    }
+    // Test the glob patterns.
+    test_glob();
 
+    // Test the approximate search with a max number of edits.
+    test_fuzzy_search();
 
-
-    fn split_vec(& self, at_pattern_vec: &Vec<char>) -> Vec<&[char]> {
-        let match_pattern_vec = at_pattern_vec;
-        let mut flag_ended_find = false;
-        let mut next_start_pos = 0_usize;
-        let mut indexes_vec: Vec<usize> = Vec::new(); 
-        // Find, from start to end, the indexes of the machs. Put's them on a Vec.
-        while !flag_ended_find {
-            if next_start_pos >= self.len() {
-                // flag_ended_find = true;
-                break;
-            }
-            let res = self.find_vec(match_pattern_vec, next_start_pos, None);
-            if let Some(index) = res {
-                indexes_vec.push(index);
-                next_start_pos = index + match_pattern_vec.len();                
-            } else {
-                flag_ended_find = true;
-            }
-        }
-
-        let mut res_vec: Vec<&[char]> = Vec::new();
-        // Case where it didn't found any match, it will exit earlier.
-        if indexes_vec.is_empty() {
-            return res_vec;
-        }
-
-        // Join the intervals between splits that have chars, that are not the split chars.
-        let mut last_index = 0_usize;
-        for (counter, index) in indexes_vec.iter().enumerate() {
-            // Copy the first chars before the first match.
-            if last_index < self.len() {
-                let slice_tmp = &self[last_index..*index];
-                if !slice_tmp.is_empty() {
-                    res_vec.push(slice_tmp);
-                }
-                last_index = index + match_pattern_vec.len();
-                if counter == indexes_vec.len() - 1 && last_index < self.len() {
-                    let slice_tmp = &self[last_index..];
-                    if !slice_tmp.is_empty() {
-                        res_vec.push(slice_tmp);
-                    }   
-                }
-            }
-        }
-
-        res_vec
-    }
-
-    fn split_str(& self, at_pattern_str: &str) -> Vec<&[char]> {
-        self.split_vec(&at_pattern_str.get_vec_chars())
-    }
-
-    fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize> {
-        let mut res_hashmap: HashMap<String, usize> = HashMap::new();
-        for (src_str, target_str) in map.iter() {
-            let res = self.replace_str_all(src_str, target_str);
-            match res {
-                Some(num_replaces_for_seg_string) => res_hashmap.insert(src_str.to_string(), num_replaces_for_seg_string),
-                None => res_hashmap.insert(src_str.to_string(), 0_usize),
-            };
-        }
-        res_hashmap
-    }
-
+    // Test the similarity and distance metrics.
+    test_similarity();
 }
 
+/*
+    Output:
+       abcdè, abcdèfghij, dèfgh, dèfghij.
+       abcdè, abcdèfghij, dèfgh, dèfghij.
+       abcdè, abcdèfghij, dèfgh, dèfghij.
+       abcdè, abcdèfghij, dèfgh, dèfghij.
+       abcdè, abcdèfghij, dèfgh, dèfghij.
+       abc bcd cdè dèf èfg fgh ghi hij 
+       #abc#dèf#ghi#j
+       #abc#dèf#ghi#j
+*/
+
 
 // *******************************
 // *******************************
@@ -631,6 +174,22 @@ impl StringUtilsVecCharsV2 for Vec<char> {
 // *******************************
 // *******************************
 
+// The next pseudo random number of a LCG, so the random tests are the same
+// in each run.
+fn next_random(seed: &mut u64) -> u64 {
+    *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    *seed >> 33
+}
+
+// A pseudo random text of the chars of the alphabet, shorter than max_len.
+fn random_text(seed: &mut u64, alphabet: &[char], max_len: u64) -> String {
+    let mut text = String::new();
+    for _ in 0..(next_random(seed) % max_len) {
+        text.push(alphabet[next_random(seed) as usize % alphabet.len()]);
+    }
+    text
+}
+
 fn test_vec_char_methods() {
 
 
@@ -646,8 +205,10 @@ fn test_vec_char_methods() {
     assert!(vc_a.eq_str("bla"));
     assert!(!vc_a.eq_str("tu"));
 
-    assert!(vc_a.eq_str(&"bla".to_string()));
-    assert!(!vc_a.eq_str(&"tu".to_string()));
+    let string_bla = String::from("bla");
+    let string_tu = String::from("tu");
+    assert!(vc_a.eq_str(&string_bla));
+    assert!(!vc_a.eq_str(&string_tu));
 
     drop(vc_a);
     drop(vc_b);
@@ -667,16 +228,16 @@ fn test_vec_char_methods() {
 
     println!("\nReverse Vec<char> with crate unic_normal - UNIC — Unicode Normalization Forms");
 
-    println!("{} vs {}", fnc("🇸🇪"), "🇸🇪");
+    println!("{} vs 🇸🇪", fnc("🇸🇪"));
 
-    println!("{} vs {}", fnc("noçl"), "lçon");
-    println!("{} vs {}", fnc("noãl"), "lãon");
-    println!("{} vs {}", fnc("noál"), "láon");
-    println!("{} vs {}", fnc("noél"), "léon");
-    println!("{} vs {}", fnc("noíl"), "líon");
-    println!("{} vs {}", fnc("noàl"), "làon");
-    println!("{} vs {}", fnc("noâl"), "lâon");
-    println!("{} vs {}", fnc("noêl"), "lêon");
+    println!("{} vs lçon", fnc("noçl"));
+    println!("{} vs lãon", fnc("noãl"));
+    println!("{} vs láon", fnc("noál"));
+    println!("{} vs léon", fnc("noél"));
+    println!("{} vs líon", fnc("noíl"));
+    println!("{} vs làon", fnc("noàl"));
+    println!("{} vs lâon", fnc("noâl"));
+    println!("{} vs lêon", fnc("noêl"));
 
     // Print Out reverse Vec<char> .
     //
@@ -700,16 +261,16 @@ fn test_vec_char_methods() {
         s.chars().rev().collect()
     }
 
-    println!("{} vs {}", naive_reverse_string("🇸🇪"), "🇸🇪");
+    println!("{} vs 🇸🇪", naive_reverse_string("🇸🇪"));
 
-    println!("{} vs {}", naive_reverse_string("noçl"), "lçon");
-    println!("{} vs {}", naive_reverse_string("noãl"), "lãon");
-    println!("{} vs {}", naive_reverse_string("noál"), "láon");
-    println!("{} vs {}", naive_reverse_string("noél"), "léon");
-    println!("{} vs {}", naive_reverse_string("noíl"), "líon");
-    println!("{} vs {}", naive_reverse_string("noàl"), "làon");
-    println!("{} vs {}", naive_reverse_string("noâl"), "lâon");
-    println!("{} vs {}", naive_reverse_string("noêl"), "lêon");
+    println!("{} vs lçon", naive_reverse_string("noçl"));
+    println!("{} vs lãon", naive_reverse_string("noãl"));
+    println!("{} vs láon", naive_reverse_string("noál"));
+    println!("{} vs léon", naive_reverse_string("noél"));
+    println!("{} vs líon", naive_reverse_string("noíl"));
+    println!("{} vs làon", naive_reverse_string("noàl"));
+    println!("{} vs lâon", naive_reverse_string("noâl"));
+    println!("{} vs lêon", naive_reverse_string("noêl"));
 
     // Print Out reverse String .
     //
@@ -736,7 +297,7 @@ fn test_vec_char_methods() {
     // @@ Test 4 - join_str() .
     let vc_a: Vec<char> = Vec::join_str(&["bla",
                                                     "_bli",
-                                                    &"_abc".to_string(),
+                                                    String::from("_abc").as_str(),
                                                     "_blu"]);
     assert!(vc_a.eq_str("bla_bli_abc_blu"));                                                              
     drop(vc_a);
@@ -1178,4 +739,1909 @@ fn test_vec_char_methods() {
     assert!(vc_a.eq_str("Cube Cube : Foo : Bar Bar Bar : 33 : JJ"));
     drop(vc_a);
 
-} // End of function test_vec_char_methods()
\ No newline at end of file
+} // End of function test_vec_char_methods()
+
+fn test_char_string_methods() {
+
+    // @@ Test 1 - From<&str>, Display, Debug and PartialEq<str> .
+    let cs_a = CharString::from("abcdèfghij");
+    assert_eq!(cs_a.len(), 10);
+    assert_eq!(cs_a.to_string(), "abcdèfghij");
+    assert_eq!(format!("{:?}", cs_a), "\"abcdèfghij\"");
+    assert!(cs_a == "abcdèfghij");
+    assert!(cs_a != "abc");
+    drop(cs_a);
+
+
+    // @@ Test 2 - Index by a char position and by ranges, Deref to CharStr.
+    let cs_a = CharString::from("abcdèfghij");
+    assert_eq!(cs_a[4], 'è');
+    assert!(cs_a[..5] == *"abcdè");
+    assert!(cs_a[3..8] == *"dèfgh");
+    assert!(cs_a[3..=7] == *"dèfgh");
+    assert!(cs_a[3..] == *"dèfghij");
+    let char_str: &CharStr = &cs_a[3..8];
+    assert_eq!(char_str.to_string(), "dèfgh");
+    assert!(char_str.start_with_str("dè"));
+    assert!(char_str.ends_with_str("gh"));
+    assert_eq!(char_str.to_char_string(), CharString::from("dèfgh"));
+    drop(cs_a);
+
+
+    // @@ Test 3 - FromIterator, Extend, Add and AddAssign .
+    let mut cs_a: CharString = "bla".chars().collect();
+    cs_a.extend("bli".chars());
+    assert!(cs_a == "blabli");
+    let cs_b = cs_a.clone() + "blu";
+    assert!(cs_b == "blabliblu");
+    cs_a += "blu";
+    cs_a += '!';
+    assert!(cs_a == "blabliblu!");
+    cs_a += &cs_b[..3];
+    assert!(cs_a == "blabliblu!bla");
+    drop(cs_a);
+    drop(cs_b);
+
+
+    // @@ Test 4 - Hash and Ord .
+    let mut vec_cs = vec![CharString::from("c"), CharString::from("a"), CharString::from("b")];
+    vec_cs.sort();
+    assert!(vec_cs[0] == "a" && vec_cs[1] == "b" && vec_cs[2] == "c");
+    let mut map_cs: HashMap<CharString, usize> = HashMap::new();
+    map_cs.insert(CharString::from("a"), 1);
+    assert_eq!(map_cs.get(CharString::from("a").as_char_str()), Some(&1));
+    drop(vec_cs);
+    drop(map_cs);
+
+
+    // @@ Test 5 - Inherent methods from StringUtilsVecCharsV2 .
+    let mut cs_a = CharString::from("blabliblu");
+    assert_eq!(cs_a.find_str("bli", 0, None), Some(3));
+    assert!(cs_a.contains_str("blu"));
+    assert!(cs_a.start_with_str("bla"));
+    assert!(cs_a.ends_with_str("blu"));
+    assert_eq!(cs_a.replace_str_all("bl", "BL"), Some(3));
+    assert!(cs_a == "BLaBLiBLu");
+    let res_vec = cs_a.split_str("BL");
+    assert_eq!(res_vec.len(), 3);
+    assert!(res_vec[0] == "a" && res_vec[1] == "i" && res_vec[2] == "u");
+    cs_a.push_str_start("  ");
+    cs_a.push_str("  ");
+    cs_a.trim();
+    assert!(cs_a == "BLaBLiBLu");
+    assert!(cs_a.insert_str("#", 2).is_ok());
+    assert!(cs_a == "BL#aBLiBLu");
+    drop(cs_a);
+
+
+    // @@ Test 6 - The variants with MatchOptions .
+    let mut cs_a = CharString::from("Ação, AÇÃO e acao");
+    let options = MatchOptions::new().case_insensitive(true);
+    assert_eq!(cs_a.find_str_with("ação", 1, None, options), Some(6..10));
+    assert!(cs_a[11..].contains_str_with("ACAO", options));
+    assert!(!cs_a[11..].contains_str_with("ação", options));
+    let res_vec = cs_a.split_str_with("AÇÃO", options);
+    assert!(res_vec.len() == 2 && res_vec[0] == ", " && res_vec[1] == " e acao");
+    assert_eq!(cs_a.replace_str_all_with("acao", "X", options.ignore_accents(true)), Some(3));
+    assert!(cs_a == "X, X e X");
+    drop(cs_a);
+
+
+    // @@ Test 7 - eq_str_with() and strip_diacritics() .
+    let mut cs_a = CharString::from("Coração");
+    assert!(cs_a.eq_str_with("CORACAO", MatchOptions::new().case_insensitive(true).ignore_accents(true)));
+    assert!(!cs_a[..].eq_str_with("CORACAO", MatchOptions::new().case_insensitive(true)));
+    assert!(cs_a[4..].strip_diacritics() == "cao");
+    assert!(cs_a.strip_diacritics() == "Coracao" && cs_a == "Coração");
+    cs_a.strip_diacritics_in_place();
+    assert!(cs_a == "Coracao");
+    drop(cs_a);
+
+
+    // @@ Test 8 - The regex variants.
+    let mut cs_a = CharString::from("maçã=12, pêra=7");
+    let regex = CharRegex::new(r"(?<fruit>\w+)=(\d+)").unwrap();
+    assert_eq!(cs_a.find_regex(&regex, 1), Some(1..7));
+    assert_eq!(cs_a.find_iter_regex(&regex).collect::<Vec<_>>(), vec![0..7, 9..15]);
+    assert_eq!(cs_a[9..].captures(&regex).unwrap().name("fruit"), Some(0..4));
+    let res_vec = cs_a.split_regex(&CharRegex::new(r",\s*").unwrap());
+    assert!(res_vec.len() == 2 && res_vec[1] == "pêra=7");
+    assert_eq!(cs_a.replace_regex(&regex, "$2 ${fruit}"), Some(2));
+    assert!(cs_a == "12 maçã, 7 pêra");
+    drop(cs_a);
+
+
+    // @@ Test 9 - The glob variants.
+    let cs_a = CharString::from("notas/maçã.txt");
+    assert!(cs_a.matches_glob(&CharGlob::new("*/*.{txt,md}").unwrap()));
+    assert!(cs_a.start_with_glob(&CharGlob::new("not?s/").unwrap()));
+    assert!(cs_a.ends_with_glob(&CharGlob::new("ç?.txt").unwrap()));
+    let glob = CharGlob::new("[aã]").unwrap();
+    assert!(cs_a.contains_glob(&glob) && !cs_a.contains_glob(&CharGlob::new("x*y").unwrap()));
+    assert_eq!(cs_a.find_glob(&glob, 4), Some(7..8));
+    assert_eq!(cs_a[6..].find_all_glob(&glob), vec![1..2, 3..4]);
+    drop(cs_a);
+
+
+    // @@ Test 10 - The fuzzy variants.
+    let cs_a = CharString::from("o rato roeu a roupa");
+    assert_eq!(cs_a.find_fuzzy_str("ropa", 1, 0, None), Some(FuzzyMatch { start: 14, end: 19, distance: 1 }));
+    assert_eq!(cs_a.find_fuzzy(&['r', 'a', 't', 'o'], 0, 3, None), None);
+    let res_vec = cs_a.find_fuzzy_all(&"roto".get_vec_chars(), 1, 0, Some(10));
+    assert_eq!(res_vec.iter().map(|m| (m.start, m.distance)).collect::<Vec<_>>(), vec![(2, 1)]);
+    drop(cs_a);
+
+
+    // @@ Test 11 - translate()
+    let table = TranslationTable::maketrans("çã", "ca", "!").unwrap();
+    let mut cs_a = CharString::from("maçã!");
+    assert!(cs_a[..3].translate(&table) == "mac");
+    assert!(cs_a.translate(&table) == "maca" && cs_a == "maçã!");
+    cs_a.translate_in_place(&table);
+    assert!(cs_a == "maca");
+    drop(cs_a);
+
+
+    // @@ Test 12 - The *_str methods with the form of from_str_with().
+    let mut cs_a = CharString::from_str_with("cafe\u{301} cafe\u{301}", Normalization::None);
+    assert_eq!(cs_a.normalization(), Normalization::None);
+    assert_eq!(cs_a.len(), 11);
+    assert_eq!(cs_a.find_str("é", 0, None), None);
+    assert_eq!(cs_a.find_str("e\u{301}", 4, None), Some(9));
+    assert_eq!(cs_a.replace_str("e\u{301}", "é", 0, None), Some(3));
+    assert!(cs_a == "café cafe\u{301}");
+    assert_eq!(cs_a.find_str("é", 0, None), Some(3));
+    let mut cs_b = CharString::from_str_with("ﬁo ﬁno", Normalization::NFKC);
+    assert!(cs_b == "fio fino");
+    assert_eq!(cs_b.find_str("ﬁ", 1, None), Some(4));
+    assert_eq!(cs_b.replace_str_all("ﬁ", "ﬂ"), Some(2));
+    assert!(cs_b == "flo flno");
+    assert_eq!(CharString::from("ﬁo").find_str("fi", 0, None), None);
+    drop(cs_a);
+    drop(cs_b);
+
+} // End of function test_char_string_methods()
+
+
+fn test_grapheme_string_methods() {
+
+    // @@ Test 1 - reverse() doesn't split the graphemes.
+    fn fnc(s: &str) -> String {
+        let mut gs_tmp = GraphemeString::from(s);
+        gs_tmp.reverse();
+        gs_tmp.to_string()
+    }
+
+    println!("\nReverse GraphemeString");
+
+    println!("{} vs 🇸🇪", fnc("🇸🇪"));
+    println!("{} vs lçon", fnc("noçl"));
+    println!("{} vs 👍🏽a", fnc("a👍🏽"));
+
+    assert_eq!(fnc("🇸🇪"), "🇸🇪");
+    assert_eq!(fnc("noçl"), "lçon");
+    assert_eq!(fnc("a🇸🇪b🇵🇹"), "🇵🇹b🇸🇪a");
+    assert_eq!(fnc("a👍🏽"), "👍🏽a");
+    // Decomposed e + combining acute, that NFC composes.
+    assert_eq!(fnc("noe\u{0301}l"), "léon");
+    // Hangul jamo, that NFC composes in one syllable.
+    assert_eq!(fnc("\u{1100}\u{1161}x"), "x\u{AC00}");
+
+    // Print Out reverse GraphemeString .
+    //
+    // 🇸🇪 vs 🇸🇪
+    // lçon vs lçon
+    // 👍🏽a vs 👍🏽a
+
+
+    // @@ Test 2 - len(), random access, substring() and slice() .
+    let gs_a = GraphemeString::from("a🇸🇪b👍🏽c");
+    assert_eq!(gs_a.len(), 5);
+    assert_eq!(&gs_a[1], "🇸🇪");
+    assert_eq!(gs_a.get(3), Some("👍🏽"));
+    assert_eq!(gs_a.get(5), None);
+    assert_eq!(gs_a.substring(1, 3), "🇸🇪b👍🏽");
+    assert_eq!(gs_a.substring(3, 50), "👍🏽c");
+    assert_eq!(gs_a.slice(..2), "a🇸🇪");
+    assert_eq!(gs_a.slice(1..=3), "🇸🇪b👍🏽");
+    assert_eq!(gs_a.slice(4..), "c");
+    drop(gs_a);
+
+
+    // @@ Test 3 - find_str(), contains_str(), start_with_str() and ends_with_str() .
+    let gs_a = GraphemeString::from("a🇸🇪b🇸🇪c");
+    assert_eq!(gs_a.find_str("🇸🇪", 0, None), Some(1));
+    assert_eq!(gs_a.find_str("🇸🇪", 2, None), Some(3));
+    assert_eq!(gs_a.find_str("🇸🇪", 2, Some(2)), None);
+    assert!(gs_a.contains_str("b🇸🇪"));
+    assert!(gs_a.start_with_str("a🇸🇪"));
+    assert!(gs_a.ends_with_str("🇸🇪c"));
+    // A part of a grapheme is not a match.
+    let gs_b = GraphemeString::from("noél");
+    assert!(!gs_b.contains_str("e"));
+    assert!(gs_b.contains_str("é"));
+    drop(gs_a);
+    drop(gs_b);
+
+
+    // @@ Test 4 - replace_str(), replace_str_all() and split_str() .
+    let mut gs_a = GraphemeString::from("a🇸🇪b🇸🇪c");
+    assert_eq!(gs_a.replace_str("🇸🇪", "🇵🇹", 0, None), Some(1));
+    assert_eq!(gs_a, "a🇵🇹b🇸🇪c");
+    assert_eq!(gs_a.replace_str_all("🇸🇪", "-"), Some(1));
+    assert_eq!(gs_a, "a🇵🇹b-c");
+    assert_eq!(gs_a.replace_str_all("XX", "-"), None);
+    let res_vec = GraphemeString::from("a🇸🇪b🇸🇪c").split_str("🇸🇪").iter().map(|s| s.to_string()).collect::<Vec<String>>();
+    assert_eq!(res_vec, vec!["a", "b", "c"]);
+    drop(gs_a);
+
+
+    // @@ Test 5 - The joined text is normalized again at the join point.
+    let mut gs_a = GraphemeString::from("cafe");
+    gs_a.push_str("\u{301}");
+    assert!(gs_a.eq_str("café"));
+    assert_eq!(gs_a.len(), 4);
+    let mut gs_b = GraphemeString::from("\u{301}s");
+    gs_b.push_str_start("cafe");
+    assert!(gs_b.eq_str("cafés"));
+    let mut gs_c = GraphemeString::from("ex");
+    assert!(gs_c.insert_str("\u{301}", 1).is_ok());
+    assert!(gs_c.eq_str("éx"));
+    let mut gs_d = GraphemeString::from_str_with("cafe", Normalization::NFD);
+    gs_d.push_str("\u{301}");
+    assert!(gs_d.eq_str("cafe\u{301}"));
+    drop(gs_a);
+    drop(gs_b);
+    drop(gs_c);
+    drop(gs_d);
+
+} // End of function test_grapheme_string_methods()
+
+
+fn test_normalization() {
+
+    // @@ Test 1 - get_vec_chars_with() .
+    // "é" as a single char and as "e" plus the combining acute accent.
+    let s_composed = "no\u{00E9}l";
+    let s_decomposed = "noe\u{0301}l";
+    assert_eq!(s_decomposed.get_vec_chars_with(Normalization::None).len(), 5);
+    assert_eq!(s_decomposed.get_vec_chars_with(Normalization::NFC).len(), 4);
+    assert_eq!(s_composed.get_vec_chars_with(Normalization::None).len(), 4);
+    assert_eq!(s_composed.get_vec_chars_with(Normalization::NFD).len(), 5);
+    assert!(s_composed.get_vec_chars_with(Normalization::NFD).eq_str(s_decomposed));
+    // The compatibility forms fold the ligature "ﬁ" into "fi".
+    assert!("ﬁ".get_vec_chars_with(Normalization::NFC).eq_str("ﬁ"));
+    assert!("ﬁ".get_vec_chars_with(Normalization::NFKC).eq_str("fi"));
+    assert!("ﬁé".get_vec_chars_with(Normalization::NFKD).eq_str("fie\u{0301}"));
+    // The default form is NFC.
+    assert!(s_decomposed.get_vec_chars().eq_str(s_composed));
+
+
+    // @@ Test 2 - The form of the patterns is explicit.
+    let vc_a = s_decomposed.get_vec_chars_with(Normalization::None);
+    // The *_str methods use NFC, the pattern is composed and doesn't match
+    // the decomposed text.
+    assert!(!vc_a.contains_str(s_composed));
+    assert_eq!(vc_a.find_vec(&s_composed.get_vec_chars_with(Normalization::NFD), 0, None), Some(0));
+    // The same &str is always the same chars.
+    assert!(TranslationTable::maketrans("é", "e", "").unwrap().translate(&"café".get_vec_chars()).eq_str("cafe"));
+
+    // A GraphemeString and an IndexedStr keep their form for the patterns.
+    let mut gs_a = GraphemeString::from_str_with(s_decomposed, Normalization::None);
+    assert_eq!(gs_a.normalization(), Normalization::None);
+    gs_a.push_str(s_decomposed);
+    assert!(gs_a.eq_str(&s_decomposed.repeat(2)));
+    assert_eq!(gs_a.find_str(s_decomposed, 1, None), Some(4));
+    assert!(!gs_a.contains_str(s_composed));
+    let is_a = IndexedStr::from_str_with(s_composed, Normalization::NFD);
+    assert_eq!(is_a.len(), 5);
+    assert!(is_a.contains_str(s_composed));
+    assert_eq!(GraphemeString::from(s_decomposed).normalization(), Normalization::NFC);
+    // The form is not compared.
+    assert_eq!(GraphemeString::from_str_with(s_composed, Normalization::None), GraphemeString::from(s_composed));
+
+} // End of function test_normalization()
+
+
+fn test_char_finder() {
+
+    // @@ Test 1 - find(), find_at() and find_iter() .
+    let vc_a = "abbbabbbabbba".get_vec_chars();
+    let finder = CharFinder::new(&"bbb".get_vec_chars());
+    assert_eq!(finder.find(&vc_a), Some(1));
+    assert_eq!(finder.find_at(&vc_a, 2, None), Some(5));
+    assert_eq!(finder.find_at(&vc_a, 2, Some(4)), None);
+    assert_eq!(finder.find_at(&vc_a, 100, None), None);
+    assert_eq!(finder.find_iter(&vc_a).collect::<Vec<usize>>(), vec![1, 5, 9]);
+    assert_eq!(CharFinder::new(&[]).find(&vc_a), None);
+    // A pattern that runs over the end of the haystack.
+    assert_eq!(CharFinder::new_str("ab").find(&"xa".get_vec_chars()), None);
+    drop(vc_a);
+
+
+    // @@ Test 2 - Compare with a naive search on pseudo random strings.
+    fn naive_find(haystack: &[char], needle: &[char]) -> Option<usize> {
+        if needle.is_empty() || needle.len() > haystack.len() {
+            return None;
+        }
+        (0..=(haystack.len() - needle.len())).find(|&i| haystack[i..i + needle.len()] == *needle)
+    }
+
+    // Small alphabets make many periodic patterns and partial matches.
+    let mut seed = 12345_u32;
+    let mut next_char = |alphabet_len: u32| {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        char::from_u32('a' as u32 + (seed >> 16) % alphabet_len).unwrap()
+    };
+    for alphabet_len in [1_u32, 2, 3] {
+        for _ in 0..300 {
+            let haystack: Vec<char> = (0..40).map(|_| next_char(alphabet_len)).collect();
+            let needle_len = 1 + (next_char(8) as usize - 'a' as usize);
+            let needle: Vec<char> = (0..needle_len).map(|_| next_char(alphabet_len)).collect();
+            let finder = CharFinder::new(&needle);
+            assert_eq!(finder.find(&haystack), naive_find(&haystack, &needle));
+            for start_pos in 0..haystack.len() {
+                let expected = naive_find(&haystack[start_pos..], &needle).map(|i| i + start_pos);
+                assert_eq!(finder.find_at(&haystack, start_pos, None), expected);
+            }
+        }
+    }
+
+} // End of function test_char_finder()
+
+
+fn test_aho_corasick() {
+
+    // @@ Test 1 - find_iter() with the two match kinds.
+    let vc_a = "abcd xabcd".get_vec_chars();
+    let automaton = CharAhoCorasick::new_str(&["bc", "abcd", "ab", "x"], MatchKind::LeftmostLongest);
+    let res_vec: Vec<CharMatch> = automaton.find_iter(&vc_a).collect();
+    assert_eq!(res_vec, vec![CharMatch { pattern: 1, start: 0, end: 4 },
+                             CharMatch { pattern: 3, start: 5, end: 6 },
+                             CharMatch { pattern: 1, start: 6, end: 10 }]);
+    let automaton = CharAhoCorasick::new_str(&["bc", "ab", "abcd"], MatchKind::LeftmostFirst);
+    let res_vec: Vec<CharMatch> = automaton.find_iter(&vc_a).collect();
+    assert_eq!(res_vec, vec![CharMatch { pattern: 1, start: 0, end: 2 },
+                             CharMatch { pattern: 1, start: 6, end: 8 }]);
+    let automaton = CharAhoCorasick::new_str(&["she", "he", "hers"], MatchKind::LeftmostLongest);
+    assert_eq!(automaton.find(&"ushers".get_vec_chars()), Some(CharMatch { pattern: 0, start: 1, end: 4 }));
+    assert_eq!(automaton.find(&"uhers".get_vec_chars()), Some(CharMatch { pattern: 2, start: 1, end: 5 }));
+    assert_eq!(automaton.find(&"xyz".get_vec_chars()), None);
+    drop(vc_a);
+
+
+    // @@ Test 2 - map_str() replaces at the same time, a->b and b->c don't chain.
+    let mut vc_a = "aabbcc".get_vec_chars();
+    let replace_hashmap = HashMap::from([("a", "b"), ("b", "c")]);
+    let res_hashmap = vc_a.map_str(&replace_hashmap);
+    assert!(vc_a.eq_str("bbcccc"));
+    assert_eq!(*res_hashmap.get("a").unwrap(), 2);
+    assert_eq!(*res_hashmap.get("b").unwrap(), 2);
+    drop(vc_a);
+
+
+    // @@ Test 3 - map_str_with() and the two match kinds.
+    let mut vc_a = "a1 a10 a100".get_vec_chars();
+    let res_hashmap = vc_a.map_str_with(&[("a1", "X"), ("a10", "Y")], MatchKind::LeftmostFirst);
+    assert!(vc_a.eq_str("X X0 X00"));
+    assert_eq!(*res_hashmap.get("a1").unwrap(), 3);
+    assert_eq!(*res_hashmap.get("a10").unwrap(), 0);
+    let mut vc_a = "a1 a10 a100".get_vec_chars();
+    let res_hashmap = vc_a.map_str_with(&[("a1", "X"), ("a10", "Y")], MatchKind::LeftmostLongest);
+    assert!(vc_a.eq_str("X Y Y0"));
+    assert_eq!(*res_hashmap.get("a1").unwrap(), 1);
+    assert_eq!(*res_hashmap.get("a10").unwrap(), 2);
+    drop(vc_a);
+
+
+    // @@ Test 4 - Compare with a naive leftmost longest search.
+    fn naive_leftmost_longest(haystack: &[char], patterns: &[Vec<char>]) -> Vec<(usize, usize)> {
+        let mut res_vec = Vec::new();
+        let mut pos = 0;
+        while pos < haystack.len() {
+            let best = patterns.iter()
+                .filter(|p| !p.is_empty() && haystack[pos..].starts_with(p))
+                .map(|p| p.len())
+                .max();
+            if let Some(len) = best {
+                res_vec.push((pos, pos + len));
+                pos += len;
+            } else {
+                pos += 1;
+            }
+        }
+        res_vec
+    }
+
+    let mut seed = 777_u32;
+    let mut next_char = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        char::from_u32('a' as u32 + (seed >> 16) % 3).unwrap()
+    };
+    for _ in 0..300 {
+        let haystack: Vec<char> = (0..30).map(|_| next_char()).collect();
+        let patterns: Vec<Vec<char>> = (0..4)
+            .map(|i| (0..(1 + i)).map(|_| next_char()).collect())
+            .collect();
+        let patterns_slices: Vec<&[char]> = patterns.iter().map(|p| &p[..]).collect();
+        let automaton = CharAhoCorasick::new(&patterns_slices, MatchKind::LeftmostLongest);
+        let res_vec: Vec<(usize, usize)> = automaton.find_iter(&haystack).map(|m| (m.start, m.end)).collect();
+        assert_eq!(res_vec, naive_leftmost_longest(&haystack, &patterns));
+    }
+
+} // End of function test_aho_corasick()
+
+
+fn test_errors() {
+
+    // @@ Test 1 - insert_str() and insert_vec() errors.
+    let mut vc_a = "bla".get_vec_chars();
+    assert_eq!(vc_a.insert_str("#", 1000), Err(StringUtilsError::IndexOutOfBounds { index: 1000, len: 3 }));
+    assert_eq!(vc_a.insert_vec(&['#'], 3), Err(StringUtilsError::IndexOutOfBounds { index: 3, len: 3 }));
+    let error: Box<dyn std::error::Error> = Box::new(vc_a.insert_str("#", 5).unwrap_err());
+    assert_eq!(error.to_string(), "index 5 is out of bounds for a string of len 3");
+    drop(vc_a);
+
+
+    // @@ Test 2 - try_find_vec() and try_replace_vec() .
+    let mut vc_a = "blabliblu".get_vec_chars();
+    assert_eq!(vc_a.try_find_vec(&['b', 'l', 'i'], 0, None), Ok(Some(3)));
+    assert_eq!(vc_a.try_find_vec(&['b', 'l', 'i'], 4, None), Ok(None));
+    assert_eq!(vc_a.try_find_vec(&['b'], 9, None), Err(StringUtilsError::IndexOutOfBounds { index: 9, len: 9 }));
+    assert_eq!(vc_a.try_find_vec(&['b'], 0, Some(9)), Err(StringUtilsError::IndexOutOfBounds { index: 9, len: 9 }));
+    assert_eq!(vc_a.try_find_vec(&['b'], 5, Some(2)), Err(StringUtilsError::InvalidRange { start: 5, end: 2 }));
+    assert_eq!(vc_a.try_find_vec(&[], 0, None), Err(StringUtilsError::EmptyPattern));
+    assert_eq!(vc_a.try_replace_vec(&['b', 'l', 'u'], &['#'], 0, None), Ok(Some(6)));
+    assert!(vc_a.eq_str("blabli#"));
+    assert_eq!(vc_a.try_replace_vec(&['b'], &['#'], 20, None), Err(StringUtilsError::IndexOutOfBounds { index: 20, len: 7 }));
+    // The panicking version still returns None for an empty pattern.
+    assert_eq!(vc_a.find_vec(&[], 0, None), None);
+    drop(vc_a);
+
+
+    // @@ Test 3 - try_substring() and try_slice() .
+    let s = "abcdèfghij";
+    assert_eq!(s.try_substring(3, 5), Ok("dèfgh"));
+    assert_eq!(s.try_substring(10, 0), Ok(""));
+    assert_eq!(s.try_substring(3, 50), Err(StringUtilsError::IndexOutOfBounds { index: 53, len: 10 }));
+    assert_eq!(s.try_slice(3..8), Ok("dèfgh"));
+    assert_eq!(s.try_slice(3..=7), Ok("dèfgh"));
+    assert_eq!(s.try_slice(3..), Ok("dèfghij"));
+    assert_eq!(s.try_slice(..50), Err(StringUtilsError::IndexOutOfBounds { index: 50, len: 10 }));
+    #[allow(clippy::reversed_empty_ranges)]
+    let res = s.try_slice(8..3);
+    assert_eq!(res, Err(StringUtilsError::InvalidRange { start: 8, end: 3 }));
+
+} // End of function test_errors()
+
+
+fn test_range_methods() {
+
+    // @@ Test 1 - slice() with an excluded start bound.
+    let s = "abcdèfghij";
+    let range = (std::ops::Bound::Excluded(2), std::ops::Bound::Excluded(8));
+    assert_eq!(s.slice(range), "dèfgh");
+    assert_eq!(s.try_slice(range), Ok("dèfgh"));
+
+
+    // @@ Test 2 - find_in() and count_in() .
+    let vc_a = "blabliblabla".get_vec_chars();
+    let vc_bla = "bla".get_vec_chars();
+    assert_eq!(vc_a.find_in(&vc_bla, ..), Some(0));
+    assert_eq!(vc_a.find_in(&vc_bla, 1..), Some(6));
+    assert_eq!(vc_a.find_in(&vc_bla, 1..9), Some(6));
+    // The exclusive end, the match has to be all inside of the range.
+    assert_eq!(vc_a.find_in(&vc_bla, 1..8), None);
+    assert_eq!(vc_a.find_in(&vc_bla, 1..=8), Some(6));
+    assert_eq!(vc_a.find_in(&vc_bla, 12..), None);
+    assert_eq!(vc_a.count_in(&vc_bla, ..), 3);
+    assert_eq!(vc_a.count_in(&vc_bla, 3..), 2);
+    assert_eq!(vc_a.count_in(&vc_bla, 3..11), 1);
+    assert_eq!(vc_a.count_in(&[], ..), 0);
+    drop(vc_a);
+
+
+    // @@ Test 3 - replace_in() .
+    let mut vc_a = "blabliblabla".get_vec_chars();
+    assert_eq!(vc_a.replace_in(&['b', 'l', 'a'], &['#'], 3..), Some(6));
+    assert!(vc_a.eq_str("blabli#bla"));
+    assert_eq!(vc_a.replace_in(&['b', 'l', 'a'], &['#'], ..2), None);
+    assert_eq!(vc_a.replace_in(&['b', 'l', 'a'], &['#'], 7..=9), Some(7));
+    assert!(vc_a.eq_str("blabli##"));
+    drop(vc_a);
+
+
+    // @@ Test 4 - split_in() .
+    let vc_a = "a,b,c,d".get_vec_chars();
+    let res_vec = vc_a.split_in(&[','], 2..);
+    assert_eq!(res_vec.len(), 3);
+    assert!(res_vec[0].to_vec_chars().eq_str("b"));
+    assert!(res_vec[2].to_vec_chars().eq_str("d"));
+    drop(vc_a);
+
+    // @@ Test 5 - CharString range methods.
+    let cs_a = CharString::from("blabliblabla");
+    assert_eq!(cs_a.find_in(&['b', 'l', 'a'], 1..), Some(6));
+    assert_eq!(cs_a.count_in(&['b', 'l', 'a'], ..), 3);
+    assert_eq!(cs_a.split_in(&['i'], ..6).len(), 1);
+
+} // End of function test_range_methods()
+
+
+fn test_char_pattern_methods() {
+
+    // @@ Test 1 - find() and rfind() with all the kinds of patterns.
+    let cs_a = CharString::from("blabliblu, noél!");
+    let vc_bli = "bli".get_vec_chars();
+    assert_eq!(cs_a.find('l'), Some(1));
+    assert_eq!(cs_a.find("bli"), Some(3));
+    assert_eq!(cs_a.find(&String::from("blu")), Some(6));
+    assert_eq!(cs_a.find(&vc_bli), Some(3));
+    assert_eq!(cs_a.find(&vc_bli[..2]), Some(0));
+    assert_eq!(cs_a.find(&CharString::from("noél")), Some(11));
+    assert_eq!(cs_a.find(['u', ',']), Some(8));
+    let set_array_ref: &[char; 2] = &['!', 'é'];
+    assert_eq!(cs_a.find(set_array_ref), Some(13));
+    assert_eq!(cs_a.find(AnyOf(&['n', 'o'])), Some(11));
+    assert_eq!(cs_a.find(char::is_whitespace), Some(10));
+    assert_eq!(cs_a.find(|c: char| c.is_ascii_punctuation()), Some(9));
+    assert_eq!(cs_a.find("xyz"), None);
+    assert_eq!(cs_a.find(""), None);
+    assert_eq!(cs_a.rfind('l'), Some(14));
+    assert_eq!(cs_a.rfind("bl"), Some(6));
+    assert_eq!(cs_a.rfind(char::is_alphabetic), Some(14));
+    drop(cs_a);
+
+
+    // @@ Test 2 - contains(), starts_with() and ends_with() .
+    let cs_a = CharString::from("blabliblu");
+    assert!(cs_a.contains("bli"));
+    assert!(cs_a.contains('u'));
+    assert!(!cs_a.contains(['x', 'y']));
+    assert!(cs_a.starts_with("bla"));
+    assert!(cs_a.starts_with('b'));
+    assert!(cs_a.starts_with(char::is_lowercase));
+    assert!(!cs_a.starts_with("blu"));
+    assert!(cs_a.ends_with("blu"));
+    assert!(cs_a.ends_with(['a', 'u']));
+    assert!(!cs_a.ends_with(""));
+    // A Vec<char> or a slice through as_char_str() .
+    let vc_a = "blabliblu".get_vec_chars();
+    assert!(vc_a.as_char_str().contains(['i']));
+    assert_eq!(vc_a[3..].as_char_str().find('b'), Some(0));
+    drop(cs_a);
+    drop(vc_a);
+
+
+    // @@ Test 3 - matches(), split() and replace() .
+    let cs_a = CharString::from("a1b22c333");
+    let res_vec: Vec<String> = cs_a.matches(char::is_numeric).iter().map(|m| m.to_string()).collect();
+    assert_eq!(res_vec, vec!["1", "2", "2", "3", "3", "3"]);
+    let res_vec: Vec<String> = cs_a.split(char::is_numeric).map(|m| m.to_string()).collect();
+    assert_eq!(res_vec, vec!["a", "b", "", "c", "", "", ""]);
+    let res_vec: Vec<String> = CharString::from("a,b").split(';').map(|m| m.to_string()).collect();
+    assert_eq!(res_vec, vec!["a,b"]);
+    assert!(cs_a.replace(char::is_numeric, "#") == "a#b##c###");
+    assert!(cs_a.replace("22", &['_'][..]) == "a1b_c333");
+    assert!(cs_a.replacen(['1', '2', '3'], "", 4) == "abc33");
+    drop(cs_a);
+
+} // End of function test_char_pattern_methods()
+
+
+fn to_strings<'a>(pieces: impl Iterator<Item = &'a CharStr>) -> Vec<String> {
+    pieces.map(|piece| piece.to_string()).collect()
+}
+
+fn test_split_iterators() {
+
+    // @@ Test 1 - split(), rsplit() and split_terminator() keep the empty
+    //             pieces like the Std.
+    let cs_a = CharString::from(",a,,b,");
+    assert_eq!(to_strings(cs_a.split(',')), vec!["", "a", "", "b", ""]);
+    assert_eq!(to_strings(cs_a.rsplit(',')), vec!["", "b", "", "a", ""]);
+    assert_eq!(to_strings(cs_a.split_terminator(',')), vec!["", "a", "", "b"]);
+    assert_eq!(to_strings(cs_a.split_terminator(',').rev()), vec!["b", "", "a", ""]);
+    assert_eq!(to_strings(CharString::from("").split(',')), vec![""]);
+    assert_eq!(to_strings(CharString::from("").split_terminator(',')), Vec::<String>::new());
+    assert_eq!(to_strings(CharString::from("abc").split("")), vec!["abc"]);
+    // Mixed from the front and from the back.
+    let mut iter = cs_a.split(',');
+    assert!(iter.next().unwrap().is_empty());
+    assert!(iter.next_back().unwrap().is_empty());
+    assert!(iter.next().unwrap() == "a");
+    assert!(iter.next_back().unwrap() == "b");
+    assert!(iter.next().unwrap().is_empty());
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+    drop(cs_a);
+
+
+    // @@ Test 2 - split_inclusive(), splitn() and rsplitn() .
+    let cs_a = CharString::from("l1\nl2\n\nl3");
+    assert_eq!(to_strings(cs_a.split_inclusive('\n')), vec!["l1\n", "l2\n", "\n", "l3"]);
+    assert_eq!(to_strings(CharString::from("l1\n").split_inclusive('\n')), vec!["l1\n"]);
+    let cs_b = CharString::from("a::b::c");
+    assert_eq!(to_strings(cs_b.splitn(2, "::")), vec!["a", "b::c"]);
+    assert_eq!(to_strings(cs_b.splitn(5, "::")), vec!["a", "b", "c"]);
+    assert_eq!(cs_b.splitn(0, "::").count(), 0);
+    assert_eq!(to_strings(cs_b.rsplitn(2, "::")), vec!["c", "a::b"]);
+    assert_eq!(to_strings(cs_b.rsplitn(1, "::")), vec!["a::b::c"]);
+    drop(cs_a);
+    drop(cs_b);
+
+
+    // @@ Test 3 - split_whitespace(), split_once() and rsplit_once() .
+    let cs_a = CharString::from("  un \t dos\ntrés  ");
+    assert_eq!(to_strings(cs_a.split_whitespace()), vec!["un", "dos", "trés"]);
+    assert_eq!(to_strings(cs_a.split_whitespace().rev()), vec!["trés", "dos", "un"]);
+    let cs_b = CharString::from("key=value=x");
+    let (key, value) = cs_b.split_once('=').unwrap();
+    assert!(key == "key" && value == "value=x");
+    let (key, value) = cs_b.rsplit_once('=').unwrap();
+    assert!(key == "key=value" && value == "x");
+    assert!(cs_b.split_once(';').is_none());
+    drop(cs_a);
+    drop(cs_b);
+
+
+    // @@ Test 4 - skip_empty() gives the same pieces as split_vec() .
+    let vc_a = "--a-b---c--".get_vec_chars();
+    let pattern: &[char] = &['-'];
+    let res_vec: Vec<String> = vc_a.split_vec(pattern).iter().map(|piece| piece.to_string()).collect();
+    assert_eq!(to_strings(vc_a.as_char_str().split(pattern).skip_empty()), res_vec);
+    assert_eq!(to_strings(vc_a.as_char_str().split('-').skip_empty().rev()), vec!["c", "b", "a"]);
+    // The pieces borrow from the original chars.
+    let first = vc_a.as_char_str().split("-").skip_empty().next().unwrap();
+    assert_eq!(first.as_chars().as_ptr(), vc_a[2..].as_ptr());
+    drop(vc_a);
+
+
+    // @@ Test 5 - Compare split() with the split() of str, with pseudo
+    //             random strings.
+    let mut seed = 12345_u64;
+    for _ in 0..200 {
+        let p_string = random_text(&mut seed, &['a', 'b', ','], 12);
+        let cs_a = CharString::from(p_string.as_str());
+        for pattern in [",", "a,", "ab"] {
+            let std_vec: Vec<&str> = p_string.split(pattern).collect();
+            assert_eq!(to_strings(cs_a.split(pattern)), std_vec);
+            let std_vec: Vec<&str> = p_string.rsplit(pattern).collect();
+            assert_eq!(to_strings(cs_a.rsplit(pattern)), std_vec);
+            let std_vec: Vec<&str> = p_string.split_terminator(pattern).collect();
+            assert_eq!(to_strings(cs_a.split_terminator(pattern)), std_vec);
+            let std_vec: Vec<&str> = p_string.split_inclusive(pattern).collect();
+            assert_eq!(to_strings(cs_a.split_inclusive(pattern)), std_vec);
+            let std_vec: Vec<&str> = p_string.splitn(2, pattern).collect();
+            assert_eq!(to_strings(cs_a.splitn(2, pattern)), std_vec);
+            let std_vec: Vec<&str> = p_string.rsplitn(2, pattern).collect();
+            assert_eq!(to_strings(cs_a.rsplitn(2, pattern)), std_vec);
+        }
+        seed = seed.wrapping_add(1);
+    }
+
+} // End of function test_split_iterators()
+
+
+fn test_char_rope() {
+
+    // @@ Test 1 - Conversions, len, get, index and slice.
+    let rope_a = CharRope::from("Hello, café!");
+    assert_eq!(rope_a.len(), 12);
+    assert_eq!(rope_a.get(10), Some('é'));
+    assert_eq!(rope_a.get(12), None);
+    assert_eq!(rope_a[0], 'H');
+    assert_eq!(rope_a.to_string(), "Hello, café!");
+    assert_eq!(format!("{:?}", CharRope::from("a\"b")), "\"a\\\"b\"");
+    assert!(rope_a.slice(7..11) == "café");
+    assert!(rope_a.slice(..0).is_empty());
+    assert_eq!(Vec::<char>::from(&rope_a), "Hello, café!".get_vec_chars());
+    assert!(CharRope::new().is_empty());
+    drop(rope_a);
+
+
+    // @@ Test 2 - insert(), remove() and replace_range() in a long text,
+    //             compared with a Vec<char>.
+    let mut vc_model: Vec<char> = (0..5000).map(|i| char::from(b'a' + (i % 26) as u8)).collect();
+    let mut rope_a = CharRope::from(vc_model.clone());
+    let mut seed = 987654321_u64;
+    for step in 0..2000 {
+        let pos = next_random(&mut seed) as usize % (vc_model.len() + 1);
+        let len = next_random(&mut seed) as usize % 700;
+        match step % 3 {
+            0 => {
+                let text: Vec<char> = (0..len).map(|i| char::from(b'A' + (i % 26) as u8)).collect();
+                rope_a.insert(pos, &text);
+                vc_model.splice(pos..pos, text);
+            },
+            1 => {
+                let end = (pos + len % 50).min(vc_model.len());
+                rope_a.remove(pos..end);
+                vc_model.drain(pos..end);
+            },
+            _ => {
+                let end = (pos + len % 50).min(vc_model.len());
+                rope_a.replace_range(pos..end, &['#', '#']);
+                vc_model.splice(pos..end, ['#', '#']);
+            },
+        }
+        assert_eq!(rope_a.len(), vc_model.len());
+    }
+    assert!(rope_a.eq_vec(&vc_model));
+    assert_eq!(rope_a.to_vec_chars(), vc_model);
+    for index in (0..vc_model.len()).step_by(97) {
+        assert_eq!(rope_a[index], vc_model[index]);
+        assert!(rope_a.slice(index..).eq_vec(&vc_model[index..]));
+    }
+    // A clone shares the chunks, but the edits are independent.
+    let mut rope_b = rope_a.clone();
+    rope_b.remove(..10);
+    assert_eq!(rope_a.len(), vc_model.len());
+    assert_eq!(rope_b.len(), vc_model.len() - 10);
+    rope_b.append(&rope_a);
+    assert_eq!(rope_b.len(), 2 * vc_model.len() - 10);
+    drop(rope_a);
+    drop(rope_b);
+
+
+    // @@ Test 3 - The StringUtilsVecCharsV2 methods, with matches that
+    //             cross the chunks.
+    let vc_text: Vec<char> = "abc_xyz_".repeat(200).chars().collect();
+    let mut vc_model = vc_text.clone();
+    let mut rope_a = CharRope::from(vc_text);
+    let pattern: Vec<char> = "z_abc".chars().collect();
+    for start_pos in [0, 255, 256, 600, 1500] {
+        assert_eq!(rope_a.find_vec(&pattern, start_pos, None), vc_model.find_vec(&pattern, start_pos, None));
+    }
+    assert_eq!(rope_a.find_str("xyz", 10, Some(12)), Some(12));
+    assert_eq!(rope_a.find_str("xyz", 10, Some(11)), None);
+    assert_eq!(rope_a.try_find_vec(&pattern, 5000, None),
+               Err(StringUtilsError::IndexOutOfBounds { index: 5000, len: 1600 }));
+    assert!(rope_a.contains_str("_abc_") && !rope_a.contains_str("abd"));
+    assert!(rope_a.start_with_str("abc_") && rope_a.ends_with_str("xyz_"));
+    assert!(!rope_a.ends_with_str("xyz"));
+    assert_eq!(rope_a.replace_vec_all(&pattern, &['!']), vc_model.replace_vec_all(&pattern, &['!']));
+    assert!(rope_a.eq_vec(&vc_model));
+    assert_eq!(rope_a.replace_str("xy", "XY", 0, None), Some(4));
+    assert!(rope_a.start_with_str("abc_XY!_xy!_"));
+    rope_a.push_str_start("  ");
+    rope_a.push_str(" \n");
+    rope_a.trim();
+    assert!(rope_a.start_with_str("abc_") && rope_a.ends_with_str("xyz_"));
+    assert_eq!(rope_a.insert_str("-", rope_a.len()),
+               Err(StringUtilsError::IndexOutOfBounds { index: rope_a.len(), len: rope_a.len() }));
+    drop(rope_a);
+    vc_model.clear();
+
+    let rope_a = CharRope::from("--a-bb---c--");
+    let res_vec: Vec<String> = rope_a.split_str("-").iter().map(|piece| piece.to_string()).collect();
+    assert_eq!(res_vec, vec!["a", "bb", "c"]);
+    assert!(rope_a.split_str("x").is_empty());
+    let mut rope_b = CharRope::from("a cat and a dog");
+    let map: HashMap<&str, &str> = HashMap::from([("cat", "dog"), ("dog", "cat")]);
+    rope_b.map_str(&map);
+    assert!(rope_b == "a dog and a cat");
+    drop(rope_a);
+    drop(rope_b);
+
+
+    // @@ Test 4 - The *_in methods give the same results of the Vec<char>,
+    //             with ranges that end inside of a match.
+    let vc_text: Vec<char> = "çab_xyz_".repeat(200).chars().collect();
+    let rope_a = CharRope::from(vc_text.as_slice());
+    for pattern in ["z_ça", "_", "xyz", "q"] {
+        let p_vec = pattern.get_vec_chars();
+        for range in [0..1600, 5..9, 6..258, 255..260, 300..1203, 1600..1600] {
+            assert_eq!(rope_a.find_in(&p_vec, range.clone()), vc_text.find_in(&p_vec, range.clone()));
+            assert_eq!(rope_a.count_in(&p_vec, range.clone()), vc_text.count_in(&p_vec, range.clone()));
+            let res_vec: Vec<Vec<char>> = rope_a.split_in(&p_vec, range.clone()).iter().map(|piece| piece.to_vec_chars()).collect();
+            assert_eq!(res_vec, vc_text.split_in(&p_vec, range.clone()));
+            let (mut rope_b, mut vc_b) = (rope_a.clone(), vc_text.clone());
+            assert_eq!(rope_b.replace_in(&p_vec, &['#', '#'], range.clone()), vc_b.replace_in(&p_vec, &['#', '#'], range));
+            assert!(rope_b.eq_vec(&vc_b));
+        }
+    }
+    assert!(CharRope::join_str(&["a", "b", "ç"]) == "abç");
+    assert!(CharRope::join_vec(&[&['a'], &[], &['ç', ' ']]) == "aç ");
+    // trim_end() walks back from the end, over the chunks.
+    let mut rope_b = CharRope::from(format!("{}{}", "ç ".repeat(300), " \t\n".repeat(300)).as_str());
+    rope_b.trim_end();
+    assert_eq!(rope_b.len(), 599);
+    assert_eq!(rope_b.get(598), Some('ç'));
+    drop(rope_a);
+    drop(rope_b);
+
+} // End of function test_char_rope()
+
+
+fn test_char_gap_buffer() {
+
+    // @@ Test 1 - Edits at the cursor, like in a text widget.
+    let mut gb_a = CharGapBuffer::new();
+    gb_a.insert_str("Hello world");
+    assert_eq!(gb_a.cursor(), 11);
+    gb_a.set_cursor(5);
+    gb_a.insert(',');
+    assert!(gb_a == "Hello, world");
+    assert_eq!(gb_a.cursor(), 6);
+    assert!(gb_a.cursor_right());
+    assert_eq!(gb_a.delete_back(), Some(' '));
+    assert_eq!(gb_a.delete_forward(), Some('w'));
+    gb_a.insert_vec(&['W']);
+    assert!(gb_a == "Hello,World");
+    gb_a.set_cursor(0);
+    assert!(!gb_a.cursor_left());
+    assert_eq!(gb_a.delete_back(), None);
+    gb_a.set_cursor(gb_a.len());
+    assert!(!gb_a.cursor_right());
+    assert_eq!(gb_a.delete_forward(), None);
+    // The indexes are the same of the Vec<char> with the same chars.
+    gb_a.set_cursor(3);
+    let vc_a = gb_a.to_vec_chars();
+    for (index, c) in vc_a.iter().enumerate() {
+        assert_eq!(gb_a[index], *c);
+        assert_eq!(gb_a.get(index), Some(*c));
+    }
+    assert_eq!(gb_a.get(vc_a.len()), None);
+    gb_a[0] = 'J';
+    assert_eq!(gb_a.as_slices(), (&['J', 'e', 'l'][..], &vc_a[3..]));
+    let mut buf = String::new();
+    assert_eq!(gb_a.to_string_buf(&mut buf), "Jello,World");
+    drop(gb_a);
+    drop(vc_a);
+
+
+    // @@ Test 2 - Many pseudo random edits, compared with a Vec<char>.
+    let mut vc_model: Vec<char> = Vec::new();
+    let mut gb_a = CharGapBuffer::from(vc_model.clone());
+    let mut seed = 42_u64;
+    for step in 0..5000 {
+        let random = next_random(&mut seed) as usize;
+        match random % 5 {
+            0 => gb_a.set_cursor(random % (gb_a.len() + 1)),
+            1 | 2 => {
+                let c = char::from(b'a' + (step % 26) as u8);
+                vc_model.insert(gb_a.cursor(), c);
+                gb_a.insert(c);
+            },
+            3 => {
+                if let Some(c) = gb_a.delete_back() {
+                    assert_eq!(vc_model.remove(gb_a.cursor()), c);
+                }
+            },
+            _ => {
+                if let Some(c) = gb_a.delete_forward() {
+                    assert_eq!(vc_model.remove(gb_a.cursor()), c);
+                }
+            },
+        }
+    }
+    assert!(gb_a.eq_vec(&vc_model));
+    assert_eq!(gb_a.len(), vc_model.len());
+    drop(gb_a);
+
+
+    // @@ Test 3 - find and replace across the gap.
+    let mut gb_a = CharGapBuffer::from("one two three two one");
+    gb_a.set_cursor(5);
+    assert_eq!(gb_a.find_str("two", 0, None), Some(4));
+    assert_eq!(gb_a.find_str("two", 5, None), Some(14));
+    assert_eq!(gb_a.find_str("two", 5, Some(13)), None);
+    assert_eq!(gb_a.try_find_vec(&['x'], 30, None),
+               Err(StringUtilsError::IndexOutOfBounds { index: 30, len: 21 }));
+    assert!(gb_a.contains_str("o t") && !gb_a.contains_str("four"));
+    assert!(gb_a.start_with_str("one t") && gb_a.ends_with_str("o one"));
+    assert_eq!(gb_a.cursor(), 5);
+    // The cursor keeps its place in the text.
+    assert_eq!(gb_a.replace_str("one", "1", 0, None), Some(0));
+    assert_eq!(gb_a.cursor(), 3);
+    assert_eq!(gb_a.replace_str_all("two", "2"), Some(2));
+    assert!(gb_a == "1 2 three 2 one");
+    assert_eq!(gb_a.cursor(), 2);
+    assert_eq!(gb_a.replace_vec_all(&['x'], &['y']), None);
+    gb_a.replace_range(2..9, &['3']);
+    assert!(gb_a == "1 3 2 one");
+    assert_eq!(gb_a.cursor(), 2);
+    assert_eq!(String::from(&gb_a), "1 3 2 one");
+    drop(gb_a);
+
+} // End of function test_char_gap_buffer()
+
+
+fn test_indexed_str() {
+
+    // @@ Test 1 - An ASCII text, without index.
+    let is_a = IndexedStr::from("abcdefghij");
+    assert!(is_a.is_ascii());
+    assert_eq!(is_a.len(), 10);
+    assert_eq!(is_a.get(3), Some('d'));
+    assert_eq!(&is_a[9], "j");
+    assert_eq!(is_a.substring(2, 3), "cde");
+    assert_eq!(is_a.slice(8..), "ij");
+    assert_eq!(is_a.slice(8..20), "ij");
+    assert_eq!(&is_a[..=1], "ab");
+    assert_eq!(is_a.try_slice(5..11), Err(StringUtilsError::IndexOutOfBounds { index: 11, len: 10 }));
+    drop(is_a);
+
+
+    // @@ Test 2 - A long text with many multi byte chars, compared with
+    //             the StringUtils methods and with a Vec<char>.
+    let mut p_string = String::new();
+    for i in 0..1000 {
+        p_string.push(['a', 'é', '€', '𝄞', ' '][(i * 7 + i / 3) % 5]);
+    }
+    let is_a = IndexedStr::from(p_string.as_str());
+    let vc_a = p_string.get_vec_chars();
+    assert!(!is_a.is_ascii());
+    assert_eq!(is_a.len(), vc_a.len());
+    for (index, c) in vc_a.iter().enumerate() {
+        assert_eq!(is_a.get(index), Some(*c));
+        assert_eq!(is_a.char_index(is_a.byte_offset(index)), index);
+    }
+    assert_eq!(is_a.get(vc_a.len()), None);
+    for (start, len) in [(0, 0), (0, 64), (63, 2), (500, 100), (990, 50), (2000, 5)] {
+        assert_eq!(is_a.substring(start, len), p_string.substring(start, len));
+    }
+    assert_eq!(is_a.slice(130..140), p_string.slice(130..140));
+    assert_eq!(&is_a[130..140], p_string.slice(130..140));
+    assert_eq!(is_a.try_substring(999, 1), p_string.try_substring(999, 1));
+    assert_eq!(is_a.try_substring(999, 2), p_string.try_substring(999, 2));
+    drop(is_a);
+
+
+    // @@ Test 3 - push_str() keeps the index, find_str() returns char
+    //             positions.
+    let mut is_a = IndexedStr::from("x".repeat(100).as_str());
+    is_a.push_str("ção");
+    assert!(!is_a.is_ascii());
+    assert_eq!(is_a.len(), 103);
+    assert_eq!(is_a.get(101), Some('ã'));
+    is_a.push_str(&"y".repeat(100));
+    assert_eq!(is_a.get(150), Some('y'));
+    assert_eq!(is_a.slice(99..104), "xçãoy");
+    assert_eq!(is_a.find_str("ão", 0, None), Some(101));
+    assert_eq!(is_a.find_str("ão", 0, Some(100)), None);
+    assert_eq!(is_a.find_str("x", 100, None), None);
+    // The same results and checks as find_str() of Vec<char>.
+    let vec_chars = is_a.as_str().get_vec_chars();
+    for (pattern, start_pos, end_pos) in [("y", 102, None), ("y", 202, Some(202)), ("", 0, None), ("ão", 101, Some(101))] {
+        assert_eq!(is_a.find_str(pattern, start_pos, end_pos), vec_chars.find_str(pattern, start_pos, end_pos));
+    }
+    assert_eq!(IndexedStr::from("").find_str("x", 5, None), None);
+    assert!(is_a.contains_str("çã") && is_a.start_with_str("xx") && is_a.ends_with_str("yy") && !is_a.ends_with_str("oy"));
+    assert_eq!(String::from(is_a).len(), 205);
+
+
+    // @@ Test 4 - push_str() normalizes again across the join point.
+    let mut is_a = IndexedStr::from("cafe");
+    is_a.push_str("\u{301}");
+    assert_eq!(is_a.len(), 4);
+    assert_eq!(is_a.find_str("é", 0, None), Some(3));
+    assert!(is_a.eq_str("café"));
+    // The last char is on a checkpoint, that is dropped and pushed again.
+    let mut is_b = IndexedStr::from(format!("{}e", "ç".repeat(128)).as_str());
+    is_b.push_str("\u{301}ão");
+    assert_eq!(is_b.len(), 131);
+    assert_eq!((is_b.get(127), is_b.get(128), is_b.get(130)), (Some('ç'), Some('é'), Some('o')));
+    assert_eq!(is_b.find_str("éã", 0, None), Some(128));
+    assert_eq!(is_b.char_index(is_b.byte_offset(129)), 129);
+    let mut is_c = IndexedStr::from_str_with("a\u{301}", Normalization::NFD);
+    is_c.push_str("\u{327}");
+    assert!(is_c.eq_str("a\u{327}\u{301}"));
+    let mut is_d = IndexedStr::from_str_with("cafe", Normalization::None);
+    is_d.push_str("\u{301}");
+    assert_eq!(is_d.len(), 5);
+
+} // End of function test_indexed_str()
+
+
+fn test_flex_string() {
+
+    // @@ Test 1 - The width grows with the widest char.
+    let mut fs_a = FlexString::from("abc");
+    assert_eq!(fs_a.width(), 1);
+    assert_eq!(fs_a.size_in_bytes(), 3);
+    fs_a.push_str("ção");
+    assert_eq!(fs_a.width(), 1);
+    fs_a.push_str("€");
+    assert_eq!(fs_a.width(), 2);
+    assert_eq!(fs_a.size_in_bytes(), 14);
+    assert_eq!(fs_a.insert_str("𝄞", 1), Ok(()));
+    assert_eq!(fs_a.width(), 4);
+    assert!(fs_a == "a𝄞bcção€");
+    assert_eq!(fs_a.get(1), Some('𝄞'));
+    assert_eq!(fs_a.char_at(7), '€');
+    assert_eq!(fs_a.get(8), None);
+    // Equal chars are equal with any width.
+    let mut fs_b = fs_a.slice(..1);
+    fs_b.push_str("bc");
+    assert_eq!(fs_b.width(), 4);
+    let fs_c = FlexString::from("abc");
+    assert_eq!(fs_c.width(), 1);
+    assert!(fs_c == fs_b);
+    assert_eq!(format!("{:?}", FlexString::from("a\tb")), "\"a\\tb\"");
+    // A pattern wider than the storage never matches.
+    drop(fs_b);
+    let fs_b = FlexString::from("plain ascii");
+    assert_eq!(fs_b.find_str("€", 0, None), None);
+    assert_eq!(fs_b.find_str("ascii", 0, None), Some(6));
+    drop(fs_a);
+    drop(fs_b);
+
+
+    // @@ Test 2 - The StringUtilsVecCharsV2 methods give the same results
+    //             of the Vec<char> .
+    let texts = ["  one, two, three  ", "ação, função, ", "€1,€2,,€3", "𝄞,a,𝄞"];
+    for text in texts {
+        let vc_a = text.get_vec_chars();
+        let fs_a = FlexString::from(text);
+        assert!(fs_a.eq_vec(&vc_a) && fs_a.eq_str(text));
+        for pattern in [",", ", ", "€", "𝄞", "x"] {
+            let p_vec = pattern.get_vec_chars();
+            assert_eq!(fs_a.find_vec(&p_vec, 1, None), vc_a.find_vec(&p_vec, 1, None));
+            assert_eq!(fs_a.find_vec(&p_vec, 0, Some(3)), vc_a.find_vec(&p_vec, 0, Some(3)));
+            assert_eq!(fs_a.contains_vec(&p_vec), vc_a.contains_vec(&p_vec));
+            assert_eq!(fs_a.start_with_vec(&p_vec), vc_a.start_with_vec(&p_vec));
+            assert_eq!(fs_a.ends_with_vec(&p_vec), vc_a.ends_with_vec(&p_vec));
+            assert_eq!(fs_a.find_in(&p_vec, 1..5), vc_a.find_in(&p_vec, 1..5));
+            assert_eq!(fs_a.count_in(&p_vec, 1..), vc_a.count_in(&p_vec, 1..));
+            let res_vec: Vec<Vec<char>> = fs_a.split_vec(&p_vec).iter().map(|piece| piece.to_vec_chars()).collect();
+            assert_eq!(res_vec, vc_a.split_vec(&p_vec));
+            let res_vec: Vec<Vec<char>> = fs_a.split_in(&p_vec, 1..5).iter().map(|piece| piece.to_vec_chars()).collect();
+            assert_eq!(res_vec, vc_a.split_in(&p_vec, 1..5));
+
+            let (mut fs_b, mut vc_b) = (fs_a.clone(), vc_a.clone());
+            assert_eq!(fs_b.replace_vec(&p_vec, &['@'], 1, None), vc_b.replace_vec(&p_vec, &['@'], 1, None));
+            assert!(fs_b.eq_vec(&vc_b));
+            let (mut fs_b, mut vc_b) = (fs_a.clone(), vc_a.clone());
+            assert_eq!(fs_b.replace_str_all(pattern, "«𝄞»"), vc_b.replace_str_all(pattern, "«𝄞»"));
+            assert!(fs_b.eq_vec(&vc_b));
+            let (mut fs_b, mut vc_b) = (fs_a.clone(), vc_a.clone());
+            assert_eq!(fs_b.replace_in(&p_vec, &['#'], 3..), vc_b.replace_in(&p_vec, &['#'], 3..));
+            assert!(fs_b.eq_vec(&vc_b));
+        }
+        let (mut fs_b, mut vc_b) = (fs_a.clone(), vc_a.clone());
+        fs_b.trim();
+        vc_b.trim();
+        assert!(fs_b.eq_vec(&vc_b));
+        let (mut fs_b, mut vc_b) = (fs_a.clone(), vc_a.clone());
+        fs_b.push_str_start("[");
+        vc_b.push_str_start("[");
+        fs_b.push_vec(&[']']);
+        vc_b.push_vec(&[']']);
+        assert!(fs_b.eq_vec(&vc_b));
+    }
+    let mut fs_a = FlexString::join_str(&["a", "b", "ç"]);
+    assert!(fs_a == "abç");
+    assert_eq!(fs_a.insert_vec(&['x'], 3), Err(StringUtilsError::IndexOutOfBounds { index: 3, len: 3 }));
+    let map: HashMap<&str, &str> = HashMap::from([("a", "€"), ("b", "a")]);
+    fs_a.map_str(&map);
+    assert!(fs_a == "€aç");
+    assert_eq!(fs_a.width(), 2);
+    let mut buf = String::new();
+    assert_eq!(fs_a.to_string_buf(&mut buf), "€aç");
+    drop(fs_a);
+
+} // End of function test_flex_string()
+
+fn test_py_slicing() {
+
+    // @@ Test 1 - Slices of a str against the results of Python.
+    let s = "héllo wörld";
+    let table: [(Option<isize>, Option<isize>, isize, &str); 13] = [
+        (None, Some(-3), 1, "héllo wö"),
+        (Some(-3), None, 1, "rld"),
+        (None, None, 2, "hlowrd"),
+        (None, None, -1, "dlröw olléh"),
+        (None, None, -2, "drwolh"),
+        (Some(8), Some(2), -2, "rwo"),
+        (Some(2), Some(8), -1, ""),
+        (Some(-20), Some(20), 1, "héllo wörld"),
+        (Some(20), Some(-20), -1, "dlröw olléh"),
+        (Some(1), None, 3, "éoöd"),
+        (Some(-1), Some(-20), -3, "döoé"),
+        (Some(5), Some(5), 1, ""),
+        (Some(6), Some(3), 1, ""),
+    ];
+    let vec_chars = s.get_vec_chars();
+    for (start, stop, step, expected) in table.iter() {
+        assert_eq!(s.py_slice(*start, *stop, *step), *expected);
+        assert_eq!(vec_chars.py_slice(*start, *stop, *step).to_string(), *expected);
+        assert_eq!(vec_chars[..].py_slice(*start, *stop, *step).to_string(), *expected);
+        assert!(CharStr::from_chars(&vec_chars).py_slice(*start, *stop, *step).as_ref() == *expected);
+    }
+    // The extreme steps.
+    assert_eq!(s.py_slice(None, None, isize::MIN), "d");
+    assert_eq!(s.py_slice(None, None, isize::MAX), "h");
+    // A step of 1 borrows, any other step owns.
+    assert!(matches!(s.py_slice(Some(-3), None, 1), Cow::Borrowed("rld")));
+    assert!(matches!(vec_chars.py_slice(None, None, -1), Cow::Owned(_)));
+    assert!(matches!(CharStr::from_chars(&vec_chars).py_slice(Some(-3), None, 1), Cow::Borrowed(_)));
+
+    // @@ Test 2 - Indexes from the start and from the end.
+    assert_eq!(s.py_index(0), Some('h'));
+    assert_eq!(s.py_index(1), Some('é'));
+    assert_eq!(s.py_index(-1), Some('d'));
+    assert_eq!(s.py_index(-11), Some('h'));
+    assert_eq!(s.py_index(-12), None);
+    assert_eq!(s.py_index(11), None);
+    assert_eq!(vec_chars.py_index(-4), Some('ö'));
+    assert_eq!(vec_chars[..0].py_index(-1), None);
+    assert_eq!(vec_chars[..0].py_index(0), None);
+    assert_eq!(CharStr::from_chars(&vec_chars).py_index(-4), Some('ö'));
+
+    // @@ Test 3 - All the bounds and steps, against a simple model of Python.
+    let len = vec_chars.len() as isize;
+    let bounds = [None, Some(-20), Some(-11), Some(-5), Some(-1), Some(0), Some(3), Some(10), Some(11), Some(20)];
+    for start in bounds.iter() {
+        for stop in bounds.iter() {
+            for step in [-4_isize, -3, -2, -1, 1, 2, 3, 4].iter() {
+                // Python walks from the start to the stop, inside of the chars.
+                let norm = |bound: isize| if bound < 0 { bound + len } else { bound };
+                let (mut i, stop_val) = if *step > 0 {
+                    (start.map_or(0, |b| norm(b).max(0)), stop.map_or(len, |b| norm(b).min(len)))
+                } else {
+                    (start.map_or(len - 1, |b| norm(b).min(len - 1)), stop.map_or(-1, |b| norm(b).max(-1)))
+                };
+                let mut expected = String::new();
+                while (*step > 0 && i < stop_val) || (*step < 0 && i > stop_val) {
+                    if i >= 0 && i < len {
+                        expected.push(vec_chars[i as usize]);
+                    }
+                    i += step;
+                }
+                assert_eq!(s.py_slice(*start, *stop, *step), expected);
+                assert_eq!(vec_chars.py_slice(*start, *stop, *step).to_string(), expected);
+            }
+        }
+    }
+    drop(vec_chars);
+
+} // End of function test_py_slicing()
+
+fn test_py_str_methods() {
+
+    // @@ Test 1 - Case mappings, the results of CPython 3.11 .
+    type CaseMethod = fn(&str) -> String;
+    let table: [(&str, CaseMethod, &str); 14] = [
+        ("Hello world", |s| s.title(), "Hello World"),
+        ("they're bill's friends", |s| s.title(), "They'Re Bill'S Friends"),
+        ("ǆemal ǈudevit", |s| s.title(), "ǅemal ǈudevit"),
+        ("ΑΣ ΑΣ.", |s| s.title(), "Ας Ας."),
+        ("ßtraße ﬁne", |s| s.title(), "Sstraße Fine"),
+        ("hELLO wORLD", |s| s.capitalize(), "Hello world"),
+        ("ǆungla", |s| s.capitalize(), "ǅungla"),
+        ("ΟΔΟΣ", |s| s.capitalize(), "Οδος"),
+        ("Hello ΣΑΣ World", |s| s.swapcase(), "hELLO σας wORLD"),
+        ("Straße ǅ", |s| s.swapcase(), "sTRASSE ǅ"),
+        ("Straße ΣΑΣ ﬁ", |s| s.casefold(), "strasse σασ fi"),
+        ("ᏰᎵ", |s| s.casefold(), "ᏰᎵ"),
+        // The 'Ɤ' of Unicode 16 is unassigned in Unicode 14, so 'ɤ' has no
+        // uppercase.
+        ("ɤ\u{A7CB}", |s| s.swapcase(), "ɤ\u{A7CB}"),
+        ("ɤ\u{A7CB}", |s| s.title(), "ɤ\u{A7CB}"),
+    ];
+    for (input, method, expected) in table.iter() {
+        assert_eq!(method(input), *expected);
+    }
+    // The same results on chars, that are not normalized.
+    let vec_chars: Vec<char> = "ΑΣ ΑΣ.".chars().collect();
+    assert_eq!(vec_chars.title(), "Ας Ας.".chars().collect::<Vec<char>>());
+    assert_eq!(vec_chars[3..].swapcase(), "ας.".chars().collect::<Vec<char>>());
+
+    // @@ Test 2 - Padding and tabs.
+    assert_eq!("42".zfill(5), "00042");
+    assert_eq!("-42".zfill(5), "-0042");
+    assert_eq!("+".zfill(3), "+00");
+    assert_eq!("abc".center(6, '*'), "*abc**");
+    assert_eq!("abc".center(7, '*'), "**abc**");
+    assert_eq!("ab".center(5, '*'), "**ab*");
+    assert_eq!("ab".center(6, '*'), "**ab**");
+    assert_eq!("abc".center(2, '*'), "abc");
+    assert_eq!("ab".ljust(4, '.'), "ab..");
+    assert_eq!("ab".rjust(4, '.'), "..ab");
+    assert_eq!("01\t012\t0123\t01234".expandtabs(8), "01      012     0123    01234");
+    assert_eq!("01\t012\n0\t1".expandtabs(4), "01  012\n0   1");
+    assert_eq!("a\tb".expandtabs(0), "ab");
+    let vec_chars = "-7".get_vec_chars();
+    assert_eq!(vec_chars.zfill(4).to_string(), "-007");
+    assert_eq!(vec_chars.center(4, ' ').to_string(), " -7 ");
+
+    // @@ Test 3 - Search, partition and lines.
+    assert_eq!("aaaa".count("aa", None, None), 2);
+    assert_eq!("abc".count("", None, None), 4);
+    assert_eq!("abc".count("", Some(5), None), 0);
+    assert_eq!("abc".index("", Some(3), None), Some(3));
+    assert_eq!("hello".rindex("l", None, None), Some(3));
+    assert_eq!("hello".index("l", Some(-2), None), Some(3));
+    assert_eq!("hello".index("l", Some(1), Some(3)), Some(2));
+    assert_eq!("hello".index("lo", Some(1), Some(4)), None);
+    assert_eq!("a,b,c".partition(","), ("a", ",", "b,c"));
+    assert_eq!("a,b,c".rpartition(","), ("a,b", ",", "c"));
+    assert_eq!("abc".partition(","), ("abc", "", ""));
+    assert_eq!("abc".rpartition(","), ("", "", "abc"));
+    assert_eq!("TestHook".removeprefix("Test"), "Hook");
+    assert_eq!("MiscTests".removesuffix("Tests"), "Misc");
+    assert_eq!("ab\r\ncd\x0bef\u{2028}gh\n\n".splitlines(false), vec!["ab", "cd", "ef", "gh", ""]);
+    assert_eq!("ab\r\ncd\n".splitlines(true), vec!["ab\r\n", "cd\n"]);
+    assert!("".splitlines(false).is_empty());
+    let vec_chars = "héllo wörld".get_vec_chars();
+    assert_eq!(vec_chars.count(&['l'], None, None), 3);
+    assert_eq!(vec_chars.rindex(&['l'], None, Some(-2)), Some(3));
+    let (before, sep, after) = vec_chars.partition(&[' ']);
+    assert_eq!((before.to_string(), sep.to_string(), after.to_string()),
+               ("héllo".to_string(), " ".to_string(), "wörld".to_string()));
+    let lines = "a\nb\r\n".get_vec_chars();
+    assert_eq!(lines.splitlines(true), vec![&['a', '\n'][..], &['b', '\r', '\n'][..]]);
+
+    // @@ Test 4 - The predicates, alpha, digit, decimal, numeric, space,
+    //             title, identifier and printable.
+    let table: [(&str, [bool; 8]); 17] = [
+        ("Ⅻ½三²",       [false, false, false, true,  false, true,  false, true]),
+        ("²",           [false, true,  false, true,  false, false, false, true]),
+        ("٣",           [false, true,  true,  true,  false, false, false, true]),
+        ("abc",         [true,  false, false, false, false, false, true,  true]),
+        ("ab1",         [false, false, false, false, false, false, true,  true]),
+        ("ǅemal",       [true,  false, false, false, false, true,  true,  true]),
+        ("\x1c \u{3000}", [false, false, false, false, true,  false, false, false]),
+        ("_x1",         [false, false, false, false, false, false, true,  true]),
+        ("1x",          [false, false, false, false, false, false, false, true]),
+        ("ℵ·",          [false, false, false, false, false, false, true,  true]),
+        ("a\tb",        [false, false, false, false, false, false, false, false]),
+        ("",            [false, false, false, false, false, false, false, true]),
+        ("Hello World", [false, false, false, false, false, true,  false, true]),
+        ("Hello world", [false, false, false, false, false, false, false, true]),
+        ("HELLO",       [true,  false, false, false, false, false, true,  true]),
+        (" ",           [false, false, false, false, true,  false, false, true]),
+        ("\u{AD}",      [false, false, false, false, false, false, false, false]),
+    ];
+    for (input, expected) in table.iter() {
+        let results = [input.isalpha(), input.isdigit(), input.isdecimal(), input.isnumeric(),
+                       input.isspace(), input.istitle(), input.isidentifier(), input.isprintable()];
+        assert_eq!(results, *expected, "{:?}", input);
+        let vec_chars: Vec<char> = input.chars().collect();
+        let results = [vec_chars.isalpha(), vec_chars.isdigit(), vec_chars.isdecimal(), vec_chars.isnumeric(),
+                       vec_chars.isspace(), vec_chars.istitle(), vec_chars.isidentifier(), vec_chars.isprintable()];
+        assert_eq!(results, *expected, "{:?}", input);
+    }
+
+    // @@ Test 5 - The General Category.
+    assert_eq!(GeneralCategory::of('a'), GeneralCategory::Ll);
+    assert_eq!(GeneralCategory::of('ǅ'), GeneralCategory::Lt);
+    assert_eq!(GeneralCategory::of('\u{301}'), GeneralCategory::Mn);
+    assert_eq!(GeneralCategory::of('½').abbr(), "No");
+    assert!(GeneralCategory::of('€').is_symbol());
+    assert_eq!(GeneralCategory::of('\u{10FFFF}'), GeneralCategory::Cn);
+
+} // End of function test_py_str_methods()
+
+fn test_translation_table() {
+
+    // @@ Test 1 - maketrans() and translate() like in Python.
+    // 'abcabc'.translate(str.maketrans('abc', 'xyz', 'c')) == 'xyxy'
+    let table = TranslationTable::maketrans("abc", "xyz", "c").unwrap();
+    assert_eq!(table.len(), 3);
+    let vec_chars = "abcabc".get_vec_chars();
+    assert_eq!(vec_chars[..].translate(&table).to_string(), "xyxy");
+    assert_eq!(vec_chars[1..4].translate(&table).to_string(), "yx");
+    // The by-value translate() of the slice isn't shadowed by the in place one.
+    assert_eq!(vec_chars.translate(&table).to_string(), "xyxy");
+    assert_eq!(table.get(&'a'), Some(&['x'][..]));
+    assert_eq!(table.get(&'c'), Some(&[][..]));
+    assert_eq!(table.get(&'d'), None);
+    assert_eq!(TranslationTable::maketrans("ab", "x", ""),
+               Err(StringUtilsError::UnequalLengths { left: 2, right: 1 }));
+
+    // @@ Test 2 - One pass, the translated chars are not translated again.
+    let table: TranslationTable = [('a', 'b'), ('b', 'a')].into_iter().collect();
+    let mut vec_chars = "abba".get_vec_chars();
+    vec_chars.translate_in_place(&table);
+    assert_eq!(vec_chars.to_string(), "baab");
+
+    // @@ Test 3 - Transliteration to many chars and deletes, in place.
+    let mut table = TranslationTable::new();
+    table.insert_str('ß', "ss");
+    table.insert_vec('æ', &['a', 'e']);
+    table.insert_char('ø', 'o');
+    table.insert_delete('\u{AD}');
+    let mut vec_chars = "Straße, Ærø\u{AD}skøbing, cæsar".get_vec_chars();
+    vec_chars.translate_in_place(&table);
+    assert_eq!(vec_chars.to_string(), "Strasse, Æroskobing, caesar");
+    // Without the mappings to many chars the translation is in place.
+    assert!(table.remove('ß'));
+    assert!(table.remove('æ'));
+    assert!(!table.remove('æ'));
+    let mut vec_chars = "Strøm\u{AD}ßæ".get_vec_chars();
+    let capacity = vec_chars.capacity();
+    vec_chars.translate_in_place(&table);
+    assert_eq!(vec_chars.to_string(), "Stromßæ");
+    assert_eq!(vec_chars.capacity(), capacity);
+    // A later mapping replaces the one before.
+    table.insert_str('ø', "oe");
+    table.insert_str('\u{AD}', "-");
+    assert_eq!("Strø\u{AD}m".get_vec_chars()[..].translate(&table).to_string(), "Stroe-m");
+    table.insert_str('ø', "");
+    assert_eq!("Strøm".get_vec_chars()[..].translate(&table).to_string(), "Strm");
+
+    // @@ Test 4 - Against a translation char by char.
+    let mut table = TranslationTable::new();
+    table.insert_char('a', 'b');
+    table.insert_str('b', "xyz");
+    table.insert_delete('c');
+    let mut seed: u64 = 7;
+    for _ in 0..200 {
+        let mut vec_chars: Vec<char> = random_text(&mut seed, &['a', 'b', 'c', 'd'], 20).chars().collect();
+        let expected: String = vec_chars.iter().map(|c| match c {
+            'a' => "b".to_string(),
+            'b' => "xyz".to_string(),
+            'c' => String::new(),
+            _ => c.to_string(),
+        }).collect();
+        assert_eq!(vec_chars[..].translate(&table).to_string(), expected);
+        vec_chars.translate_in_place(&table);
+        assert_eq!(vec_chars.to_string(), expected);
+    }
+
+} // End of function test_translation_table()
+
+fn test_trim_matches() {
+
+    // @@ Test 1 - On slices, with a char, a set, a predicate and a sequence.
+    let vec_chars = "xxyhello worldyx".get_vec_chars();
+    assert_eq!(vec_chars[..].trim_matches('x').to_string(), "yhello worldy");
+    assert_eq!(vec_chars[..].trim_matches(['x', 'y']).to_string(), "hello world");
+    assert_eq!(vec_chars[..].trim_start_matches(AnyOf(&['x', 'y'])).to_string(), "hello worldyx");
+    assert_eq!(vec_chars[..].trim_end_matches(['x', 'y']).to_string(), "xxyhello world");
+    assert_eq!(vec_chars[..].trim_matches(|c: char| c != ' ').to_string(), " ");
+    assert_eq!(vec_chars[..].trim_matches(char::is_alphabetic).to_string(), " ");
+    let vec_chars = "ababcab".get_vec_chars();
+    assert_eq!(vec_chars[..].trim_matches("ab").to_string(), "c");
+    assert_eq!(vec_chars[..].trim_start_matches(&['a', 'b'][..]).to_string(), "cab");
+    // An empty sequence never matches.
+    assert_eq!(vec_chars[..].trim_matches("").to_string(), "ababcab");
+    // The matches at the start and at the end don't overlap.
+    let vec_chars = "aaa".get_vec_chars();
+    assert_eq!(vec_chars[..].trim_matches("aa").to_string(), "a");
+    assert_eq!(vec_chars[..].trim_matches('a').to_string(), "");
+    let char_str = CharStr::from_chars(&vec_chars);
+    assert_eq!(char_str.trim_end_matches('a'), "");
+    // Only the chars at the edges are tested.
+    let vec_chars: Vec<char> = "xx".chars().chain(std::iter::repeat_n('a', 1000)).chain("yx".chars()).collect();
+    let mut num_calls = 0;
+    let res = vec_chars[..].trim_matches(|c: char| { num_calls += 1; c == 'x' || c == 'y' });
+    assert_eq!(res.len(), 1000);
+    assert_eq!(num_calls, 6);
+
+    // @@ Test 2 - In place on Vec<char>, with the same results.
+    let inputs = ["", "   ", "  a", "a  ", " a b ", "xyx", "\t\n x \n", "yxyzy"];
+    for input in inputs.iter() {
+        let vec_chars = input.get_vec_chars();
+        for mode in 0..3 {
+            let mut vec_chars_mut = vec_chars.clone();
+            let expected = match mode {
+                0 => { vec_chars_mut.trim_matches(['x', 'y']); vec_chars[..].trim_matches(['x', 'y']) },
+                1 => { vec_chars_mut.trim_start_matches(['x', 'y']); vec_chars[..].trim_start_matches(['x', 'y']) },
+                _ => { vec_chars_mut.trim_end_matches(['x', 'y']); vec_chars[..].trim_end_matches(['x', 'y']) },
+            };
+            assert_eq!(&vec_chars_mut[..], expected);
+        }
+        // The whitespace trim against the Std.
+        let mut vec_chars_mut = vec_chars.clone();
+        vec_chars_mut.trim();
+        assert_eq!(vec_chars_mut.to_string(), input.trim());
+        let mut vec_chars_mut = vec_chars.clone();
+        vec_chars_mut.trim_start();
+        assert_eq!(vec_chars_mut.to_string(), input.trim_start());
+        let mut vec_chars_mut = vec_chars.clone();
+        vec_chars_mut.trim_end();
+        assert_eq!(vec_chars_mut.to_string(), input.trim_end());
+    }
+    let mut vec_chars = "--==title==--".get_vec_chars();
+    vec_chars.trim_matches(['-', '=']);
+    assert_eq!(vec_chars.to_string(), "title");
+
+} // End of function test_trim_matches()
+
+fn test_case_mapping() {
+
+    // @@ Test 1 - The mappings that change the len and the final sigma.
+    let to_string = |vec_chars: Vec<char>| -> String { vec_chars.into_iter().collect() };
+    assert_eq!(to_string("Straße ﬁne".get_vec_chars().to_uppercase()), "STRASSE FINE");
+    assert_eq!(to_string("İ".get_vec_chars().to_lowercase()), "i\u{307}");
+    assert_eq!(to_string("ΟΔΟΣ ΟΔΟΣ. Σ ΑΣ'Α".get_vec_chars().to_lowercase()), "οδος οδος. σ ασ'α");
+    assert_eq!(to_string("ΌΣΟΣ\u{301}".get_vec_chars().to_lowercase()), "όσος\u{301}");
+    assert_eq!(to_string("Straße ΣΑΣ ﬁ".get_vec_chars().to_casefold()), "strasse σασ fi");
+    // The mappings of Unicode 14, whatever the version of the Std.
+    assert_eq!(to_string("ɤ\u{A7CB}".get_vec_chars().to_uppercase()), "ɤ\u{A7CB}");
+    assert_eq!(to_string("ɤ\u{A7CB}".get_vec_chars().to_lowercase()), "ɤ\u{A7CB}");
+    // The same results as the Std on a whole str.
+    let mut seed: u64 = 11;
+    let alphabet = ['a', 'Σ', 'σ', 'ß', 'İ', 'ﬁ', '\'', '\u{301}', ' ', 'Ω', 'x', 'ǅ'];
+    for _ in 0..300 {
+        let text = random_text(&mut seed, &alphabet, 9);
+        let vec_chars: Vec<char> = text.chars().collect();
+        assert_eq!(to_string(vec_chars.to_uppercase()), text.to_uppercase());
+        assert_eq!(to_string(vec_chars.to_lowercase()), text.to_lowercase());
+    }
+
+    // @@ Test 2 - Titlecase, words with the apostrophe inside.
+    assert_eq!(to_string("they're bill's ǆungla".get_vec_chars().to_titlecase()), "They're Bill's ǅungla");
+    assert_eq!(to_string("ﬁsh and ßIPS-OK".get_vec_chars().to_titlecase()), "Fish And Ssips-Ok");
+    assert_eq!(to_string("ΟΔΟΣ ΟΔΟΣ".get_vec_chars().to_titlecase()), "Οδος Οδος");
+
+    // @@ Test 3 - Turkish and Azeri.
+    let turkic = CaseLocale::Turkic;
+    assert_eq!(to_string("istanbul diyarbakır".get_vec_chars().to_uppercase_with(turkic)), "İSTANBUL DİYARBAKIR");
+    assert_eq!(to_string("ISPARTA İZMİR".get_vec_chars().to_lowercase_with(turkic)), "ısparta izmir");
+    // An 'I' with a combining dot above is an 'i' without the dot.
+    assert_eq!(to_string("I\u{307}I\u{323}\u{307}".chars().collect::<Vec<char>>().to_lowercase_with(turkic)), "ii\u{323}");
+    assert_eq!(to_string("ilk Işık".get_vec_chars().to_titlecase_with(turkic)), "İlk Işık");
+    assert_eq!(to_string("KIRIK İ".get_vec_chars().to_casefold_with(turkic)), "kırık i");
+    assert_eq!(to_string("KIRIK".get_vec_chars().to_casefold()), "kirik");
+
+    // @@ Test 4 - Lithuanian.
+    let lithuanian = CaseLocale::Lithuanian;
+    let vec_chars: Vec<char> = "\u{CC}I\u{301}J\u{303}IA".chars().collect();
+    assert_eq!(to_string(vec_chars.to_lowercase_with(lithuanian)),
+               "i\u{307}\u{300}i\u{307}\u{301}j\u{307}\u{303}ia");
+    assert_eq!(to_string(vec_chars.to_lowercase()), "\u{EC}i\u{301}j\u{303}ia");
+    // The dot of a soft dotted char is removed in uppercase.
+    let vec_chars: Vec<char> = "i\u{307}\u{301} j\u{323}\u{307} a\u{307}".chars().collect();
+    assert_eq!(to_string(vec_chars.to_uppercase_with(lithuanian)), "I\u{301} J\u{323} A\u{307}");
+    assert_eq!(to_string(vec_chars.to_uppercase()), "I\u{307}\u{301} J\u{323}\u{307} A\u{307}");
+
+    // @@ Test 5 - In place, only when no char changes the len.
+    let mut vec_chars = "Olá ΟΔΟΣ".get_vec_chars();
+    assert!(vec_chars.make_lowercase(CaseLocale::Root));
+    assert_eq!(vec_chars.to_string(), "olá οδος");
+    assert!(vec_chars.make_titlecase(CaseLocale::Root));
+    assert_eq!(vec_chars.to_string(), "Olá Οδος");
+    assert!(vec_chars[4..].make_uppercase(CaseLocale::Root));
+    assert_eq!(vec_chars.to_string(), "Olá ΟΔΟΣ");
+    let mut vec_chars = "straße".get_vec_chars();
+    assert!(!vec_chars.make_uppercase(CaseLocale::Root));
+    assert!(!vec_chars.make_casefold(CaseLocale::Root));
+    assert_eq!(vec_chars.to_string(), "straße");
+    let mut vec_chars = "İi".get_vec_chars();
+    assert!(!vec_chars.make_lowercase(CaseLocale::Root));
+    assert!(vec_chars.make_lowercase(CaseLocale::Turkic));
+    assert_eq!(vec_chars.to_string(), "ii");
+    assert!(vec_chars.make_uppercase(CaseLocale::Turkic));
+    assert_eq!(vec_chars.to_string(), "İİ");
+
+} // End of function test_case_mapping()
+
+fn test_match_options() {
+
+    // @@ Test 1 - Case insensitive, with the simple case folding.
+    let case_insensitive = MatchOptions::new().case_insensitive(true);
+    let vec_chars = "Hello WORLD, hello ẞtraſſe ΣΑΣ".get_vec_chars();
+    assert_eq!(vec_chars.find_str_with("hello", 0, None, case_insensitive), Some(0..5));
+    assert_eq!(vec_chars.find_str_with("hello", 1, None, case_insensitive), Some(13..18));
+    assert_eq!(vec_chars.find_str_with("hello", 1, Some(12), case_insensitive), None);
+    assert_eq!(vec_chars.find_str_with("world", 0, None, MatchOptions::new()), None);
+    assert!(vec_chars.contains_str_with("world", case_insensitive));
+    assert!(vec_chars.contains_str_with("ßTRASSE", case_insensitive));
+    assert!(vec_chars.contains_str_with("σας", case_insensitive));
+    // The simple folding never changes the len, so 'ß' doesn't match "ss".
+    assert!(!vec_chars.contains_str_with("strasse", case_insensitive));
+    assert!(!vec_chars.contains_str_with("", case_insensitive));
+    assert!(!Vec::<char>::new().contains_str_with("a", case_insensitive));
+
+    // @@ Test 2 - Normalized, the positions are of the original chars.
+    let normalize = MatchOptions::new().normalize(true);
+    let vec_chars: Vec<char> = "cafe\u{301} e café".chars().collect();
+    assert_eq!(vec_chars.find_str_with("é", 0, None, normalize), Some(3..5));
+    assert_eq!(vec_chars.find_str_with("é", 5, None, normalize), Some(11..12));
+    assert_eq!(vec_chars.find_str_with("é", 0, None, MatchOptions::new()), Some(11..12));
+    // An 'e' inside of a precomposed 'é' or before an accent isn't a match.
+    assert_eq!(vec_chars.find_str_with("e", 0, None, normalize), Some(6..7));
+    assert_eq!(vec_chars.find_str_with("e", 7, None, normalize), None);
+    // The marks of other classes can be in any order.
+    let vec_chars: Vec<char> = "xa\u{301}\u{323}y".chars().collect();
+    assert_eq!(vec_chars.find_str_with("a\u{323}\u{301}", 0, None, normalize), Some(1..4));
+    assert_eq!(vec_chars.find_str_with("ạ\u{301}", 0, None, normalize), Some(1..4));
+    assert_eq!(vec_chars.find_str_with("a\u{301}", 0, None, normalize), None);
+
+    // @@ Test 3 - Replace and split, with matches of many lens.
+    let both = MatchOptions::new().case_insensitive(true).normalize(true);
+    let mut vec_chars: Vec<char> = "ÉCOLE, E\u{301}cole et école".chars().collect();
+    assert_eq!(vec_chars.split_str_with("école", both).iter().map(|slice| slice.to_string()).collect::<Vec<String>>(),
+               vec![", ", " et "]);
+    assert_eq!(vec_chars.replace_str_all_with("e\u{301}cole", "school", both), Some(3));
+    assert_eq!(vec_chars.to_string(), "school, school et school");
+    assert_eq!(vec_chars.replace_str_all_with("X", "y", both), None);
+    assert!(vec_chars.split_str_with("X", both).is_empty());
+
+    // @@ Test 4 - The same results as the methods without options.
+    let mut seed: u64 = 23;
+    let alphabet = ['a', 'A', 'b', 'B', ' '];
+    for _ in 0..300 {
+        let text = random_text(&mut seed, &alphabet, 20);
+        let pattern = random_text(&mut seed, &alphabet, 4);
+        let vec_chars = text.get_vec_chars();
+        let options = MatchOptions::new();
+        assert_eq!(vec_chars.find_str_with(&pattern, 0, None, options).map(|range| range.start),
+                   vec_chars.find_str(&pattern, 0, None));
+        assert_eq!(vec_chars.split_str_with(&pattern, options), vec_chars.split_str(&pattern));
+        let mut vec_chars_with = vec_chars.clone();
+        let mut vec_chars_without = vec_chars.clone();
+        assert_eq!(vec_chars_with.replace_str_all_with(&pattern, "_", options),
+                   vec_chars_without.replace_str_all(&pattern, "_"));
+        assert_eq!(vec_chars_with, vec_chars_without);
+        // Without case, like the search in the lowercase of both.
+        let lowercase_vec_chars = text.to_lowercase().get_vec_chars();
+        let lowercase_pattern = pattern.to_lowercase();
+        assert_eq!(vec_chars.find_str_with(&pattern, 0, None, case_insensitive).map(|range| range.start),
+                   lowercase_vec_chars.find_str(&lowercase_pattern, 0, None));
+        assert_eq!(vec_chars.split_str_with(&pattern, case_insensitive).len(),
+                   lowercase_vec_chars.split_str(&lowercase_pattern).len());
+    }
+
+} // End of function test_match_options()
+
+fn test_diacritics() {
+
+    // @@ Test 1 - Strip the diacritics, with the indexes of the original chars.
+    assert_eq!("noçl noãl noél".get_vec_chars().strip_diacritics().to_string(), "nocl noal noel");
+    let vec_chars: Vec<char> = "a\u{301}ǖ\u{327}ø한".chars().collect();
+    let (stripped, indexes) = vec_chars.strip_diacritics_indexed();
+    assert_eq!(stripped.to_string(), "auø한");
+    assert_eq!(indexes, vec![0, 2, 4, 5]);
+    let mut vec_chars_in_place = vec_chars.clone();
+    vec_chars_in_place.strip_diacritics_in_place();
+    assert_eq!(vec_chars_in_place, stripped);
+    assert!(Vec::<char>::new().strip_diacritics_indexed().0.is_empty());
+
+    // @@ Test 2 - Find, contains and eq without accents.
+    let ignore_accents = MatchOptions::new().ignore_accents(true);
+    let vec_chars = "O Natal é no\u{E7}l, nao e noel.".get_vec_chars();
+    assert_eq!(vec_chars.find_str_with("nocl", 0, None, ignore_accents), Some(10..14));
+    assert_eq!(vec_chars.find_str_with("não é", 0, None, ignore_accents), Some(16..21));
+    assert_eq!(vec_chars.find_str_with("noél", 0, None, ignore_accents), Some(22..26));
+    assert_eq!(vec_chars.find_str_with("noél", 0, None, MatchOptions::new()), None);
+    assert!(vec_chars.contains_str_with("natal e", ignore_accents.case_insensitive(true)));
+    assert!(!vec_chars.contains_str_with("natal e", ignore_accents));
+    assert!("noçl".get_vec_chars().eq_str_with("nocl", ignore_accents));
+    assert!("noãl".get_vec_chars().eq_str_with("NOAL", ignore_accents.case_insensitive(true)));
+    assert!(!"noãl".get_vec_chars().eq_str_with("noel", ignore_accents));
+    assert!(!"noãl".get_vec_chars().eq_str_with("noal", MatchOptions::new()));
+    // The marks of a decomposed text are inside of the range of the match.
+    let vec_chars: Vec<char> = "no\u{E3}\u{301}l nao\u{303}".chars().collect();
+    assert_eq!(vec_chars.find_str_with("noal", 0, None, ignore_accents), Some(0..5));
+    assert_eq!(vec_chars.find_str_with("nao", 0, None, ignore_accents), Some(6..10));
+    assert_eq!(vec_chars.find_str_with("nao", 0, None, ignore_accents.normalize(true)), Some(6..10));
+
+    // @@ Test 3 - Replace and split without accents.
+    let mut vec_chars = "Ação, acao e AÇÃO".get_vec_chars();
+    let options = ignore_accents.case_insensitive(true);
+    assert_eq!(vec_chars.split_str_with(", ", options).len(), 2);
+    assert_eq!(vec_chars.replace_str_all_with("acao", "x", options), Some(3));
+    assert_eq!(vec_chars.to_string(), "x, x e x");
+
+} // End of function test_diacritics()
+
+fn test_regex() {
+
+    // @@ Test 1 - Find, the positions are char indexes.
+    let vec_chars = "Olá, são 12 maçãs e 7 pêras.".get_vec_chars();
+    let regex = CharRegex::new(r"\d+").unwrap();
+    assert_eq!(vec_chars.find_regex(&regex, 0), Some(9..11));
+    assert_eq!(vec_chars.find_regex(&regex, 11), Some(20..21));
+    assert_eq!(vec_chars.find_regex(&regex, vec_chars.len()), None);
+    let words = CharRegex::new(r"\b\p{L}+\b").unwrap();
+    let found: Vec<String> = vec_chars.find_iter_regex(&words).map(|range| vec_chars[range].to_string()).collect();
+    assert_eq!(found, vec!["Olá", "são", "maçãs", "e", "pêras"]);
+    assert!(CharRegex::new(r"(?i)MAÇÃS").unwrap().is_match(&vec_chars));
+    assert!(!CharRegex::new(r"MAÇÃS").unwrap().is_match(&vec_chars));
+    assert!(CharRegex::new(r"^Olá").unwrap().is_match(&vec_chars));
+    assert!(!CharRegex::new(r"^são").unwrap().is_match(&vec_chars));
+    assert_eq!(CharRegex::new(r"(?m)^b$").unwrap().find(&"a\nb\nc".get_vec_chars()), Some(2..3));
+    assert_eq!(CharRegex::new(r"a.c").unwrap().find(&"a\nc".get_vec_chars()), None);
+    assert_eq!(CharRegex::new(r"(?s)a.c").unwrap().find(&"a\nc".get_vec_chars()), Some(0..3));
+
+    // @@ Test 2 - Leftmost first, greedy and lazy repetitions.
+    let vec_chars = "<a><b>".get_vec_chars();
+    assert_eq!(CharRegex::new(r"<.+>").unwrap().find(&vec_chars), Some(0..6));
+    assert_eq!(CharRegex::new(r"<.+?>").unwrap().find(&vec_chars), Some(0..3));
+    assert_eq!(CharRegex::new(r"a|ab").unwrap().find(&"ab".get_vec_chars()), Some(0..1));
+    assert_eq!(CharRegex::new(r"x{2,3}").unwrap().find(&"xxxxx".get_vec_chars()), Some(0..3));
+    assert_eq!(CharRegex::new(r"x{2,}?").unwrap().find(&"xxxxx".get_vec_chars()), Some(0..2));
+    let empty_matches: Vec<_> = CharRegex::new(r"x*").unwrap().find_iter(&"abxd".get_vec_chars()).collect();
+    assert_eq!(empty_matches, vec![0..0, 1..1, 2..3, 4..4]);
+
+    // @@ Test 3 - Captures, named groups and the replace with groups.
+    let vec_chars = "2022-05-19 e 2023-12-01".get_vec_chars();
+    let date = CharRegex::new(r"(?<year>\d{4})-(\d\d)-(\d\d)").unwrap();
+    assert_eq!(date.captures_len(), 4);
+    let captures = vec_chars.captures(&date).unwrap();
+    assert_eq!(captures.get(0), Some(0..10));
+    assert_eq!(captures.name("year"), Some(0..4));
+    assert_eq!(captures.get(3), Some(8..10));
+    assert_eq!(captures.get(4), None);
+    assert_eq!(CharRegex::new(r"(a)|(b)").unwrap().captures(&"b".get_vec_chars()).unwrap().get(1), None);
+    let mut dates = vec_chars.clone();
+    assert_eq!(dates.replace_regex(&date, "$3/$2/${year} $$"), Some(2));
+    assert_eq!(dates.to_string(), "19/05/2022 $ e 01/12/2023 $");
+    assert_eq!(dates.replace_regex(&date, "x"), None);
+
+    // @@ Test 4 - Split, like split_vec().
+    let vec_chars = "a, b;c ,,d".get_vec_chars();
+    let separators = CharRegex::new(r"\s*[,;]\s*").unwrap();
+    let pieces: Vec<String> = vec_chars.split_regex(&separators).iter().map(|piece| piece.to_string()).collect();
+    assert_eq!(pieces, vec!["a", "b", "c", "d"]);
+
+    // @@ Test 5 - The errors with the position of the char.
+    let invalid_regex = |pattern: &str| CharRegex::new(pattern).unwrap_err();
+    assert_eq!(invalid_regex("(ab"), StringUtilsError::InvalidRegex { position: 3, reason: "unclosed group" });
+    assert_eq!(invalid_regex("ab)"), StringUtilsError::InvalidRegex { position: 2, reason: "unmatched ')'" });
+    assert_eq!(invalid_regex("a**"), StringUtilsError::InvalidRegex { position: 2, reason: "repetition of a repetition" });
+    assert_eq!(invalid_regex("[z-a]"), StringUtilsError::InvalidRegex { position: 4, reason: "range with the start greater then the end" });
+    assert_eq!(invalid_regex(r"\p{Foo}"), StringUtilsError::InvalidRegex { position: 3, reason: "unknown property" });
+    assert_eq!(invalid_regex(r"\q"), StringUtilsError::InvalidRegex { position: 1, reason: "unknown escape" });
+    assert_eq!(invalid_regex(&"(".repeat(30_000)), StringUtilsError::InvalidRegex { position: 250, reason: "nested too deeply" });
+    assert_eq!(invalid_regex(&"(?:".repeat(100_000)), StringUtilsError::InvalidRegex { position: 750, reason: "nested too deeply" });
+    assert_eq!(invalid_regex(&"()".repeat(8000)), StringUtilsError::InvalidRegex { position: 0, reason: "the regex is too big" });
+    assert_eq!(CharRegex::new(&format!("{}a{}", "(".repeat(250), ")".repeat(250))).unwrap().captures_len(), 251);
+
+    // @@ Test 6 - The same matches as CharFinder for the literal patterns.
+    let mut seed: u64 = 31;
+    let alphabet = ['a', 'b', 'ç', '.'];
+    for _ in 0..300 {
+        let vec_chars = random_text(&mut seed, &alphabet, 30).get_vec_chars();
+        let pattern = random_text(&mut seed, &alphabet, 5);
+        if pattern.is_empty() {
+            continue;
+        }
+        let regex = CharRegex::new(&pattern.replace('.', r"\.")).unwrap();
+        let finder = CharFinder::new_str(&pattern);
+        let regex_starts: Vec<usize> = vec_chars.find_iter_regex(&regex).map(|range| range.start).collect();
+        assert_eq!(regex_starts, finder.find_iter(&vec_chars).collect::<Vec<usize>>());
+    }
+
+} // End of function test_regex()
+
+fn test_glob() {
+
+    // @@ Test 1 - Matches the whole text, each '?' is one char.
+    let glob = CharGlob::new("config.{toml,y?ml,json*}").unwrap();
+    assert!(glob.matches_str("config.toml"));
+    assert!(glob.matches_str("config.yaml"));
+    assert!(glob.matches_str("config.json5"));
+    assert!(!glob.matches_str("config.ini"));
+    assert!(!glob.matches_str("my.config.toml"));
+    assert!(CharGlob::new("ma??s").unwrap().matches_str("maçãs"));
+    assert!(CharGlob::new("*").unwrap().matches_str(""));
+    assert!(CharGlob::new("{,a}b").unwrap().matches_str("b"));
+    assert!(CharGlob::new("{a,{b,c}d}").unwrap().matches_str("cd"));
+    assert!(CharGlob::new(r"\*.\?").unwrap().matches_str("*.?"));
+    assert!(!CharGlob::new(r"\*.\?").unwrap().matches_str("a.b"));
+
+    // @@ Test 2 - The classes.
+    let glob = CharGlob::new("file[0-9][!a-c]").unwrap();
+    assert!(glob.matches_str("file1z"));
+    assert!(!glob.matches_str("file1b"));
+    assert!(!glob.matches_str("fileX1"));
+    assert!(CharGlob::new("[]a]").unwrap().matches_str("]"));
+    assert!(CharGlob::new("[^]]").unwrap().matches_str("a"));
+    assert!(CharGlob::new("[a-]").unwrap().matches_str("-"));
+    assert!(CharGlob::new("[à-ú]").unwrap().matches_str("é"));
+
+    // @@ Test 3 - Case insensitive.
+    let glob = CharGlob::new_case_insensitive("*.TXT").unwrap();
+    assert!(glob.is_case_insensitive());
+    assert!(glob.matches_str("Notas.txt"));
+    assert!(CharGlob::new_case_insensitive("[a-c]ÇÃO").unwrap().matches_str("Ação"));
+    assert!(!CharGlob::new("*.TXT").unwrap().matches_str("Notas.txt"));
+
+    // @@ Test 4 - Find, contains, starts and ends with in a Vec<char>.
+    let vec_chars = "logs: app.log, db.log.1 e x.log".get_vec_chars();
+    let glob = CharGlob::new("[a-z][a-z].log").unwrap();
+    assert!(vec_chars.contains_glob(&glob));
+    assert_eq!(vec_chars.find_glob(&glob, 0), Some(7..13));
+    assert_eq!(vec_chars.find_glob(&glob, 8), Some(15..21));
+    assert_eq!(vec_chars.find_glob(&glob, 16), None);
+    assert_eq!(vec_chars.find_all_glob(&CharGlob::new("?.log").unwrap()), vec![8..13, 16..21, 26..31]);
+    assert!(vec_chars.start_with_glob(&CharGlob::new("log?:").unwrap()));
+    assert!(!vec_chars.start_with_glob(&CharGlob::new("app").unwrap()));
+    assert!(vec_chars.ends_with_glob(&CharGlob::new("x.{log,txt}").unwrap()));
+    assert!(!vec_chars.ends_with_glob(&CharGlob::new("db.log").unwrap()));
+    assert!(vec_chars.matches_glob(&CharGlob::new("logs:*.log").unwrap()));
+
+    // @@ Test 5 - The errors with the position of the char.
+    assert_eq!(CharGlob::new("a{b,c").unwrap_err(), StringUtilsError::InvalidGlob { position: 1, reason: "unclosed '{'" });
+    assert_eq!(CharGlob::new("ab[cd").unwrap_err(), StringUtilsError::InvalidGlob { position: 2, reason: "unclosed '['" });
+    assert_eq!(CharGlob::new("[z-a]").unwrap_err(),
+               StringUtilsError::InvalidGlob { position: 3, reason: "range with the start greater then the end" });
+    let glob_error = StringUtilsError::InvalidGlob { position: 0, reason: "glob too big or nested too deeply" };
+    let nested_glob = format!("{}a{}", "{".repeat(300), "}".repeat(300));
+    assert_eq!(CharGlob::new(&nested_glob).unwrap_err(), glob_error);
+    assert_eq!(CharGlob::new(&"ab".repeat(60_000)).unwrap_err(), glob_error);
+
+    // @@ Test 6 - The same results as a simple matcher with backtracking.
+    fn simple_match(glob: &[char], text: &[char]) -> bool {
+        match glob.split_first() {
+            None => text.is_empty(),
+            Some(('*', rest)) => (0..=text.len()).any(|skip| simple_match(rest, &text[skip..])),
+            Some(('?', rest)) => !text.is_empty() && simple_match(rest, &text[1..]),
+            Some((c, rest)) => text.first() == Some(c) && simple_match(rest, &text[1..]),
+        }
+    }
+    let mut seed: u64 = 41;
+    for _ in 0..500 {
+        let glob_str = random_text(&mut seed, &['a', 'b', 'ç', '*', '?'], 7);
+        let text = random_text(&mut seed, &['a', 'b', 'ç'], 9);
+        let glob = CharGlob::new(&glob_str).unwrap();
+        assert_eq!(glob.matches_str(&text), simple_match(&glob_str.get_vec_chars(), &text.get_vec_chars()));
+    }
+
+} // End of function test_glob()
+
+fn test_fuzzy_search() {
+
+    // @@ Test 1 - The best match, with the fewest edits.
+    let vec_chars = "O rato roeu a roupa do rei de Roma.".get_vec_chars();
+    assert_eq!(vec_chars.find_fuzzy_str("roupa", 0, 0, None), Some(FuzzyMatch { start: 14, end: 19, distance: 0 }));
+    assert_eq!(vec_chars.find_fuzzy_str("ropa", 1, 0, None), Some(FuzzyMatch { start: 14, end: 19, distance: 1 }));
+    assert_eq!(vec_chars.find_fuzzy_str("Rooma", 1, 0, None), Some(FuzzyMatch { start: 30, end: 34, distance: 1 }));
+    assert_eq!(vec_chars.find_fuzzy_str("xyzw", 2, 0, None), None);
+    assert_eq!(vec_chars.find_fuzzy_str("", 2, 0, None), None);
+    assert_eq!("maçãs".get_vec_chars().find_fuzzy_str("macas", 2, 0, None), Some(FuzzyMatch { start: 0, end: 5, distance: 2 }));
+
+    // @@ Test 2 - The start and end limits of the start of the match.
+    assert_eq!(vec_chars.find_fuzzy_str("roeu", 1, 3, None), Some(FuzzyMatch { start: 7, end: 11, distance: 0 }));
+    assert_eq!(vec_chars.find_fuzzy_str("roeu", 1, 8, None).map(|m| m.distance), Some(1));
+    assert_eq!(vec_chars.find_fuzzy_str("Roma", 0, 0, Some(20)), None);
+    assert_eq!(vec_chars.find_fuzzy_str("Roma", 0, 0, Some(30)), Some(FuzzyMatch { start: 30, end: 34, distance: 0 }));
+    assert_eq!(Vec::<char>::new().find_fuzzy_str("a", 1, 0, None), None);
+
+    // @@ Test 3 - All the non overlapping matches.
+    let vec_chars = "color colour colr kolor".get_vec_chars();
+    let matches = vec_chars.find_fuzzy_all(&"color".get_vec_chars(), 1, 0, None);
+    assert_eq!(matches, vec![FuzzyMatch { start: 0, end: 5, distance: 0 },
+                             FuzzyMatch { start: 6, end: 12, distance: 1 },
+                             FuzzyMatch { start: 13, end: 17, distance: 1 },
+                             FuzzyMatch { start: 18, end: 23, distance: 1 }]);
+    assert_eq!(vec_chars.find_fuzzy_all(&"color".get_vec_chars(), 1, 1, Some(12)).len(), 2);
+    let finder = CharFuzzyFinder::new_str("colour", 0);
+    assert_eq!(finder.find_all(&vec_chars), vec![FuzzyMatch { start: 6, end: 12, distance: 0 }]);
+
+    // @@ Test 4 - The same distances as a simple dynamic program, with
+    //             patterns longer than the 64 bits of a block.
+    fn levenshtein(a: &[char], b: &[char]) -> usize {
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, a_c) in a.iter().enumerate() {
+            let mut diagonal = row[0];
+            row[0] = i + 1;
+            for j in 1..=b.len() {
+                let value = (diagonal + usize::from(*a_c != b[j - 1])).min(row[j] + 1).min(row[j - 1] + 1);
+                diagonal = row[j];
+                row[j] = value;
+            }
+        }
+        row[b.len()]
+    }
+    let mut seed: u64 = 7;
+    let next = |seed: &mut u64, max: u64| -> usize { (next_random(seed) % max) as usize };
+    let alphabet = ['a', 'b', 'ç', 'd'];
+    for round in 0..300 {
+        let max_pattern_len = if round % 3 == 0 { 150 } else { 8 };
+        let pattern: Vec<char> = (0..1 + next(&mut seed, max_pattern_len)).map(|_| alphabet[next(&mut seed, 4)]).collect();
+        let mut text: Vec<char> = (0..next(&mut seed, 40)).map(|_| alphabet[next(&mut seed, 4)]).collect();
+        if round % 2 == 0 {
+            // A copy of the pattern with some edits.
+            let at = next(&mut seed, text.len() as u64 + 1);
+            let mut copy = pattern.clone();
+            for _ in 0..next(&mut seed, 4) {
+                let index = next(&mut seed, copy.len() as u64);
+                copy[index] = alphabet[next(&mut seed, 4)];
+            }
+            text.splice(at..at, copy);
+        }
+        let max_edits = next(&mut seed, 1 + pattern.len() as u64 / 4 + 2);
+        let best_distance = (0..=text.len())
+            .flat_map(|start| (start..=text.len()).map(move |end| (start, end)))
+            .map(|(start, end)| levenshtein(&pattern, &text[start..end]))
+            .min()
+            .unwrap();
+        let finder = CharFuzzyFinder::new(&pattern, max_edits);
+        match finder.find(&text) {
+            Some(fuzzy_match) => {
+                assert_eq!(fuzzy_match.distance, best_distance);
+                assert_eq!(levenshtein(&pattern, &text[fuzzy_match.start..fuzzy_match.end]), best_distance);
+            }
+            None => assert!(best_distance > max_edits),
+        }
+        let mut last_end = 0;
+        for fuzzy_match in finder.find_all(&text) {
+            assert!(fuzzy_match.start >= last_end && fuzzy_match.distance <= max_edits);
+            assert_eq!(levenshtein(&pattern, &text[fuzzy_match.start..fuzzy_match.end]), fuzzy_match.distance);
+            last_end = fuzzy_match.end;
+        }
+    }
+
+} // End of function test_fuzzy_search()
+
+fn test_similarity() {
+    let chars = |p_str: &str| p_str.get_vec_chars();
+    let is_close = |left: f64, right: f64| (left - right).abs() < 1e-4;
+
+    // @@ Test 1 - The edit distances.
+    assert_eq!(similarity::levenshtein(&chars("kitten"), &chars("sitting")), 3);
+    assert_eq!(similarity::levenshtein(&chars(""), &chars("abc")), 3);
+    assert_eq!(similarity::levenshtein(&chars("ação"), &chars("acao")), 2);
+    assert_eq!(similarity::osa_distance(&chars("ca"), &chars("abc")), 3);
+    assert_eq!(similarity::damerau_levenshtein(&chars("ca"), &chars("abc")), 2);
+    assert_eq!(similarity::osa_distance(&chars("abcdef"), &chars("abdcef")), 1);
+    assert_eq!(similarity::damerau_levenshtein(&chars("abcdef"), &chars("abdcef")), 1);
+    assert_eq!(similarity::hamming(&chars("karolin"), &chars("kathrin")), Ok(3));
+    assert_eq!(similarity::hamming(&chars("ab"), &chars("abc")), Err(StringUtilsError::UnequalLengths { left: 2, right: 3 }));
+    assert!(is_close(similarity::normalized_levenshtein(&chars("kitten"), &chars("sitting")), 1.0 - 3.0 / 7.0));
+
+    // @@ Test 2 - The bounded variants.
+    assert_eq!(similarity::levenshtein_bounded(&chars("kitten"), &chars("sitting"), 3), Some(3));
+    assert_eq!(similarity::levenshtein_bounded(&chars("kitten"), &chars("sitting"), 2), None);
+    assert_eq!(similarity::levenshtein_bounded(&chars("a"), &chars("abcd"), 2), None);
+    assert_eq!(similarity::osa_distance_bounded(&chars("ab"), &chars("ba"), 1), Some(1));
+    assert_eq!(similarity::damerau_levenshtein_bounded(&chars("ca"), &chars("abc"), 1), None);
+    assert_eq!(similarity::hamming_bounded(&chars("karolin"), &chars("kathrin"), 2), None);
+    assert_eq!(similarity::hamming_bounded(&chars("karolin"), &chars("kathrin"), 3), Some(3));
+
+    // @@ Test 3 - Jaro and Jaro-Winkler.
+    assert!(is_close(similarity::jaro(&chars("MARTHA"), &chars("MARHTA")), 0.9444));
+    assert!(is_close(similarity::jaro_winkler(&chars("MARTHA"), &chars("MARHTA")), 0.9611));
+    assert!(is_close(similarity::jaro(&chars("DIXON"), &chars("DICKSONX")), 0.7667));
+    assert!(is_close(similarity::jaro_winkler(&chars("DIXON"), &chars("DICKSONX")), 0.8133));
+    assert_eq!(similarity::jaro(&chars("abc"), &chars("xyz")), 0.0);
+    assert_eq!(similarity::jaro_winkler(&chars(""), &chars("")), 1.0);
+
+    // @@ Test 4 - The longest common subsequence and substring.
+    assert_eq!(similarity::longest_common_subsequence_len(&chars("ABCBDAB"), &chars("BDCABA")), 4);
+    assert_eq!(similarity::longest_common_subsequence(&chars("ABCBDAB"), &chars("BDCABA")).len(), 4);
+    assert_eq!(similarity::longest_common_subsequence(&chars("pão de ló"), &chars("pé de lã")).to_string(), "p de l");
+    assert_eq!(similarity::longest_common_substring(&chars("xabcdy"), &chars("zzabcdw")), (1..5, 2..6));
+    assert_eq!(similarity::longest_common_substring(&chars("abc"), &chars("xyz")), (0..0, 0..0));
+
+    // @@ Test 5 - The n-gram similarities.
+    assert!(is_close(similarity::jaccard(&chars("night"), &chars("nacht"), 2), 1.0 / 7.0));
+    assert!(is_close(similarity::sorensen_dice(&chars("night"), &chars("nacht"), 2), 0.25));
+    assert!(is_close(similarity::cosine(&chars("night"), &chars("nacht"), 2), 0.25));
+    assert!(is_close(similarity::cosine(&chars("aaab"), &chars("aab"), 2), 3.0 / (5.0_f64.sqrt() * 2.0_f64.sqrt())));
+    assert_eq!(similarity::jaccard(&chars("a"), &chars("a"), 2), 1.0);
+    assert_eq!(similarity::sorensen_dice(&chars("a"), &chars("b"), 2), 0.0);
+
+    // @@ Test 6 - In graphemes, a flag or an emoji with a skin tone is one
+    //             element.
+    let left = chars("🇵🇹 ok 👍🏽");
+    let right = chars("🇧🇷 ok 👍");
+    assert_eq!(similarity::graphemes(&left).len(), 6);
+    assert_eq!(similarity::levenshtein(&left, &right), 3);
+    assert_eq!(similarity::levenshtein(&similarity::graphemes(&left), &similarity::graphemes(&right)), 2);
+    assert_eq!(similarity::jaccard(&similarity::graphemes(&left), &similarity::graphemes(&left), 1), 1.0);
+
+    // @@ Test 7 - The bounded variants agree with the full ones, and the
+    //             transpositions never add edits.
+    let mut seed: u64 = 13;
+    let mut random_chars = |max_len: u64| -> Vec<char> {
+        random_text(&mut seed, &['a', 'b', 'ç', 'd'], max_len).chars().collect()
+    };
+    for max in 0..400 {
+        let (left, right) = (random_chars(10), random_chars(10));
+        let max = max % 6;
+        let levenshtein = similarity::levenshtein(&left, &right);
+        let osa = similarity::osa_distance(&left, &right);
+        let damerau = similarity::damerau_levenshtein(&left, &right);
+        assert!(damerau <= osa && osa <= levenshtein);
+        assert_eq!(similarity::levenshtein_bounded(&left, &right, max), Some(levenshtein).filter(|d| *d <= max));
+        assert_eq!(similarity::osa_distance_bounded(&left, &right, max), Some(osa).filter(|d| *d <= max));
+        assert_eq!(similarity::damerau_levenshtein_bounded(&left, &right, max), Some(damerau).filter(|d| *d <= max));
+        let lcs_len = similarity::longest_common_subsequence_len(&left, &right);
+        assert_eq!(similarity::longest_common_subsequence(&left, &right).len(), lcs_len);
+        assert!(levenshtein >= left.len().max(right.len()) - lcs_len);
+        let (left_range, right_range) = similarity::longest_common_substring(&left, &right);
+        assert_eq!(left[left_range], right[right_range]);
+        let jaro_winkler = similarity::jaro_winkler(&left, &right);
+        assert!((0.0..=1.0).contains(&jaro_winkler) && jaro_winkler >= similarity::jaro(&left, &right));
+    }
+
+} // End of function test_similarity()
@@ -0,0 +1,226 @@
+//! The `MatchOptions` of the `_with` search methods, to find, replace and
+//! split without regard to the case, to the normalization or to the accents
+//! of the chars.
+//!
+//! The text and the pattern are both folded, the case with the simple case
+//! folding of Unicode, the normalization with the canonical decomposition
+//! and the canonical order of the marks, and the accents by the removal of
+//! the combining marks of the decomposition. The search runs on
+//! the folded chars, that can be more than the original ones, and each
+//! match is mapped back to the range of the original chars. A match has to
+//! start and to end at the boundary of an original char, so "e" doesn't
+//! match the 'e' inside of a precomposed 'é'. When normalized, it can't end
+//! before a combining mark either, so "e" doesn't match the 'e' of an 'e'
+//! followed by the combining acute accent.
+
+use std::ops::Range;
+
+use unic_ucd_normal::{decompose_canonical, CanonicalCombiningClass};
+
+use crate::case_mapping;
+use crate::general_category::GeneralCategory;
+use crate::finder::CharFinder;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MatchOptions {
+    /// Compares the simple case folding of the chars, so 'A' matches 'a'
+    /// and 'ſ' matches 's'.
+    pub case_insensitive: bool,
+    /// Compares the canonical decomposition of the chars, so 'é' matches
+    /// an 'e' followed by the combining acute accent.
+    pub normalize: bool,
+    /// Ignores the combining marks, so 'ç' matches 'c' and 'ã' matches 'a'.
+    /// The letters without a decomposition, like 'ø' and 'ł', are kept.
+    pub ignore_accents: bool,
+}
+
+impl MatchOptions {
+    /// The exact match, like the methods without options.
+    pub fn new() -> MatchOptions {
+        MatchOptions::default()
+    }
+
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> MatchOptions {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    pub fn normalize(mut self, normalize: bool) -> MatchOptions {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn ignore_accents(mut self, ignore_accents: bool) -> MatchOptions {
+        self.ignore_accents = ignore_accents;
+        self
+    }
+}
+
+// The folded chars of a text, with the position of each boundary between
+// the folded chars in the original chars.
+pub(crate) struct FoldedChars {
+    chars: Vec<char>,
+    // For each position in chars, and for the end, the index of the
+    // original char that starts there, or a None if the position is inside
+    // of the folding of an original char.
+    origins: Vec<Option<usize>>,
+    normalize: bool,
+}
+
+impl FoldedChars {
+    pub(crate) fn new(chars: &[char], options: MatchOptions) -> FoldedChars {
+        let mut folded_chars = Vec::with_capacity(chars.len());
+        let mut sources = Vec::with_capacity(chars.len());
+        for (index, &c) in chars.iter().enumerate() {
+            let c = if options.case_insensitive { case_mapping::simple_casefold(c) } else { c };
+            if options.normalize {
+                decompose_canonical(c, |decomposed_c| {
+                    if !(options.ignore_accents && is_mark(decomposed_c)) {
+                        folded_chars.push(decomposed_c);
+                        sources.push(index);
+                    }
+                });
+            } else if options.ignore_accents {
+                if let Some(stripped_c) = strip_diacritics_of(c) {
+                    folded_chars.push(stripped_c);
+                    sources.push(index);
+                }
+            } else {
+                folded_chars.push(c);
+                sources.push(index);
+            }
+        }
+        if options.normalize {
+            canonical_order(&mut folded_chars, &mut sources);
+        }
+
+        // A position is a boundary when all the chars before it come from
+        // original chars before all the chars after it, the marks of many
+        // original chars can be mixed by the canonical order.
+        let mut min_after = vec![chars.len(); sources.len() + 1];
+        for pos in (0..sources.len()).rev() {
+            min_after[pos] = min_after[pos + 1].min(sources[pos]);
+        }
+        let mut origins = Vec::with_capacity(sources.len() + 1);
+        let mut max_before: Option<usize> = None;
+        for (pos, min_after_val) in min_after.iter().enumerate() {
+            let is_boundary = match max_before {
+                None => true,
+                Some(max_before_val) => max_before_val < *min_after_val,
+            };
+            origins.push(if is_boundary { Some(*min_after_val) } else { None });
+            if pos < sources.len() {
+                max_before = max_before.max(Some(sources[pos]));
+            }
+        }
+        FoldedChars { chars: folded_chars, origins, normalize: options.normalize }
+    }
+
+    /// Returns a None or the range in the original chars of the first match
+    /// that starts between start_pos and end_pos inclusive, positions in
+    /// the original chars.
+    pub(crate) fn find_at(&self, finder: &CharFinder, start_pos: usize, end_pos: usize) -> Option<Range<usize>> {
+        let mut pos = self.folded_pos(start_pos);
+        while let Some(index) = finder.find_at(&self.chars, pos, None) {
+            if let Some(range) = self.original_range(index, index + finder.needle().len()) {
+                return if range.start <= end_pos { Some(range) } else { None };
+            }
+            pos = index + 1;
+        }
+        None
+    }
+
+    /// Returns the ranges in the original chars of the non overlapping
+    /// matches, from the start to the end.
+    pub(crate) fn find_all(&self, finder: &CharFinder) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut pos = 0;
+        while let Some(index) = finder.find_at(&self.chars, pos, None) {
+            let end = index + finder.needle().len();
+            match self.original_range(index, end) {
+                Some(range) => {
+                    ranges.push(range);
+                    pos = end;
+                }
+                None => pos = index + 1,
+            }
+        }
+        ranges
+    }
+
+    // The first boundary at or after the original position.
+    fn folded_pos(&self, original_pos: usize) -> usize {
+        self.origins.iter()
+            .position(|origin| matches!(origin, Some(index) if *index >= original_pos))
+            .unwrap_or(self.chars.len())
+    }
+
+    fn original_range(&self, folded_start: usize, folded_end: usize) -> Option<Range<usize>> {
+        if self.normalize && folded_end < self.chars.len() && combining_class(self.chars[folded_end]) != 0 {
+            return None;
+        }
+        Some(self.origins[folded_start]?..self.origins[folded_end]?)
+    }
+}
+
+/// Returns the char without its combining marks, the base char of the
+/// canonical decomposition, or a None if the char is a combining mark.
+/// The chars whose decomposition has no marks, like the Hangul syllables,
+/// are kept.
+pub(crate) fn strip_diacritics_of(c: char) -> Option<char> {
+    if is_mark(c) {
+        return None;
+    }
+    let mut base_chars = 0;
+    let mut base_c = c;
+    let mut has_marks = false;
+    decompose_canonical(c, |decomposed_c| {
+        if is_mark(decomposed_c) {
+            has_marks = true;
+        } else {
+            base_chars += 1;
+            base_c = decomposed_c;
+        }
+    });
+    if has_marks && base_chars == 1 { Some(base_c) } else { Some(c) }
+}
+
+/// Returns the folded chars of a pattern.
+pub(crate) fn fold_chars(chars: &[char], options: MatchOptions) -> Vec<char> {
+    FoldedChars::new(chars, options).chars
+}
+
+// Sorts each run of marks by the combining class, keeping the order of the
+// marks with the same class, and moves the sources with the chars.
+fn canonical_order(chars: &mut [char], sources: &mut [usize]) {
+    let mut start = 0;
+    while start < chars.len() {
+        if combining_class(chars[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let mut end = start + 1;
+        while end < chars.len() && combining_class(chars[end]) != 0 {
+            end += 1;
+        }
+        for i in (start + 1)..end {
+            let mut j = i;
+            while j > start && combining_class(chars[j - 1]) > combining_class(chars[j]) {
+                chars.swap(j - 1, j);
+                sources.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+        start = end;
+    }
+}
+
+#[inline]
+fn combining_class(c: char) -> u8 {
+    CanonicalCombiningClass::of(c).number()
+}
+
+#[inline]
+fn is_mark(c: char) -> bool {
+    GeneralCategory::of(c).is_mark()
+}
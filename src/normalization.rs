@@ -0,0 +1,43 @@
+//! Unicode normalization forms.
+//!
+//! `StringUtils::get_vec_chars()`, and with it all the `*_str` methods of
+//! `StringUtilsVecCharsV2` and `CharStr`, normalize the `&str` with NFC
+//! before converting it into chars. Another form is always explicit:
+//! `get_vec_chars_with()` converts a `&str` with the form, for the `*_vec`
+//! methods, and a `CharString`, a `GraphemeString` or an `IndexedStr` made
+//! with `from_str_with()` keeps its form for the patterns of its `*_str`
+//! methods.
+
+use unic_normal::StrNormalForm;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// No normalization, the chars are kept byte exact.
+    None,
+    /// Canonical Decomposition followed by Canonical Composition.
+    #[default]
+    NFC,
+    /// Canonical Decomposition.
+    NFD,
+    /// Compatibility Decomposition followed by Canonical Composition.
+    NFKC,
+    /// Compatibility Decomposition.
+    NFKD,
+}
+
+impl Normalization {
+    /// Returns the normalized chars of p_str.
+    pub fn chars(self, p_str: &str) -> Box<dyn Iterator<Item = char> + '_> {
+        match self {
+            Normalization::None => Box::new(p_str.chars()),
+            Normalization::NFC  => Box::new(p_str.nfc()),
+            Normalization::NFD  => Box::new(p_str.nfd()),
+            Normalization::NFKC => Box::new(p_str.nfkc()),
+            Normalization::NFKD => Box::new(p_str.nfkd()),
+        }
+    }
+
+    pub fn normalize(self, p_str: &str) -> String {
+        self.chars(p_str).collect()
+    }
+}
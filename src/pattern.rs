@@ -0,0 +1,250 @@
+//! A generic pattern for the search methods, like `std::str::pattern::Pattern`.
+//!
+//! With `CharPattern` one method, like `CharStr::find()`, covers all the
+//! cases that before needed a `_vec` and a `_str` method, and some more:
+//!
+//! * `char` - A single char.
+//! * `&str` and `&String` - A sequence of chars, normalized like in
+//!   `StringUtils::get_vec_chars()`.
+//! * `&[char]`, `&Vec<char>`, `&CharStr` and `&CharString` - A sequence of
+//!   chars.
+//! * `[char; N]` and `&[char; N]` - Any of the chars, like in the Std.
+//! * `AnyOf(&[char])` - Any of the chars of a slice, because a plain `&[char]`
+//!   is a sequence in this crate.
+//! * `FnMut(char) -> bool` - Any char for which the predicate is true.
+//!
+//! Like in all the other find methods of the crate, an empty sequence never
+//! matches.
+
+use std::ops::Range;
+
+use crate::char_string::{CharStr, CharString};
+use crate::finder::CharFinder;
+use crate::str_utils::StringUtils;
+
+pub trait CharPattern: Sized {
+    type Searcher: CharSearcher;
+
+    fn into_searcher(self) -> Self::Searcher;
+}
+
+/// The searcher of a pattern, the matches are returned as (start, end) with
+/// an exclusive end.
+pub trait CharSearcher {
+    /// Returns the first match that starts at or after start_pos.
+    fn find_at(&mut self, haystack: &[char], start_pos: usize) -> Option<(usize, usize)>;
+    /// Returns the last match that ends at or before end_pos.
+    fn rfind_at(&mut self, haystack: &[char], end_pos: usize) -> Option<(usize, usize)>;
+    /// Returns a None or the len of the match at the start of haystack.
+    fn prefix_len(&mut self, haystack: &[char]) -> Option<usize>;
+    /// Returns a None or the len of the match at the end of haystack.
+    fn suffix_len(&mut self, haystack: &[char]) -> Option<usize>;
+
+    fn is_prefix_of(&mut self, haystack: &[char]) -> bool {
+        self.prefix_len(haystack).is_some()
+    }
+
+    fn is_suffix_of(&mut self, haystack: &[char]) -> bool {
+        self.suffix_len(haystack).is_some()
+    }
+}
+
+/// Any of the chars of the slice.
+#[derive(Clone, Copy, Debug)]
+pub struct AnyOf<'a>(pub &'a [char]);
+
+
+// Searcher of a sequence of chars.
+
+#[derive(Clone, Debug)]
+pub struct SeqSearcher {
+    finder: CharFinder,
+}
+
+impl SeqSearcher {
+    pub fn new(needle: &[char]) -> SeqSearcher {
+        SeqSearcher { finder: CharFinder::new(needle) }
+    }
+}
+
+impl CharSearcher for SeqSearcher {
+    fn find_at(&mut self, haystack: &[char], start_pos: usize) -> Option<(usize, usize)> {
+        let index = self.finder.find_at(haystack, start_pos, None)?;
+        Some((index, index + self.finder.needle().len()))
+    }
+
+    fn rfind_at(&mut self, haystack: &[char], end_pos: usize) -> Option<(usize, usize)> {
+        let needle = self.finder.needle();
+        let end_pos = end_pos.min(haystack.len());
+        if needle.is_empty() || needle.len() > end_pos {
+            return None;
+        }
+        (0..=(end_pos - needle.len()))
+            .rev()
+            .find(|&i| haystack[i..i + needle.len()] == *needle)
+            .map(|i| (i, i + needle.len()))
+    }
+
+    fn prefix_len(&mut self, haystack: &[char]) -> Option<usize> {
+        let needle = self.finder.needle();
+        (!needle.is_empty() && haystack.starts_with(needle)).then_some(needle.len())
+    }
+
+    fn suffix_len(&mut self, haystack: &[char]) -> Option<usize> {
+        let needle = self.finder.needle();
+        (!needle.is_empty() && haystack.ends_with(needle)).then_some(needle.len())
+    }
+}
+
+
+// Searcher of a single char that matches a char, a set or a predicate.
+
+pub trait CharMatcher {
+    fn matches_char(&mut self, c: char) -> bool;
+}
+
+impl CharMatcher for char {
+    #[inline]
+    fn matches_char(&mut self, c: char) -> bool {
+        *self == c
+    }
+}
+
+impl<const N: usize> CharMatcher for [char; N] {
+    #[inline]
+    fn matches_char(&mut self, c: char) -> bool {
+        self.contains(&c)
+    }
+}
+
+impl CharMatcher for AnyOf<'_> {
+    #[inline]
+    fn matches_char(&mut self, c: char) -> bool {
+        self.0.contains(&c)
+    }
+}
+
+impl<F: FnMut(char) -> bool> CharMatcher for F {
+    #[inline]
+    fn matches_char(&mut self, c: char) -> bool {
+        self(c)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CharMatcherSearcher<M: CharMatcher> {
+    matcher: M,
+}
+
+impl<M: CharMatcher> CharSearcher for CharMatcherSearcher<M> {
+    fn find_at(&mut self, haystack: &[char], start_pos: usize) -> Option<(usize, usize)> {
+        let tail = haystack.get(start_pos..)?;
+        let index = start_pos + tail.iter().position(|c| self.matcher.matches_char(*c))?;
+        Some((index, index + 1))
+    }
+
+    fn rfind_at(&mut self, haystack: &[char], end_pos: usize) -> Option<(usize, usize)> {
+        let head = &haystack[..end_pos.min(haystack.len())];
+        let index = head.iter().rposition(|c| self.matcher.matches_char(*c))?;
+        Some((index, index + 1))
+    }
+
+    fn prefix_len(&mut self, haystack: &[char]) -> Option<usize> {
+        haystack.first().is_some_and(|c| self.matcher.matches_char(*c)).then_some(1)
+    }
+
+    fn suffix_len(&mut self, haystack: &[char]) -> Option<usize> {
+        haystack.last().is_some_and(|c| self.matcher.matches_char(*c)).then_some(1)
+    }
+}
+
+
+/// The range of haystack without the repeated matches at the start, if
+/// trim_start, and at the end, if trim_end, like `str::trim_matches()`.
+pub(crate) fn trim_range<S: CharSearcher>(searcher: &mut S, haystack: &[char], trim_start: bool, trim_end: bool) -> Range<usize> {
+    // Only the chars at the edge are tested, so the cost is the one of the
+    // trimmed chars.
+    let mut start = 0;
+    if trim_start {
+        while let Some(len) = searcher.prefix_len(&haystack[start..]) {
+            if len == 0 {
+                break;
+            }
+            start += len;
+        }
+    }
+    let mut end = haystack.len();
+    if trim_end {
+        while let Some(len) = searcher.suffix_len(&haystack[start..end]) {
+            if len == 0 {
+                break;
+            }
+            end -= len;
+        }
+    }
+    start..end
+}
+
+
+// The patterns.
+
+macro_rules! impl_char_pattern_matcher {
+    ($(impl$([$($gen:tt)*])? for $ty:ty => |$self_:ident| $matcher:expr;)*) => {
+        $(
+            impl$(<$($gen)*>)? CharPattern for $ty {
+                type Searcher = CharMatcherSearcher<$ty>;
+
+                fn into_searcher(self) -> Self::Searcher {
+                    let $self_ = self;
+                    CharMatcherSearcher { matcher: $matcher }
+                }
+            }
+        )*
+    };
+}
+
+impl_char_pattern_matcher! {
+    impl for char => |c| c;
+    impl[const N: usize] for [char; N] => |set| set;
+    impl['a] for AnyOf<'a> => |set| set;
+}
+
+impl<const N: usize> CharPattern for &[char; N] {
+    type Searcher = CharMatcherSearcher<[char; N]>;
+
+    fn into_searcher(self) -> Self::Searcher {
+        CharMatcherSearcher { matcher: *self }
+    }
+}
+
+impl<F: FnMut(char) -> bool> CharPattern for F {
+    type Searcher = CharMatcherSearcher<F>;
+
+    fn into_searcher(self) -> Self::Searcher {
+        CharMatcherSearcher { matcher: self }
+    }
+}
+
+macro_rules! impl_char_pattern_seq {
+    ($(for $ty:ty => |$self_:ident| $needle:expr;)*) => {
+        $(
+            impl CharPattern for $ty {
+                type Searcher = SeqSearcher;
+
+                fn into_searcher(self) -> SeqSearcher {
+                    let $self_ = self;
+                    SeqSearcher::new($needle)
+                }
+            }
+        )*
+    };
+}
+
+impl_char_pattern_seq! {
+    for &str => |p_str| &p_str.get_vec_chars();
+    for &String => |string| &string.get_vec_chars();
+    for &[char] => |chars| chars;
+    for &Vec<char> => |vec_chars| vec_chars;
+    for &CharStr => |char_str| char_str.as_chars();
+    for &CharString => |char_string| char_string.as_chars();
+}
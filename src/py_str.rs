@@ -0,0 +1,625 @@
+//! The methods of the `str` of Python.
+//!
+//! The `PyStrMethods` trait has the methods of the Python `str` that have
+//! no direct equivalent in the Std, with the names and the results of
+//! CPython 3.11, on char positions. It's implemented for `[char]`, and so
+//! for `Vec<char>` by deref, and for `str`. The `&str` arguments are used
+//! as they are, not normalized, like in Python.
+//!
+//! The optional start and end of `count()`, `index()` and `rindex()` are
+//! like in `self[start:end]` of Python, negative positions are from the
+//! end. Where Python raises a ValueError the methods return None, except
+//! for an empty separator in `partition()` and `rpartition()`, that
+//! panics.
+
+use std::ops::Range;
+
+use crate::case_mapping::{self, in_ranges, CaseLocale, CaseMapper};
+use crate::error::StringUtilsError;
+use crate::finder::CharFinder;
+use crate::general_category::GeneralCategory;
+
+pub trait PyStrMethods {
+    /// `[char]` for the chars, `str` for a `str`.
+    type Slice: ?Sized;
+    /// `Vec<char>` for the chars, `String` for a `str`.
+    type Owned;
+
+    /// Returns the number of non overlapping occurrences of sub, an empty
+    /// sub is counted before every char and at the end.
+    fn count(&self, sub: &Self::Slice, start: Option<isize>, end: Option<isize>) -> usize;
+    /// Returns a None or the index of the first occurrence of sub.
+    fn index(&self, sub: &Self::Slice, start: Option<isize>, end: Option<isize>) -> Option<usize>;
+    /// Returns a None or the index of the last occurrence of sub.
+    fn rindex(&self, sub: &Self::Slice, start: Option<isize>, end: Option<isize>) -> Option<usize>;
+
+    /// Centered in width chars, with the extra fillchar on the right for an
+    /// odd padding, except when width is odd.
+    fn center(&self, width: usize, fillchar: char) -> Self::Owned;
+    fn ljust(&self, width: usize, fillchar: char) -> Self::Owned;
+    fn rjust(&self, width: usize, fillchar: char) -> Self::Owned;
+    /// Padded on the left with zeros to width chars, after a sign + or - .
+    fn zfill(&self, width: usize) -> Self::Owned;
+    /// The tabs replaced by spaces up to the next column multiple of
+    /// tabsize, the column restarts after a '\n' or a '\r'. A tabsize of 0
+    /// removes the tabs.
+    fn expandtabs(&self, tabsize: usize) -> Self::Owned;
+
+    /// Returns the part before the first sep, the sep and the part after,
+    /// or self and two empty parts if sep is not found.
+    /// Panics if sep is empty.
+    fn partition<'a>(&'a self, sep: &Self::Slice) -> (&'a Self::Slice, &'a Self::Slice, &'a Self::Slice);
+    /// Like `partition()` for the last sep, or two empty parts and self if
+    /// sep is not found.
+    /// Panics if sep is empty.
+    fn rpartition<'a>(&'a self, sep: &Self::Slice) -> (&'a Self::Slice, &'a Self::Slice, &'a Self::Slice);
+    fn removeprefix<'a>(&'a self, prefix: &Self::Slice) -> &'a Self::Slice;
+    fn removesuffix<'a>(&'a self, suffix: &Self::Slice) -> &'a Self::Slice;
+    /// Splits at the line boundaries of Python, '\n', '\r', "\r\n", '\x0b',
+    /// '\x0c', '\x1c', '\x1d', '\x1e', '\u{85}', '\u{2028}' and '\u{2029}'.
+    /// A line boundary at the end doesn't add an empty line.
+    fn splitlines(&self, keepends: bool) -> Vec<&Self::Slice>;
+
+    /// The first cased char of each word in titlecase and the others in
+    /// lowercase, a word is a run of cased chars.
+    fn title(&self) -> Self::Owned;
+    /// The first char in titlecase and the others in lowercase.
+    fn capitalize(&self) -> Self::Owned;
+    fn swapcase(&self) -> Self::Owned;
+    /// The full case folding, for caseless comparisons, "ß" is "ss".
+    fn casefold(&self) -> Self::Owned;
+
+    // The predicates are false for an empty string, except `isprintable()`.
+
+    /// All the chars are letters.
+    fn isalpha(&self) -> bool;
+    /// All the chars are digits, the decimals and the digits like '²'.
+    fn isdigit(&self) -> bool;
+    /// All the chars are decimal digits of any script.
+    fn isdecimal(&self) -> bool;
+    /// All the chars have a numeric value, the digits, '½', 'Ⅻ' or '三'.
+    fn isnumeric(&self) -> bool;
+    fn isspace(&self) -> bool;
+    /// The uppercase chars only start the words and the lowercase chars
+    /// only follow a cased char, with at least one cased char.
+    fn istitle(&self) -> bool;
+    /// A valid identifier of Python, not checking the keywords.
+    fn isidentifier(&self) -> bool;
+    /// No char is a control, a format, a separator other than the space,
+    /// a private use or an unassigned char.
+    fn isprintable(&self) -> bool;
+}
+
+impl PyStrMethods for [char] {
+    type Slice = [char];
+    type Owned = Vec<char>;
+
+    fn count(&self, sub: &[char], start: Option<isize>, end: Option<isize>) -> usize {
+        let range = match find_range(self.len(), start, end) {
+            Some(range) => range,
+            None => return 0,
+        };
+        if sub.is_empty() {
+            return range.len() + 1;
+        }
+        CharFinder::new(sub).find_iter(&self[range]).count()
+    }
+
+    fn index(&self, sub: &[char], start: Option<isize>, end: Option<isize>) -> Option<usize> {
+        let range = find_range(self.len(), start, end)?;
+        if sub.is_empty() {
+            return Some(range.start);
+        }
+        CharFinder::new(sub).find(&self[range.clone()]).map(|index| range.start + index)
+    }
+
+    fn rindex(&self, sub: &[char], start: Option<isize>, end: Option<isize>) -> Option<usize> {
+        let range = find_range(self.len(), start, end)?;
+        if sub.is_empty() {
+            return Some(range.end);
+        }
+        self[range.clone()].windows(sub.len()).rposition(|window| window == sub).map(|index| range.start + index)
+    }
+
+    fn center(&self, width: usize, fillchar: char) -> Vec<char> {
+        center_into(self, width, fillchar)
+    }
+
+    fn ljust(&self, width: usize, fillchar: char) -> Vec<char> {
+        pad_into(self, 0, width.saturating_sub(self.len()), fillchar)
+    }
+
+    fn rjust(&self, width: usize, fillchar: char) -> Vec<char> {
+        pad_into(self, width.saturating_sub(self.len()), 0, fillchar)
+    }
+
+    fn zfill(&self, width: usize) -> Vec<char> {
+        zfill_into(self, width)
+    }
+
+    fn expandtabs(&self, tabsize: usize) -> Vec<char> {
+        expandtabs_into(self, tabsize)
+    }
+
+    fn partition<'a>(&'a self, sep: &[char]) -> (&'a [char], &'a [char], &'a [char]) {
+        if sep.is_empty() {
+            panic!("Error: In partition() {}.", StringUtilsError::EmptyPattern);
+        }
+        match CharFinder::new(sep).find(self) {
+            Some(index) => (&self[..index], &self[index..index + sep.len()], &self[index + sep.len()..]),
+            None => (self, &self[self.len()..], &self[self.len()..]),
+        }
+    }
+
+    fn rpartition<'a>(&'a self, sep: &[char]) -> (&'a [char], &'a [char], &'a [char]) {
+        if sep.is_empty() {
+            panic!("Error: In rpartition() {}.", StringUtilsError::EmptyPattern);
+        }
+        match self.windows(sep.len()).rposition(|window| window == sep) {
+            Some(index) => (&self[..index], &self[index..index + sep.len()], &self[index + sep.len()..]),
+            None => (&self[..0], &self[..0], self),
+        }
+    }
+
+    fn removeprefix<'a>(&'a self, prefix: &[char]) -> &'a [char] {
+        self.strip_prefix(prefix).unwrap_or(self)
+    }
+
+    fn removesuffix<'a>(&'a self, suffix: &[char]) -> &'a [char] {
+        self.strip_suffix(suffix).unwrap_or(self)
+    }
+
+    fn splitlines(&self, keepends: bool) -> Vec<&[char]> {
+        line_ranges(self.iter().copied().enumerate(), self.len(), keepends)
+            .into_iter()
+            .map(|range| &self[range])
+            .collect()
+    }
+
+    fn title(&self) -> Vec<char> {
+        title_into(self)
+    }
+
+    fn capitalize(&self) -> Vec<char> {
+        capitalize_into(self)
+    }
+
+    fn swapcase(&self) -> Vec<char> {
+        swapcase_into(self)
+    }
+
+    fn casefold(&self) -> Vec<char> {
+        casefold_into(self)
+    }
+
+    fn isalpha(&self) -> bool {
+        all_non_empty(self.iter().copied(), is_alpha)
+    }
+
+    fn isdigit(&self) -> bool {
+        all_non_empty(self.iter().copied(), is_digit)
+    }
+
+    fn isdecimal(&self) -> bool {
+        all_non_empty(self.iter().copied(), is_decimal)
+    }
+
+    fn isnumeric(&self) -> bool {
+        all_non_empty(self.iter().copied(), is_numeric)
+    }
+
+    fn isspace(&self) -> bool {
+        all_non_empty(self.iter().copied(), is_space)
+    }
+
+    fn istitle(&self) -> bool {
+        is_title(self.iter().copied())
+    }
+
+    fn isidentifier(&self) -> bool {
+        is_identifier(self.iter().copied())
+    }
+
+    fn isprintable(&self) -> bool {
+        self.iter().all(|&c| is_printable(c))
+    }
+}
+
+impl PyStrMethods for str {
+    type Slice = str;
+    type Owned = String;
+
+    fn count(&self, sub: &str, start: Option<isize>, end: Option<isize>) -> usize {
+        let sub_chars: Vec<char> = sub.chars().collect();
+        self.chars().collect::<Vec<char>>().count(&sub_chars, start, end)
+    }
+
+    fn index(&self, sub: &str, start: Option<isize>, end: Option<isize>) -> Option<usize> {
+        let sub_chars: Vec<char> = sub.chars().collect();
+        self.chars().collect::<Vec<char>>().index(&sub_chars, start, end)
+    }
+
+    fn rindex(&self, sub: &str, start: Option<isize>, end: Option<isize>) -> Option<usize> {
+        let sub_chars: Vec<char> = sub.chars().collect();
+        self.chars().collect::<Vec<char>>().rindex(&sub_chars, start, end)
+    }
+
+    fn center(&self, width: usize, fillchar: char) -> String {
+        center_into(&self.chars().collect::<Vec<char>>(), width, fillchar)
+    }
+
+    fn ljust(&self, width: usize, fillchar: char) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        pad_into(&chars, 0, width.saturating_sub(chars.len()), fillchar)
+    }
+
+    fn rjust(&self, width: usize, fillchar: char) -> String {
+        let chars: Vec<char> = self.chars().collect();
+        pad_into(&chars, width.saturating_sub(chars.len()), 0, fillchar)
+    }
+
+    fn zfill(&self, width: usize) -> String {
+        zfill_into(&self.chars().collect::<Vec<char>>(), width)
+    }
+
+    fn expandtabs(&self, tabsize: usize) -> String {
+        expandtabs_into(&self.chars().collect::<Vec<char>>(), tabsize)
+    }
+
+    fn partition<'a>(&'a self, sep: &str) -> (&'a str, &'a str, &'a str) {
+        if sep.is_empty() {
+            panic!("Error: In partition() {}.", StringUtilsError::EmptyPattern);
+        }
+        match self.find(sep) {
+            Some(index) => (&self[..index], &self[index..index + sep.len()], &self[index + sep.len()..]),
+            None => (self, &self[self.len()..], &self[self.len()..]),
+        }
+    }
+
+    fn rpartition<'a>(&'a self, sep: &str) -> (&'a str, &'a str, &'a str) {
+        if sep.is_empty() {
+            panic!("Error: In rpartition() {}.", StringUtilsError::EmptyPattern);
+        }
+        match self.rfind(sep) {
+            Some(index) => (&self[..index], &self[index..index + sep.len()], &self[index + sep.len()..]),
+            None => (&self[..0], &self[..0], self),
+        }
+    }
+
+    fn removeprefix<'a>(&'a self, prefix: &str) -> &'a str {
+        self.strip_prefix(prefix).unwrap_or(self)
+    }
+
+    fn removesuffix<'a>(&'a self, suffix: &str) -> &'a str {
+        self.strip_suffix(suffix).unwrap_or(self)
+    }
+
+    fn splitlines(&self, keepends: bool) -> Vec<&str> {
+        line_ranges(self.char_indices(), self.len(), keepends)
+            .into_iter()
+            .map(|range| &self[range])
+            .collect()
+    }
+
+    fn title(&self) -> String {
+        title_into(&self.chars().collect::<Vec<char>>())
+    }
+
+    fn capitalize(&self) -> String {
+        capitalize_into(&self.chars().collect::<Vec<char>>())
+    }
+
+    fn swapcase(&self) -> String {
+        swapcase_into(&self.chars().collect::<Vec<char>>())
+    }
+
+    fn casefold(&self) -> String {
+        casefold_into(&self.chars().collect::<Vec<char>>())
+    }
+
+    fn isalpha(&self) -> bool {
+        all_non_empty(self.chars(), is_alpha)
+    }
+
+    fn isdigit(&self) -> bool {
+        all_non_empty(self.chars(), is_digit)
+    }
+
+    fn isdecimal(&self) -> bool {
+        all_non_empty(self.chars(), is_decimal)
+    }
+
+    fn isnumeric(&self) -> bool {
+        all_non_empty(self.chars(), is_numeric)
+    }
+
+    fn isspace(&self) -> bool {
+        all_non_empty(self.chars(), is_space)
+    }
+
+    fn istitle(&self) -> bool {
+        is_title(self.chars())
+    }
+
+    fn isidentifier(&self) -> bool {
+        is_identifier(self.chars())
+    }
+
+    fn isprintable(&self) -> bool {
+        self.chars().all(is_printable)
+    }
+}
+
+// The range of `self[start:end]` for a search, like the ADJUST_INDICES of
+// CPython, the end is clamped to len but not the start, so a start after
+// the end has no range, and with it no match, not even of an empty sub.
+fn find_range(len: usize, start: Option<isize>, end: Option<isize>) -> Option<Range<usize>> {
+    let adjust = |position: isize| if position < 0 { (position + len as isize).max(0) as usize } else { position as usize };
+    let start = start.map_or(0, adjust);
+    let end = end.map_or(len, adjust).min(len);
+    if start <= end { Some(start..end) } else { None }
+}
+
+fn pad_into<O: Default + Extend<char>>(chars: &[char], left: usize, right: usize, fillchar: char) -> O {
+    let mut out = O::default();
+    out.extend(std::iter::repeat_n(fillchar, left));
+    out.extend(chars.iter().copied());
+    out.extend(std::iter::repeat_n(fillchar, right));
+    out
+}
+
+fn center_into<O: Default + Extend<char>>(chars: &[char], width: usize, fillchar: char) -> O {
+    let margin = width.saturating_sub(chars.len());
+    // Like CPython, the extra fillchar of an odd margin goes to the left
+    // only when the width is odd.
+    let left = margin / 2 + (margin & width & 1);
+    pad_into(chars, left, margin - left, fillchar)
+}
+
+fn zfill_into<O: Default + Extend<char>>(chars: &[char], width: usize) -> O {
+    let fill = width.saturating_sub(chars.len());
+    let mut out = O::default();
+    match chars.first() {
+        Some(&sign) if sign == '+' || sign == '-' => {
+            out.extend([sign]);
+            out.extend(std::iter::repeat_n('0', fill));
+            out.extend(chars[1..].iter().copied());
+        }
+        _ => {
+            out.extend(std::iter::repeat_n('0', fill));
+            out.extend(chars.iter().copied());
+        }
+    }
+    out
+}
+
+fn expandtabs_into<O: Default + Extend<char>>(chars: &[char], tabsize: usize) -> O {
+    let mut out = O::default();
+    let mut column = 0;
+    for &c in chars {
+        if c == '\t' {
+            if tabsize > 0 {
+                let spaces = tabsize - column % tabsize;
+                out.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+        } else {
+            out.extend([c]);
+            column = if c == '\n' || c == '\r' { 0 } else { column + 1 };
+        }
+    }
+    out
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\x0b' | '\x0c' | '\x1c' | '\x1d' | '\x1e' | '\u{85}' | '\u{2028}' | '\u{2029}')
+}
+
+// The ranges of the lines, from the positions of the chars, in chars or
+// in bytes, and the len in the same unit.
+fn line_ranges(chars: impl Iterator<Item = (usize, char)>, len: usize, keepends: bool) -> Vec<Range<usize>> {
+    let mut chars = chars.peekable();
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    while let Some((pos, c)) = chars.next() {
+        if !is_line_break(c) {
+            continue;
+        }
+        if c == '\r' && chars.peek().map(|&(_, next_c)| next_c) == Some('\n') {
+            chars.next();
+        }
+        let break_end = chars.peek().map_or(len, |&(next_pos, _)| next_pos);
+        lines.push(line_start..if keepends { break_end } else { pos });
+        line_start = break_end;
+    }
+    if line_start < len {
+        lines.push(line_start..len);
+    }
+    lines
+}
+
+fn title_into<O: Default + Extend<char>>(chars: &[char]) -> O {
+    let mut out = O::default();
+    let mut mapper = CaseMapper::new(CaseLocale::Root);
+    // Unlike `StringUtilsCase::to_titlecase()`, a word of Python ends at
+    // any char that isn't cased, so "they're" is "They'Re".
+    let mut previous_is_cased = false;
+    for (index, &c) in chars.iter().enumerate() {
+        if previous_is_cased {
+            mapper.push_lowercase(chars, index, &mut out);
+        } else {
+            mapper.push_titlecase(chars, index, &mut out);
+        }
+        previous_is_cased = case_mapping::is_cased(c);
+    }
+    out
+}
+
+fn capitalize_into<O: Default + Extend<char>>(chars: &[char]) -> O {
+    let mut out = O::default();
+    let mut mapper = CaseMapper::new(CaseLocale::Root);
+    for index in 0..chars.len() {
+        if index == 0 {
+            mapper.push_titlecase(chars, index, &mut out);
+        } else {
+            mapper.push_lowercase(chars, index, &mut out);
+        }
+    }
+    out
+}
+
+fn swapcase_into<O: Default + Extend<char>>(chars: &[char]) -> O {
+    let mut out = O::default();
+    let mut mapper = CaseMapper::new(CaseLocale::Root);
+    for (index, &c) in chars.iter().enumerate() {
+        if case_mapping::is_uppercase(c) {
+            mapper.push_lowercase(chars, index, &mut out);
+        } else if case_mapping::is_lowercase(c) {
+            mapper.push_uppercase(chars, index, &mut out);
+        } else {
+            mapper.push_unchanged(chars, index, &mut out);
+        }
+    }
+    out
+}
+
+fn casefold_into<O: Default + Extend<char>>(chars: &[char]) -> O {
+    let mut out = O::default();
+    let mut mapper = CaseMapper::new(CaseLocale::Root);
+    for index in 0..chars.len() {
+        mapper.push_casefold(chars, index, &mut out);
+    }
+    out
+}
+
+fn all_non_empty(mut chars: impl Iterator<Item = char>, predicate: fn(char) -> bool) -> bool {
+    match chars.next() {
+        Some(c) => predicate(c) && chars.all(predicate),
+        None => false,
+    }
+}
+
+fn is_alpha(c: char) -> bool {
+    GeneralCategory::of(c).is_letter()
+}
+
+fn is_decimal(c: char) -> bool {
+    GeneralCategory::of(c) == GeneralCategory::Nd
+}
+
+fn is_digit(c: char) -> bool {
+    is_decimal(c) || in_ranges(c, DIGIT_RANGES)
+}
+
+fn is_numeric(c: char) -> bool {
+    GeneralCategory::of(c).is_number() || in_ranges(c, NUMERIC_RANGES)
+}
+
+fn is_space(c: char) -> bool {
+    // Python adds the separators of files, groups, records and units.
+    c.is_whitespace() || ('\x1c'..='\x1f').contains(&c)
+}
+
+fn is_printable(c: char) -> bool {
+    use GeneralCategory::*;
+    c == ' ' || !matches!(GeneralCategory::of(c), Cc | Cf | Cs | Co | Cn | Zs | Zl | Zp)
+}
+
+fn is_title(chars: impl Iterator<Item = char>) -> bool {
+    let mut cased = false;
+    let mut previous_is_cased = false;
+    for c in chars {
+        if case_mapping::is_uppercase(c) || GeneralCategory::of(c) == GeneralCategory::Lt {
+            if previous_is_cased {
+                return false;
+            }
+            previous_is_cased = true;
+            cased = true;
+        } else if case_mapping::is_lowercase(c) {
+            if !previous_is_cased {
+                return false;
+            }
+            previous_is_cased = true;
+            cased = true;
+        } else {
+            previous_is_cased = false;
+        }
+    }
+    cased
+}
+
+// XID_Start and XID_Continue, from the General Category with the
+// exceptions of Unicode, like the chars that change with NFKC.
+fn is_xid_continue(c: char) -> bool {
+    use GeneralCategory::*;
+    if in_ranges(c, NOT_XID_RANGES) {
+        return false;
+    }
+    matches!(GeneralCategory::of(c), Lu | Ll | Lt | Lm | Lo | Nl | Mn | Mc | Nd | Pc)
+        || in_ranges(c, XID_CONTINUE_EXTRA_RANGES)
+}
+
+fn is_xid_start(c: char) -> bool {
+    use GeneralCategory::*;
+    if in_ranges(c, NOT_XID_RANGES) || in_ranges(c, NOT_XID_START_RANGES) {
+        return false;
+    }
+    matches!(GeneralCategory::of(c), Lu | Ll | Lt | Lm | Lo | Nl) || in_ranges(c, XID_START_EXTRA_RANGES)
+}
+
+fn is_identifier(mut chars: impl Iterator<Item = char>) -> bool {
+    match chars.next() {
+        Some(c) => (c == '_' || is_xid_start(c)) && chars.all(is_xid_continue),
+        None => false,
+    }
+}
+
+// The digits that are not decimal, like the superscripts and the circled
+// digits.
+const DIGIT_RANGES: &[(u32, u32)] = &[
+    (0x00B2, 0x00B3), (0x00B9, 0x00B9), (0x1369, 0x1371), (0x19DA, 0x19DA), (0x2070, 0x2070),
+    (0x2074, 0x2079), (0x2080, 0x2089), (0x2460, 0x2468), (0x2474, 0x247C), (0x2488, 0x2490),
+    (0x24EA, 0x24EA), (0x24F5, 0x24FD), (0x24FF, 0x24FF), (0x2776, 0x277E), (0x2780, 0x2788),
+    (0x278A, 0x2792), (0x10A40, 0x10A43), (0x10E60, 0x10E68), (0x11052, 0x1105A),
+    (0x1F100, 0x1F10A),
+];
+
+// The chars with a numeric value that are not numbers, like the CJK
+// ideographs of numbers.
+const NUMERIC_RANGES: &[(u32, u32)] = &[
+    (0x3405, 0x3405), (0x3483, 0x3483), (0x382A, 0x382A), (0x3B4D, 0x3B4D), (0x4E00, 0x4E00),
+    (0x4E03, 0x4E03), (0x4E07, 0x4E07), (0x4E09, 0x4E09), (0x4E5D, 0x4E5D), (0x4E8C, 0x4E8C),
+    (0x4E94, 0x4E94), (0x4E96, 0x4E96), (0x4EBF, 0x4EC0), (0x4EDF, 0x4EDF), (0x4EE8, 0x4EE8),
+    (0x4F0D, 0x4F0D), (0x4F70, 0x4F70), (0x5104, 0x5104), (0x5146, 0x5146), (0x5169, 0x5169),
+    (0x516B, 0x516B), (0x516D, 0x516D), (0x5341, 0x5341), (0x5343, 0x5345), (0x534C, 0x534C),
+    (0x53C1, 0x53C4), (0x56DB, 0x56DB), (0x58F1, 0x58F1), (0x58F9, 0x58F9), (0x5E7A, 0x5E7A),
+    (0x5EFE, 0x5EFF), (0x5F0C, 0x5F0E), (0x5F10, 0x5F10), (0x62FE, 0x62FE), (0x634C, 0x634C),
+    (0x67D2, 0x67D2), (0x6F06, 0x6F06), (0x7396, 0x7396), (0x767E, 0x767E), (0x8086, 0x8086),
+    (0x842C, 0x842C), (0x8CAE, 0x8CAE), (0x8CB3, 0x8CB3), (0x8D30, 0x8D30), (0x9621, 0x9621),
+    (0x9646, 0x9646), (0x964C, 0x964C), (0x9678, 0x9678), (0x96F6, 0x96F6), (0xF96B, 0xF96B),
+    (0xF973, 0xF973), (0xF978, 0xF978), (0xF9B2, 0xF9B2), (0xF9D1, 0xF9D1), (0xF9D3, 0xF9D3),
+    (0xF9FD, 0xF9FD), (0x20001, 0x20001), (0x20064, 0x20064), (0x200E2, 0x200E2),
+    (0x20121, 0x20121), (0x2092A, 0x2092A), (0x20983, 0x20983), (0x2098C, 0x2098C),
+    (0x2099C, 0x2099C), (0x20AEA, 0x20AEA), (0x20AFD, 0x20AFD), (0x20B19, 0x20B19),
+    (0x22390, 0x22390), (0x22998, 0x22998), (0x23B1B, 0x23B1B), (0x2626D, 0x2626D),
+    (0x2F890, 0x2F890),
+];
+
+const NOT_XID_RANGES: &[(u32, u32)] = &[
+    (0x037A, 0x037A), (0x2E2F, 0x2E2F), (0xFC5E, 0xFC63), (0xFDFA, 0xFDFB), (0xFE70, 0xFE70),
+    (0xFE72, 0xFE72), (0xFE74, 0xFE74), (0xFE76, 0xFE76), (0xFE78, 0xFE78), (0xFE7A, 0xFE7A),
+    (0xFE7C, 0xFE7C), (0xFE7E, 0xFE7E),
+];
+
+const NOT_XID_START_RANGES: &[(u32, u32)] = &[
+    (0x0E33, 0x0E33), (0x0EB3, 0x0EB3), (0xFF9E, 0xFF9F),
+];
+
+const XID_START_EXTRA_RANGES: &[(u32, u32)] = &[
+    (0x1885, 0x1886), (0x2118, 0x2118), (0x212E, 0x212E),
+];
+
+const XID_CONTINUE_EXTRA_RANGES: &[(u32, u32)] = &[
+    (0x00B7, 0x00B7), (0x0387, 0x0387), (0x1369, 0x1371), (0x19DA, 0x19DA), (0x2118, 0x2118),
+    (0x212E, 0x212E),
+];
@@ -0,0 +1,1117 @@
+//! Regular expressions that match directly on a slice of chars.
+//!
+//! A `CharRegex` is parsed and compiled into a Thompson NFA, that is run by
+//! a Pike VM: all the threads of the NFA advance together, one char at a
+//! time, so the search is O(n * m) for n chars of text and m instructions
+//! of the program, without backtracking. The match is the leftmost first,
+//! like in Perl and in the regex crate, and all the positions are char
+//! indexes, never bytes.
+//!
+//! The syntax:
+//!
+//! * `a`, `\.`, `\n`, `\t`, `\u{E9}`, `\x41` - A char, escaped if it's
+//!   special.
+//! * `.` - Any char except `\n`, any char with the flag `s`.
+//! * `[a-z_]`, `[^0-9\s]` - A class of chars, with ranges and negation.
+//! * `\d`, `\w`, `\s` and `\D`, `\W`, `\S` - The Unicode decimal digits,
+//!   word chars and white space.
+//! * `\p{L}`, `\p{Lu}`, `\pN`, `\P{Letter}` - The chars of a General
+//!   Category, of a group of categories or of one of the properties `Any`,
+//!   `ASCII`, `Alphabetic`, `Lowercase`, `Uppercase` and `White_Space`.
+//! * `a|b` - Alternation.
+//! * `(a)`, `(?<name>a)`, `(?P<name>a)` and `(?:a)` - Groups, with a
+//!   capture, a named capture and without capture.
+//! * `*`, `+`, `?`, `{n}`, `{n,}` and `{n,m}` - Repetitions, greedy, or
+//!   lazy with a `?` after them.
+//! * `^`, `$`, `\A`, `\z`, `\b` and `\B` - The start and the end of the
+//!   text, or of a line with the flag `m`, and the word boundaries.
+//! * `(?ims)`, `(?-i)` and `(?i:a)` - The flags, `i` case insensitive with
+//!   the simple case folding, `m` multi line and `s` a dot that matches
+//!   `\n`, until the end of the group or only inside of the group.
+
+use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
+
+use crate::case_mapping;
+use crate::error::StringUtilsError;
+use crate::general_category::GeneralCategory;
+use crate::str_utils::StringUtils;
+
+// The limits of the size of a compiled regex.
+const MAX_REPETITION: u32 = 1000;
+const MAX_INSTS: usize = 100_000;
+// The instructions times the slots of the captures, the size of the
+// threads of a search.
+const MAX_THREAD_SLOTS: usize = 2_000_000;
+const MAX_NESTING: usize = 250;
+
+#[derive(Clone, Debug)]
+pub struct CharRegex {
+    pattern: String,
+    insts: Vec<Inst>,
+    // The number of capture groups, with the group 0 of the whole match.
+    num_groups: usize,
+    names: HashMap<String, usize>,
+    // The regex starts with `^` or `\A`, so it can only match at the start.
+    anchored_start: bool,
+}
+
+impl CharRegex {
+    /// Compiles the pattern, normalized like in `StringUtils::get_vec_chars()`.
+    /// Returns an error with the position of the char of a syntax error.
+    pub fn new(pattern: &str) -> Result<CharRegex, StringUtilsError> {
+        let pattern_chars = pattern.get_vec_chars();
+        let mut parser = Parser { chars: &pattern_chars, pos: 0, num_groups: 1, names: HashMap::new(), depth: 0 };
+        let node = parser.parse_alternation(Flags::default())?;
+        if parser.pos < pattern_chars.len() {
+            return Err(parser.error("unmatched ')'"));
+        }
+
+        let mut compiler = Compiler { insts: Vec::new(), depth: 0 };
+        compiler.push(Inst::Save(0))?;
+        compiler.compile(&node)?;
+        compiler.push(Inst::Save(1))?;
+        compiler.push(Inst::Match)?;
+        if compiler.insts.len() * 2 * parser.num_groups > MAX_THREAD_SLOTS {
+            return Err(StringUtilsError::InvalidRegex { position: 0, reason: "the regex is too big" });
+        }
+        let anchored_start = matches!(compiler.insts.get(1), Some(Inst::Assert(Assertion::StartText)));
+        Ok(CharRegex {
+            pattern: pattern.to_string(),
+            insts: compiler.insts,
+            num_groups: parser.num_groups,
+            names: parser.names,
+            anchored_start,
+        })
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// The number of capture groups, with the group 0 of the whole match.
+    #[inline]
+    pub fn captures_len(&self) -> usize {
+        self.num_groups
+    }
+
+    pub fn is_match(&self, haystack: &[char]) -> bool {
+        self.find(haystack).is_some()
+    }
+
+    /// Returns a None or the range of the first match.
+    pub fn find(&self, haystack: &[char]) -> Option<Range<usize>> {
+        self.find_at(haystack, 0)
+    }
+
+    /// Returns a None or the range of the first match that starts at or
+    /// after start_pos. The anchors and the word boundaries see the chars
+    /// before start_pos. A start_pos after the end returns None.
+    pub fn find_at(&self, haystack: &[char], start_pos: usize) -> Option<Range<usize>> {
+        let slots = self.exec(&mut Cache::new(self), haystack, start_pos)?;
+        Some(slots[0]?..slots[1]?)
+    }
+
+    /// Iterator over the ranges of the non overlapping matches. An empty
+    /// match right after the previous match is skipped.
+    pub fn find_iter<'r, 'h>(&'r self, haystack: &'h [char]) -> CharRegexMatches<'r, 'h> {
+        CharRegexMatches { captures_iter: self.captures_iter(haystack) }
+    }
+
+    /// Returns a None or the capture groups of the first match.
+    pub fn captures(&self, haystack: &[char]) -> Option<CharCaptures<'_>> {
+        self.captures_at(haystack, 0)
+    }
+
+    /// Like `find_at()` but returns the capture groups.
+    pub fn captures_at(&self, haystack: &[char], start_pos: usize) -> Option<CharCaptures<'_>> {
+        let slots = self.exec(&mut Cache::new(self), haystack, start_pos)?;
+        Some(CharCaptures { regex: self, slots })
+    }
+
+    /// Iterator over the capture groups of the non overlapping matches,
+    /// like `find_iter()`.
+    pub fn captures_iter<'r, 'h>(&'r self, haystack: &'h [char]) -> CharCapturesIter<'r, 'h> {
+        CharCapturesIter { regex: self, haystack, cache: Cache::new(self), next_start_pos: 0, last_end: None }
+    }
+
+    /// Returns the chars with all the matches replaced by the replacement,
+    /// with the groups expanded like in `CharCaptures::expand()`, and the
+    /// number of replaces.
+    pub fn replace_all(&self, haystack: &[char], replacement: &str) -> (Vec<char>, usize) {
+        let pieces = parse_replacement(&replacement.get_vec_chars());
+        let mut res_vec_chars = Vec::with_capacity(haystack.len());
+        let mut last_index = 0;
+        let mut num_matches = 0;
+        for captures in self.captures_iter(haystack) {
+            let range = captures.range();
+            res_vec_chars.extend_from_slice(&haystack[last_index..range.start]);
+            captures.expand_pieces(haystack, &pieces, &mut res_vec_chars);
+            last_index = range.end;
+            num_matches += 1;
+        }
+        res_vec_chars.extend_from_slice(&haystack[last_index..]);
+        (res_vec_chars, num_matches)
+    }
+
+    // Runs the Pike VM from start_pos and returns the slots of the first
+    // match, two for each group with the start and the end.
+    fn exec(&self, cache: &mut Cache, haystack: &[char], start_pos: usize) -> Option<Vec<Option<usize>>> {
+        if start_pos > haystack.len() {
+            return None;
+        }
+        let Cache { clist, nlist, caps, stack } = cache;
+        clist.clear();
+        nlist.clear();
+        let mut matched: Option<Vec<Option<usize>>> = None;
+        let mut at = start_pos;
+        loop {
+            // A new thread at each position, with the lowest priority, until
+            // there is a match.
+            if matched.is_none() && (!self.anchored_start || at == 0) {
+                caps.fill(None);
+                self.add_thread(clist, 0, at, haystack, caps, stack);
+            }
+            if clist.is_empty() && (matched.is_some() || self.anchored_start) {
+                break;
+            }
+
+            let c = haystack.get(at).copied();
+            for i in 0..clist.len() {
+                let pc = clist.dense[i];
+                match &self.insts[pc] {
+                    Inst::Match => {
+                        // The threads after this one have a lower priority.
+                        matched = Some(clist.caps(pc).to_vec());
+                        break;
+                    }
+                    inst => {
+                        if let Some(c) = c {
+                            if inst.matches(c) {
+                                caps.copy_from_slice(clist.caps(pc));
+                                self.add_thread(nlist, pc + 1, at + 1, haystack, caps, stack);
+                            }
+                        }
+                    }
+                }
+            }
+            mem::swap(clist, nlist);
+            nlist.clear();
+            if at >= haystack.len() {
+                break;
+            }
+            at += 1;
+        }
+        matched
+    }
+
+    // Adds the thread at pc and the ones it reaches without a char, in the
+    // order of priority, with the captures of caps.
+    fn add_thread(&self, list: &mut Threads, pc: usize, at: usize, haystack: &[char],
+                  caps: &mut [Option<usize>], stack: &mut Vec<Frame>) {
+        stack.push(Frame::Explore(pc));
+        while let Some(frame) = stack.pop() {
+            let pc = match frame {
+                Frame::Explore(pc) => pc,
+                Frame::RestoreCapture { slot, old } => {
+                    caps[slot] = old;
+                    continue;
+                }
+            };
+            if list.contains(pc) {
+                continue;
+            }
+            list.insert(pc);
+            match self.insts[pc] {
+                Inst::Jmp(target) => stack.push(Frame::Explore(target)),
+                Inst::Split(first, second) => {
+                    stack.push(Frame::Explore(second));
+                    stack.push(Frame::Explore(first));
+                }
+                Inst::Save(slot) => {
+                    stack.push(Frame::RestoreCapture { slot, old: caps[slot] });
+                    caps[slot] = Some(at);
+                    stack.push(Frame::Explore(pc + 1));
+                }
+                Inst::Assert(assertion) => {
+                    if assertion.holds(haystack, at) {
+                        stack.push(Frame::Explore(pc + 1));
+                    }
+                }
+                _ => list.caps_mut(pc).copy_from_slice(caps),
+            }
+        }
+    }
+}
+
+/// The capture groups of a match, the group 0 is the whole match.
+#[derive(Clone, Debug)]
+pub struct CharCaptures<'r> {
+    regex: &'r CharRegex,
+    slots: Vec<Option<usize>>,
+}
+
+impl CharCaptures<'_> {
+    /// Returns a None if the group didn't participate in the match.
+    pub fn get(&self, group: usize) -> Option<Range<usize>> {
+        let start = (*self.slots.get(2 * group)?)?;
+        let end = (*self.slots.get(2 * group + 1)?)?;
+        Some(start..end)
+    }
+
+    pub fn name(&self, name: &str) -> Option<Range<usize>> {
+        self.get(*self.regex.names.get(name)?)
+    }
+
+    /// The range of the whole match.
+    pub fn range(&self) -> Range<usize> {
+        self.get(0).unwrap_or(0..0)
+    }
+
+    /// The number of groups, with the group 0.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.regex.num_groups
+    }
+
+    /// Always false, there is always the group 0.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Pushes the replacement to dst, with `$1` or `${1}` replaced by the
+    /// chars of the group 1 in haystack, `${name}` by the named group and
+    /// `$$` by a `$`. The groups that didn't match are empty.
+    pub fn expand(&self, haystack: &[char], replacement: &str, dst: &mut Vec<char>) {
+        let pieces = parse_replacement(&replacement.get_vec_chars());
+        self.expand_pieces(haystack, &pieces, dst);
+    }
+
+    fn expand_pieces(&self, haystack: &[char], pieces: &[ReplacePiece], dst: &mut Vec<char>) {
+        for piece in pieces {
+            let range = match piece {
+                ReplacePiece::Chars(chars) => {
+                    dst.extend_from_slice(chars);
+                    continue;
+                }
+                ReplacePiece::Group(group) => self.get(*group),
+                ReplacePiece::Name(name) => self.name(name),
+            };
+            if let Some(range) = range {
+                dst.extend_from_slice(&haystack[range]);
+            }
+        }
+    }
+}
+
+pub struct CharCapturesIter<'r, 'h> {
+    regex: &'r CharRegex,
+    haystack: &'h [char],
+    // The threads of the searches, allocated once for all the matches.
+    cache: Cache,
+    next_start_pos: usize,
+    last_end: Option<usize>,
+}
+
+impl<'r> Iterator for CharCapturesIter<'r, '_> {
+    type Item = CharCaptures<'r>;
+
+    fn next(&mut self) -> Option<CharCaptures<'r>> {
+        loop {
+            let slots = self.regex.exec(&mut self.cache, self.haystack, self.next_start_pos)?;
+            let captures = CharCaptures { regex: self.regex, slots };
+            let range = captures.range();
+            if range.is_empty() {
+                self.next_start_pos = range.end + 1;
+                if self.last_end == Some(range.end) {
+                    continue;
+                }
+            } else {
+                self.next_start_pos = range.end;
+            }
+            self.last_end = Some(range.end);
+            return Some(captures);
+        }
+    }
+}
+
+pub struct CharRegexMatches<'r, 'h> {
+    captures_iter: CharCapturesIter<'r, 'h>,
+}
+
+impl Iterator for CharRegexMatches<'_, '_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        self.captures_iter.next().map(|captures| captures.range())
+    }
+}
+
+// The program of the NFA.
+
+#[derive(Clone, Debug)]
+enum Inst {
+    Char(char),
+    // A char compared by the simple case folding, the char is folded.
+    CharFold(char),
+    Any,
+    AnyButNewLine,
+    Class(CharClass),
+    Assert(Assertion),
+    // Continues at both, the first one with more priority.
+    Split(usize, usize),
+    Jmp(usize),
+    Save(usize),
+    Match,
+}
+
+impl Inst {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Inst::Char(inst_c) => *inst_c == c,
+            Inst::CharFold(inst_c) => *inst_c == case_mapping::simple_casefold(c),
+            Inst::Any => true,
+            Inst::AnyButNewLine => c != '\n',
+            Inst::Class(class) => class.matches(c),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Assertion {
+    StartText,
+    EndText,
+    StartLine,
+    EndLine,
+    WordBoundary,
+    NotWordBoundary,
+}
+
+impl Assertion {
+    fn holds(self, haystack: &[char], at: usize) -> bool {
+        let is_word_at = |pos: Option<usize>| pos.and_then(|pos| haystack.get(pos)).is_some_and(|c| is_word_char(*c));
+        match self {
+            Assertion::StartText => at == 0,
+            Assertion::EndText => at == haystack.len(),
+            Assertion::StartLine => at == 0 || haystack[at - 1] == '\n',
+            Assertion::EndLine => at == haystack.len() || haystack[at] == '\n',
+            Assertion::WordBoundary => is_word_at(at.checked_sub(1)) != is_word_at(Some(at)),
+            Assertion::NotWordBoundary => is_word_at(at.checked_sub(1)) == is_word_at(Some(at)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CharClass {
+    items: Vec<ClassItem>,
+    negated: bool,
+    case_insensitive: bool,
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        let mut is_match = self.items.iter().any(|item| item.matches(c));
+        if !is_match && self.case_insensitive {
+            // The other chars of the same case folding, in the usual cases.
+            let mut lowercase = c.to_lowercase();
+            let mut uppercase = c.to_uppercase();
+            let variants = [Some(case_mapping::simple_casefold(c)),
+                            lowercase.next().filter(|_| lowercase.next().is_none()),
+                            uppercase.next().filter(|_| uppercase.next().is_none())];
+            is_match = variants.iter()
+                .flatten()
+                .any(|variant| *variant != c && self.items.iter().any(|item| item.matches(*variant)));
+        }
+        is_match != self.negated
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ClassItem {
+    Range(char, char),
+    Property(Property, bool),
+}
+
+impl ClassItem {
+    fn matches(self, c: char) -> bool {
+        match self {
+            ClassItem::Range(start, end) => start <= c && c <= end,
+            ClassItem::Property(property, negated) => property.matches(c) != negated,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Property {
+    // A bit for each GeneralCategory.
+    Categories(u32),
+    Digit,
+    Word,
+    Space,
+    Any,
+    Ascii,
+    Alphabetic,
+    Lowercase,
+    Uppercase,
+}
+
+impl Property {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Property::Categories(mask) => mask & category_bit(GeneralCategory::of(c)) != 0,
+            Property::Digit => GeneralCategory::of(c) == GeneralCategory::Nd,
+            Property::Word => is_word_char(c),
+            Property::Space => c.is_whitespace(),
+            Property::Any => true,
+            Property::Ascii => c.is_ascii(),
+            Property::Alphabetic => c.is_alphabetic(),
+            Property::Lowercase => c.is_lowercase(),
+            Property::Uppercase => c.is_uppercase(),
+        }
+    }
+
+    // The names are compared without case, spaces, '_' and '-', so
+    // "white space" is "White_Space".
+    fn from_name(name: &[char]) -> Option<Property> {
+        let name: String = name.iter()
+            .filter(|c| !matches!(c, ' ' | '_' | '-'))
+            .flat_map(|c| c.to_lowercase())
+            .collect();
+        let groups: &[(&str, &str)] = &[("l", "letter"), ("m", "mark"), ("n", "number"), ("p", "punctuation"),
+                                        ("s", "symbol"), ("z", "separator"), ("c", "other")];
+        for (abbr, long_name) in groups {
+            if name == *abbr || name == *long_name {
+                let mask = ALL_CATEGORIES.iter()
+                    .filter(|category| category.abbr().to_lowercase().starts_with(abbr))
+                    .fold(0, |mask, category| mask | category_bit(*category));
+                return Some(Property::Categories(mask));
+            }
+        }
+        if let Some(category) = ALL_CATEGORIES.iter().find(|category| category.abbr().to_lowercase() == name) {
+            return Some(Property::Categories(category_bit(*category)));
+        }
+        match name.as_str() {
+            "lc" | "l&" | "casedletter" => {
+                use GeneralCategory::*;
+                Some(Property::Categories(category_bit(Lu) | category_bit(Ll) | category_bit(Lt)))
+            }
+            "any" => Some(Property::Any),
+            "ascii" => Some(Property::Ascii),
+            "alphabetic" | "alpha" => Some(Property::Alphabetic),
+            "lowercase" | "lower" => Some(Property::Lowercase),
+            "uppercase" | "upper" => Some(Property::Uppercase),
+            "whitespace" | "space" => Some(Property::Space),
+            _ => None,
+        }
+    }
+}
+
+const ALL_CATEGORIES: [GeneralCategory; 30] = {
+    use GeneralCategory::*;
+    [Lu, Ll, Lt, Lm, Lo, Mn, Mc, Me, Nd, Nl, No, Pc, Pd, Ps, Pe, Pi, Pf, Po,
+     Sm, Sc, Sk, So, Zs, Zl, Zp, Cc, Cf, Cs, Co, Cn]
+};
+
+#[inline]
+fn category_bit(category: GeneralCategory) -> u32 {
+    1 << (category as u32)
+}
+
+// A char of a word for `\w` and `\b`, a letter, a mark, a decimal digit or
+// a connector punctuation like '_'.
+fn is_word_char(c: char) -> bool {
+    use GeneralCategory::*;
+    c.is_alphabetic() || matches!(GeneralCategory::of(c), Mn | Mc | Me | Nd | Pc)
+}
+
+// The parser, from the chars of the pattern to the tree of nodes.
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Flags {
+    case_insensitive: bool,
+    multi_line: bool,
+    dot_all: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Char(char, bool),
+    Any(bool),
+    Class(CharClass),
+    Assert(Assertion),
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repetition { node: Box<Node>, min: u32, max: Option<u32>, greedy: bool },
+}
+
+enum Escape {
+    Char(char),
+    Property(Property, bool),
+    Assert(Assertion),
+}
+
+struct Parser<'a> {
+    chars: &'a [char],
+    pos: usize,
+    num_groups: usize,
+    names: HashMap<String, usize>,
+    // The number of open groups.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, reason: &'static str) -> StringUtilsError {
+        StringUtilsError::InvalidRegex { position: self.pos, reason }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(& mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next_char(& mut self, reason: &'static str) -> Result<char, StringUtilsError> {
+        let c = self.peek().ok_or_else(|| self.error(reason))?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn parse_alternation(& mut self, flags: Flags) -> Result<Node, StringUtilsError> {
+        // The flags of `(?i)` apply until the end of the group.
+        let mut flags = flags;
+        let mut branches = vec![self.parse_concat(&mut flags)?];
+        while self.eat('|') {
+            branches.push(self.parse_concat(&mut flags)?);
+        }
+        Ok(if branches.len() == 1 { branches.swap_remove(0) } else { Node::Alternation(branches) })
+    }
+
+    fn parse_concat(& mut self, flags: &mut Flags) -> Result<Node, StringUtilsError> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            if let Some(atom) = self.parse_atom(flags)? {
+                nodes.push(self.parse_repetition(atom)?);
+            }
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    // Returns a None for a group that only sets the flags.
+    fn parse_atom(& mut self, flags: &mut Flags) -> Result<Option<Node>, StringUtilsError> {
+        let c = self.next_char("unexpected end")?;
+        let node = match c {
+            '(' => return self.parse_group(flags),
+            '[' => Node::Class(self.parse_class(flags)?),
+            '.' => Node::Any(flags.dot_all),
+            '^' => Node::Assert(if flags.multi_line { Assertion::StartLine } else { Assertion::StartText }),
+            '$' => Node::Assert(if flags.multi_line { Assertion::EndLine } else { Assertion::EndText }),
+            '*' | '+' | '?' | '{' => {
+                self.pos -= 1;
+                return Err(self.error("repetition without an operand"));
+            }
+            '\\' => match self.parse_escape()? {
+                Escape::Char(c) => Node::Char(c, flags.case_insensitive),
+                Escape::Property(property, negated) => Node::Class(CharClass {
+                    items: vec![ClassItem::Property(property, negated)],
+                    negated: false,
+                    case_insensitive: false,
+                }),
+                Escape::Assert(assertion) => Node::Assert(assertion),
+            },
+            c => Node::Char(c, flags.case_insensitive),
+        };
+        Ok(Some(node))
+    }
+
+    fn parse_group(& mut self, flags: &mut Flags) -> Result<Option<Node>, StringUtilsError> {
+        if self.depth >= MAX_NESTING {
+            self.pos -= 1;
+            return Err(self.error("nested too deeply"));
+        }
+        let mut group_flags = *flags;
+        let mut capture = true;
+        if self.eat('?') {
+            if self.eat('P') && self.peek() != Some('<') {
+                return Err(self.error("expected '<' after '(?P'"));
+            }
+            if self.eat('<') {
+                let name_start = self.pos;
+                while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                    self.pos += 1;
+                }
+                let name: String = self.chars[name_start..self.pos].iter().collect();
+                if name.is_empty() || !self.eat('>') {
+                    return Err(self.error("invalid name of a group"));
+                }
+                if self.names.insert(name, self.num_groups).is_some() {
+                    return Err(self.error("repeated name of a group"));
+                }
+            } else {
+                // The flags, until the end of the group or only inside of the group.
+                let mut enable = true;
+                loop {
+                    match self.next_char("unclosed group")? {
+                        'i' => group_flags.case_insensitive = enable,
+                        'm' => group_flags.multi_line = enable,
+                        's' => group_flags.dot_all = enable,
+                        '-' if enable => enable = false,
+                        ':' => break,
+                        ')' => {
+                            *flags = group_flags;
+                            return Ok(None);
+                        }
+                        _ => {
+                            self.pos -= 1;
+                            return Err(self.error("unknown flag"));
+                        }
+                    }
+                }
+                capture = false;
+            }
+        }
+        let group_index = if capture {
+            self.num_groups += 1;
+            Some(self.num_groups - 1)
+        } else {
+            None
+        };
+        self.depth += 1;
+        let node = self.parse_alternation(group_flags)?;
+        self.depth -= 1;
+        if !self.eat(')') {
+            return Err(self.error("unclosed group"));
+        }
+        Ok(Some(Node::Group(Box::new(node), group_index)))
+    }
+
+    fn parse_repetition(& mut self, node: Node) -> Result<Node, StringUtilsError> {
+        let (min, max) = match self.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.pos += 1;
+                let min = self.parse_number()?;
+                let max = if self.eat(',') {
+                    if self.peek() == Some('}') { None } else { Some(self.parse_number()?) }
+                } else {
+                    Some(min)
+                };
+                if self.peek() != Some('}') {
+                    return Err(self.error("unclosed repetition"));
+                }
+                if max.is_some_and(|max| max < min) {
+                    return Err(self.error("repetition with the min greater then the max"));
+                }
+                (min, max)
+            }
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        let greedy = !self.eat('?');
+        if matches!(self.peek(), Some('*' | '+' | '?' | '{')) {
+            return Err(self.error("repetition of a repetition"));
+        }
+        Ok(Node::Repetition { node: Box::new(node), min, max, greedy })
+    }
+
+    fn parse_number(& mut self) -> Result<u32, StringUtilsError> {
+        let start = self.pos;
+        let mut number: u32 = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            number = number.saturating_mul(10).saturating_add(digit);
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("expected a number"));
+        }
+        if number > MAX_REPETITION {
+            return Err(self.error("repetition count too large"));
+        }
+        Ok(number)
+    }
+
+    fn parse_class(& mut self, flags: &Flags) -> Result<CharClass, StringUtilsError> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        // A ']' at the start is a char.
+        if self.eat(']') {
+            items.push(ClassItem::Range(']', ']'));
+        }
+        loop {
+            let c = self.next_char("unclosed class")?;
+            let start = match c {
+                ']' => break,
+                '\\' => match self.parse_escape()? {
+                    Escape::Char(c) => c,
+                    Escape::Property(property, negated) => {
+                        items.push(ClassItem::Property(property, negated));
+                        continue;
+                    }
+                    Escape::Assert(_) => return Err(self.error("anchor inside of a class")),
+                },
+                c => c,
+            };
+            // A range, unless the '-' is the last char of the class.
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                self.pos += 1;
+                let end = match self.next_char("unclosed class")? {
+                    '\\' => match self.parse_escape()? {
+                        Escape::Char(c) => c,
+                        _ => return Err(self.error("invalid end of a range")),
+                    },
+                    c => c,
+                };
+                if end < start {
+                    return Err(self.error("range with the start greater then the end"));
+                }
+                items.push(ClassItem::Range(start, end));
+            } else {
+                items.push(ClassItem::Range(start, start));
+            }
+        }
+        Ok(CharClass { items, negated, case_insensitive: flags.case_insensitive })
+    }
+
+    // Parses the escape after a '\'.
+    fn parse_escape(& mut self) -> Result<Escape, StringUtilsError> {
+        let c = self.next_char("incomplete escape")?;
+        let escape = match c {
+            'n' => Escape::Char('\n'),
+            't' => Escape::Char('\t'),
+            'r' => Escape::Char('\r'),
+            'f' => Escape::Char('\u{C}'),
+            'v' => Escape::Char('\u{B}'),
+            '0' => Escape::Char('\0'),
+            'd' | 'D' => Escape::Property(Property::Digit, c == 'D'),
+            'w' | 'W' => Escape::Property(Property::Word, c == 'W'),
+            's' | 'S' => Escape::Property(Property::Space, c == 'S'),
+            'p' | 'P' => {
+                let name_start;
+                let name_end;
+                if self.eat('{') {
+                    name_start = self.pos;
+                    while self.peek().is_some_and(|c| c != '}') {
+                        self.pos += 1;
+                    }
+                    name_end = self.pos;
+                    if !self.eat('}') {
+                        return Err(self.error("unclosed property name"));
+                    }
+                } else {
+                    name_start = self.pos;
+                    self.next_char("incomplete property")?;
+                    name_end = self.pos;
+                }
+                let property = Property::from_name(&self.chars[name_start..name_end])
+                    .ok_or(StringUtilsError::InvalidRegex { position: name_start, reason: "unknown property" })?;
+                Escape::Property(property, c == 'P')
+            }
+            'u' | 'x' => Escape::Char(self.parse_hex(c)?),
+            'A' => Escape::Assert(Assertion::StartText),
+            'z' => Escape::Assert(Assertion::EndText),
+            'b' => Escape::Assert(Assertion::WordBoundary),
+            'B' => Escape::Assert(Assertion::NotWordBoundary),
+            c if c.is_alphanumeric() => {
+                self.pos -= 1;
+                return Err(self.error("unknown escape"));
+            }
+            c => Escape::Char(c),
+        };
+        Ok(escape)
+    }
+
+    // Parses `{hex}` or the 4 hex digits after a 'u' and the 2 after an 'x'.
+    fn parse_hex(& mut self, kind: char) -> Result<char, StringUtilsError> {
+        let braces = self.eat('{');
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) && (braces || self.pos - start < if kind == 'u' { 4 } else { 2 }) {
+            self.pos += 1;
+        }
+        let hex: String = self.chars[start..self.pos].iter().collect();
+        if braces && !self.eat('}') {
+            return Err(self.error("unclosed hex escape"));
+        }
+        u32::from_str_radix(&hex, 16).ok()
+            .and_then(char::from_u32)
+            .ok_or(StringUtilsError::InvalidRegex { position: start, reason: "invalid hex escape" })
+    }
+}
+
+// The compiler, from the tree of nodes to the program of the NFA.
+
+struct Compiler {
+    insts: Vec<Inst>,
+    // The number of groups around the node.
+    depth: usize,
+}
+
+impl Compiler {
+    fn push(& mut self, inst: Inst) -> Result<usize, StringUtilsError> {
+        if self.insts.len() >= MAX_INSTS {
+            return Err(StringUtilsError::InvalidRegex { position: 0, reason: "the regex is too big" });
+        }
+        self.insts.push(inst);
+        Ok(self.insts.len() - 1)
+    }
+
+    fn compile(& mut self, node: &Node) -> Result<(), StringUtilsError> {
+        match node {
+            Node::Char(c, case_insensitive) => {
+                let folded_c = case_mapping::simple_casefold(*c);
+                // Only a char with other chars of the same folding.
+                let inst = if *case_insensitive && (folded_c != *c || has_other_case(*c)) {
+                    Inst::CharFold(folded_c)
+                } else {
+                    Inst::Char(*c)
+                };
+                self.push(inst)?;
+            }
+            Node::Any(dot_all) => {
+                self.push(if *dot_all { Inst::Any } else { Inst::AnyButNewLine })?;
+            }
+            Node::Class(class) => {
+                self.push(Inst::Class(class.clone()))?;
+            }
+            Node::Assert(assertion) => {
+                self.push(Inst::Assert(*assertion))?;
+            }
+            Node::Group(node, group_index) => {
+                if self.depth >= MAX_NESTING {
+                    return Err(StringUtilsError::InvalidRegex { position: 0, reason: "nested too deeply" });
+                }
+                self.depth += 1;
+                match group_index {
+                    Some(group_index) => {
+                        self.push(Inst::Save(2 * group_index))?;
+                        self.compile(node)?;
+                        self.push(Inst::Save(2 * group_index + 1))?;
+                    }
+                    None => self.compile(node)?,
+                }
+                self.depth -= 1;
+            }
+            Node::Concat(nodes) => {
+                for node in nodes {
+                    self.compile(node)?;
+                }
+            }
+            Node::Alternation(branches) => {
+                let mut jmps = Vec::new();
+                for (index, branch) in branches.iter().enumerate() {
+                    if index + 1 < branches.len() {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.compile(branch)?;
+                        jmps.push(self.push(Inst::Jmp(0))?);
+                        self.insts[split] = Inst::Split(split + 1, self.insts.len());
+                    } else {
+                        self.compile(branch)?;
+                    }
+                }
+                let end = self.insts.len();
+                for jmp in jmps {
+                    self.insts[jmp] = Inst::Jmp(end);
+                }
+            }
+            Node::Repetition { node, min, max, greedy } => {
+                let split_to = |body: usize, end: usize| {
+                    if *greedy { Inst::Split(body, end) } else { Inst::Split(end, body) }
+                };
+                match max {
+                    None => {
+                        // `x{n,}` is n - 1 times x and `x+`, and `x*` is `(x+)?`,
+                        // so an empty x can match once and set its captures.
+                        for _ in 1..*min {
+                            self.compile(node)?;
+                        }
+                        let optional_split = if *min == 0 { Some(self.push(Inst::Split(0, 0))?) } else { None };
+                        let body = self.insts.len();
+                        self.compile(node)?;
+                        let loop_split = self.push(Inst::Split(0, 0))?;
+                        let end = self.insts.len();
+                        self.insts[loop_split] = split_to(body, end);
+                        if let Some(optional_split) = optional_split {
+                            self.insts[optional_split] = split_to(body, end);
+                        }
+                    }
+                    Some(max) => {
+                        for _ in 0..*min {
+                            self.compile(node)?;
+                        }
+                        let mut splits = Vec::new();
+                        for _ in *min..*max {
+                            splits.push(self.push(Inst::Split(0, 0))?);
+                            self.compile(node)?;
+                        }
+                        let end = self.insts.len();
+                        for split in splits {
+                            self.insts[split] = split_to(split + 1, end);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// A char with other chars of the same simple case folding, like 'k' with
+// 'K' and the Kelvin sign.
+fn has_other_case(c: char) -> bool {
+    c.to_uppercase().ne(std::iter::once(c)) || c.to_lowercase().ne(std::iter::once(c))
+}
+
+// The Pike VM, the threads of a step in the order of priority, each one
+// with its slots of the captures.
+
+struct Cache {
+    clist: Threads,
+    nlist: Threads,
+    caps: Vec<Option<usize>>,
+    stack: Vec<Frame>,
+}
+
+impl Cache {
+    fn new(regex: &CharRegex) -> Cache {
+        let num_slots = 2 * regex.num_groups;
+        Cache {
+            clist: Threads::new(regex.insts.len(), num_slots),
+            nlist: Threads::new(regex.insts.len(), num_slots),
+            caps: vec![None; num_slots],
+            stack: Vec::new(),
+        }
+    }
+}
+
+struct Threads {
+    dense: Vec<usize>,
+    sparse: Vec<usize>,
+    slots: Vec<Option<usize>>,
+    num_slots: usize,
+}
+
+impl Threads {
+    fn new(num_insts: usize, num_slots: usize) -> Threads {
+        Threads {
+            dense: Vec::with_capacity(num_insts),
+            sparse: vec![0; num_insts],
+            slots: vec![None; num_insts * num_slots],
+            num_slots,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.dense.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+
+    #[inline]
+    fn contains(&self, pc: usize) -> bool {
+        let index = self.sparse[pc];
+        index < self.dense.len() && self.dense[index] == pc
+    }
+
+    fn insert(& mut self, pc: usize) {
+        self.sparse[pc] = self.dense.len();
+        self.dense.push(pc);
+    }
+
+    fn clear(& mut self) {
+        self.dense.clear();
+    }
+
+    fn caps(&self, pc: usize) -> &[Option<usize>] {
+        &self.slots[pc * self.num_slots..(pc + 1) * self.num_slots]
+    }
+
+    fn caps_mut(& mut self, pc: usize) -> & mut [Option<usize>] {
+        &mut self.slots[pc * self.num_slots..(pc + 1) * self.num_slots]
+    }
+}
+
+enum Frame {
+    Explore(usize),
+    RestoreCapture { slot: usize, old: Option<usize> },
+}
+
+// The replacement of `replace_all()`, parsed once.
+
+enum ReplacePiece {
+    Chars(Vec<char>),
+    Group(usize),
+    Name(String),
+}
+
+fn parse_replacement(replacement: &[char]) -> Vec<ReplacePiece> {
+    let mut pieces = Vec::new();
+    let mut chars = Vec::new();
+    let mut pos = 0;
+    while pos < replacement.len() {
+        let c = replacement[pos];
+        pos += 1;
+        if c != '$' || pos == replacement.len() {
+            chars.push(c);
+            continue;
+        }
+        let group_start = pos;
+        let piece = if replacement[pos] == '$' {
+            pos += 1;
+            chars.push('$');
+            continue;
+        } else if replacement[pos] == '{' {
+            match replacement[pos..].iter().position(|c| *c == '}') {
+                Some(len) => {
+                    pos += len + 1;
+                    let name: String = replacement[(group_start + 1)..(pos - 1)].iter().collect();
+                    match name.parse::<usize>() {
+                        Ok(group) => ReplacePiece::Group(group),
+                        Err(_) => ReplacePiece::Name(name),
+                    }
+                }
+                None => {
+                    chars.push('$');
+                    continue;
+                }
+            }
+        } else {
+            while pos < replacement.len() && replacement[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            if pos == group_start {
+                chars.push('$');
+                continue;
+            }
+            let digits: String = replacement[group_start..pos].iter().collect();
+            ReplacePiece::Group(digits.parse().unwrap_or(usize::MAX))
+        };
+        if !chars.is_empty() {
+            pieces.push(ReplacePiece::Chars(mem::take(&mut chars)));
+        }
+        pieces.push(piece);
+    }
+    if !chars.is_empty() {
+        pieces.push(ReplacePiece::Chars(chars));
+    }
+    pieces
+}
@@ -0,0 +1,788 @@
+//! The `CharRope` type, a string of chars for large editable texts.
+//!
+//! The chars are kept in chunks of at most a few hundred chars, in the nodes
+//! of a balanced ( AVL ) tree ordered by char position. Insert, remove,
+//! slice and random access by char index are O(log n), instead of the O(n)
+//! of a `Vec<char>` that has to move or copy all the chars after the edit.
+//! The nodes are shared with copy on write, so a clone or a slice is cheap
+//! and only the path to an edited chunk is copied.
+//!
+//! The search methods go over the chunks in place with a `CharFinder`, and
+//! a match can cross the chunks.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, Range, RangeBounds};
+use std::sync::Arc;
+
+use crate::aho_corasick::MatchKind;
+use crate::error::StringUtilsError;
+use crate::finder::CharFinder;
+use crate::str_utils::{self, StringUtils};
+use crate::vec_chars::{self, StringUtilsVecCharsV2};
+
+// The len of the chunks of a new rope, and the max len of a chunk that
+// grows with the inserts.
+const CHUNK_LEN: usize = 256;
+const MAX_CHUNK_LEN: usize = 512;
+
+type Link = Option<Arc<Node>>;
+
+#[derive(Clone)]
+struct Node {
+    chunk: Vec<char>,
+    left: Link,
+    right: Link,
+    // Number of chars and height of the subtree.
+    len: usize,
+    height: usize,
+}
+
+#[derive(Clone, Default)]
+pub struct CharRope {
+    root: Link,
+}
+
+
+// The balanced tree of chunks, all the chunks have at least one char.
+
+#[inline]
+fn link_len(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.len)
+}
+
+#[inline]
+fn link_height(link: &Link) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn update(node: &mut Node) {
+    node.len = link_len(&node.left) + node.chunk.len() + link_len(&node.right);
+    node.height = 1 + link_height(&node.left).max(link_height(&node.right));
+}
+
+fn new_node(chunk: Vec<char>) -> Arc<Node> {
+    let len = chunk.len();
+    Arc::new(Node { chunk, left: None, right: None, len, height: 1 })
+}
+
+fn balance_factor(node: &Node) -> isize {
+    link_height(&node.left) as isize - link_height(&node.right) as isize
+}
+
+fn rotate_right(mut node: Arc<Node>) -> Arc<Node> {
+    let n = Arc::make_mut(&mut node);
+    let mut left = n.left.take().expect("rotate_right() needs a left child");
+    let l = Arc::make_mut(&mut left);
+    n.left = l.right.take();
+    update(n);
+    l.right = Some(node);
+    update(l);
+    left
+}
+
+fn rotate_left(mut node: Arc<Node>) -> Arc<Node> {
+    let n = Arc::make_mut(&mut node);
+    let mut right = n.right.take().expect("rotate_left() needs a right child");
+    let r = Arc::make_mut(&mut right);
+    n.right = r.left.take();
+    update(n);
+    r.left = Some(node);
+    update(r);
+    right
+}
+
+// Restores the balance of a node whose children heights differ by at most 2.
+fn balance(mut node: Arc<Node>) -> Arc<Node> {
+    let n = Arc::make_mut(&mut node);
+    update(n);
+    let factor = balance_factor(n);
+    if factor > 1 {
+        if n.left.as_ref().is_some_and(|left| balance_factor(left) < 0) {
+            n.left = n.left.take().map(rotate_left);
+        }
+        return rotate_right(node);
+    }
+    if factor < -1 {
+        if n.right.as_ref().is_some_and(|right| balance_factor(right) > 0) {
+            n.right = n.right.take().map(rotate_right);
+        }
+        return rotate_left(node);
+    }
+    node
+}
+
+// Joins the trees left, mid and right, in this order, mid has no children.
+fn join(left: Link, mut mid: Arc<Node>, right: Link) -> Arc<Node> {
+    let left_height = link_height(&left);
+    let right_height = link_height(&right);
+    if left_height > right_height + 1 {
+        let mut left = left.unwrap();
+        let l = Arc::make_mut(&mut left);
+        let left_right = l.right.take();
+        l.right = Some(join(left_right, mid, right));
+        balance(left)
+    } else if right_height > left_height + 1 {
+        let mut right = right.unwrap();
+        let r = Arc::make_mut(&mut right);
+        let right_left = r.left.take();
+        r.left = Some(join(left, mid, right_left));
+        balance(right)
+    } else {
+        let m = Arc::make_mut(&mut mid);
+        m.left = left;
+        m.right = right;
+        update(m);
+        mid
+    }
+}
+
+// Removes the last node of the tree, returns the rest of the tree and it.
+fn remove_last(mut node: Arc<Node>) -> (Link, Arc<Node>) {
+    let n = Arc::make_mut(&mut node);
+    match n.right.take() {
+        None => {
+            let left = n.left.take();
+            update(n);
+            (left, node)
+        },
+        Some(right) => {
+            let (rest, last) = remove_last(right);
+            n.right = rest;
+            (Some(balance(node)), last)
+        },
+    }
+}
+
+// Joins the trees left and right, in this order.
+fn join2(left: Link, right: Link) -> Link {
+    match left {
+        None => right,
+        Some(left) => {
+            let (rest, last) = remove_last(left);
+            Some(join(rest, last, right))
+        },
+    }
+}
+
+// Splits the tree in the chars before pos and the chars from pos.
+fn split(link: Link, pos: usize) -> (Link, Link) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    let n = Arc::make_mut(&mut node);
+    let left = n.left.take();
+    let right = n.right.take();
+    let left_len = link_len(&left);
+    let chunk_len = n.chunk.len();
+    if pos <= left_len {
+        let (left_left, left_right) = split(left, pos);
+        (left_left, Some(join(left_right, node, right)))
+    } else if pos >= left_len + chunk_len {
+        let (right_left, right_right) = split(right, pos - left_len - chunk_len);
+        (Some(join(left, node, right_left)), right_right)
+    } else {
+        let tail = n.chunk.split_off(pos - left_len);
+        (Some(join(left, node, None)), Some(join(None, new_node(tail), right)))
+    }
+}
+
+// Builds a perfectly balanced tree, with chunks of CHUNK_LEN chars.
+fn build(chars: &[char]) -> Link {
+    if chars.is_empty() {
+        return None;
+    }
+    let num_chunks = chars.len().div_ceil(CHUNK_LEN);
+    let mid_start = (num_chunks / 2) * CHUNK_LEN;
+    let mid_end = (mid_start + CHUNK_LEN).min(chars.len());
+    let mut mid = new_node(chars[mid_start..mid_end].to_vec());
+    let m = Arc::make_mut(&mut mid);
+    m.left = build(&chars[..mid_start]);
+    m.right = build(&chars[mid_end..]);
+    update(m);
+    Some(mid)
+}
+
+// Inserts the chars in the chunk that has pos, if they fit in it.
+fn insert_in_chunk(link: &mut Link, pos: usize, chars: &[char]) -> bool {
+    let Some(node) = link else {
+        return false;
+    };
+    let n = Arc::make_mut(node);
+    let left_len = link_len(&n.left);
+    let done = if pos < left_len {
+        insert_in_chunk(&mut n.left, pos, chars)
+    } else if pos <= left_len + n.chunk.len() {
+        let fits = n.chunk.len() + chars.len() <= MAX_CHUNK_LEN;
+        if fits {
+            let index = pos - left_len;
+            n.chunk.splice(index..index, chars.iter().copied());
+        }
+        fits
+    } else {
+        insert_in_chunk(&mut n.right, pos - left_len - n.chunk.len(), chars)
+    };
+    if done {
+        n.len += chars.len();
+    }
+    done
+}
+
+// Removes the chars start..end from the chunk that has them, if the chunk
+// doesn't get empty.
+fn remove_in_chunk(link: &mut Link, start: usize, end: usize) -> bool {
+    let Some(node) = link else {
+        return false;
+    };
+    let n = Arc::make_mut(node);
+    let left_len = link_len(&n.left);
+    let chunk_len = n.chunk.len();
+    let done = if end <= left_len {
+        remove_in_chunk(&mut n.left, start, end)
+    } else if start >= left_len + chunk_len {
+        remove_in_chunk(&mut n.right, start - left_len - chunk_len, end - left_len - chunk_len)
+    } else if start >= left_len && end <= left_len + chunk_len && end - start < chunk_len {
+        n.chunk.drain((start - left_len)..(end - left_len));
+        true
+    } else {
+        false
+    };
+    if done {
+        n.len -= end - start;
+    }
+    done
+}
+
+
+impl CharRope {
+    pub fn new() -> CharRope {
+        CharRope { root: None }
+    }
+
+    pub fn from_chars(chars: &[char]) -> CharRope {
+        CharRope { root: build(chars) }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        link_len(&self.root)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Returns a None or the char at index, in O(log n).
+    pub fn get(&self, index: usize) -> Option<char> {
+        let mut link = &self.root;
+        let mut index = index;
+        while let Some(node) = link {
+            let left_len = link_len(&node.left);
+            if index < left_len {
+                link = &node.left;
+            } else if index < left_len + node.chunk.len() {
+                return Some(node.chunk[index - left_len]);
+            } else {
+                index -= left_len + node.chunk.len();
+                link = &node.right;
+            }
+        }
+        None
+    }
+
+    /// Iterator over the chunks of chars, from the start.
+    pub fn chunks(&self) -> CharRopeChunks<'_> {
+        self.chunks_at(0)
+    }
+
+    /// Iterator over the chunks of chars, from the char at_pos.
+    pub fn chunks_at(&self, at_pos: usize) -> CharRopeChunks<'_> {
+        let mut chunks = CharRopeChunks { stack: Vec::new(), first: None };
+        let mut link = &self.root;
+        let mut pos = at_pos;
+        while let Some(node) = link {
+            let left_len = link_len(&node.left);
+            if pos < left_len {
+                chunks.stack.push(node);
+                link = &node.left;
+            } else if pos < left_len + node.chunk.len() {
+                chunks.first = Some(&node.chunk[(pos - left_len)..]);
+                chunks.push_left_path(&node.right);
+                break;
+            } else {
+                pos -= left_len + node.chunk.len();
+                link = &node.right;
+            }
+        }
+        chunks
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(|chunk| chunk.iter().copied())
+    }
+
+    pub fn to_vec_chars(&self) -> Vec<char> {
+        let mut vec_chars: Vec<char> = Vec::with_capacity(self.len());
+        for chunk in self.chunks() {
+            vec_chars.extend_from_slice(chunk);
+        }
+        vec_chars
+    }
+
+    pub fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        buf.extend(self.chars());
+        buf
+    }
+
+    /// Returns a new rope with the chars of range, in O(log n).
+    /// Panics if the range is invalid or out of bounds.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> CharRope {
+        let range = self.checked_range(range, "slice");
+        let (_, right) = split(self.root.clone(), range.start);
+        let (middle, _) = split(right, range.end - range.start);
+        CharRope { root: middle }
+    }
+
+    /// Inserts the chars at_pos, in O(log n + chars len).
+    /// Panics if at_pos > len, like `Vec::insert()`.
+    pub fn insert(& mut self, at_pos: usize, chars: &[char]) {
+        if at_pos > self.len() {
+            panic!("Error: In insert() {}.", StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        if chars.is_empty() || insert_in_chunk(&mut self.root, at_pos, chars) {
+            return;
+        }
+        let (left, right) = split(self.root.take(), at_pos);
+        self.root = join2(join2(left, build(chars)), right);
+    }
+
+    /// Removes the chars of range, in O(log n).
+    /// Panics if the range is invalid or out of bounds.
+    pub fn remove(& mut self, range: impl RangeBounds<usize>) {
+        let range = self.checked_range(range, "remove");
+        if range.is_empty() || remove_in_chunk(&mut self.root, range.start, range.end) {
+            return;
+        }
+        let (left, right) = split(self.root.take(), range.start);
+        let (_, right) = split(right, range.end - range.start);
+        self.root = join2(left, right);
+    }
+
+    /// Replaces the chars of range with chars.
+    /// Panics if the range is invalid or out of bounds.
+    pub fn replace_range(& mut self, range: impl RangeBounds<usize>, chars: &[char]) {
+        let range = self.checked_range(range, "replace_range");
+        self.remove(range.clone());
+        self.insert(range.start, chars);
+    }
+
+    /// Appends the chars of other, in O(log n).
+    pub fn append(& mut self, other: &CharRope) {
+        self.root = join2(self.root.take(), other.root.clone());
+    }
+
+    fn checked_range(&self, range: impl RangeBounds<usize>, method_name: &str) -> Range<usize> {
+        match str_utils::check_range(&range, self.len()) {
+            Ok(range) => range,
+            Err(error) => panic!("Error: In {}() {}.", method_name, error),
+        }
+    }
+
+    // Returns the first match of the finder that starts from start_pos up to
+    // max_start.
+    fn find_from(&self, finder: &CharFinder, start_pos: usize, max_start: usize) -> Option<usize> {
+        finder.find_in_chunks(self.chunks_at(start_pos), start_pos, max_start)
+    }
+
+    // The start of the non overlapping matches that are all inside of range.
+    fn find_all_in(&self, pattern: &[char], range: Range<usize>) -> Vec<usize> {
+        let mut indexes_vec: Vec<usize> = Vec::new();
+        if pattern.is_empty() || pattern.len() > range.len() {
+            return indexes_vec;
+        }
+        let finder = CharFinder::new(pattern);
+        let last_start = range.end - pattern.len();
+        let mut start_pos = range.start;
+        while start_pos <= last_start {
+            match self.find_from(&finder, start_pos, last_start) {
+                Some(index) => {
+                    indexes_vec.push(index);
+                    start_pos = index + pattern.len();
+                },
+                None => break,
+            }
+        }
+        indexes_vec
+    }
+
+    // The pieces between the matches, like `split_vec()`, slices of the rope.
+    fn split_pieces(&self, at_pattern: &[char], range: Range<usize>) -> Vec<CharRope> {
+        let indexes_vec = self.find_all_in(at_pattern, range.clone());
+        let mut res_vec: Vec<CharRope> = Vec::new();
+        if indexes_vec.is_empty() {
+            return res_vec;
+        }
+        let mut last_index = range.start;
+        for index in indexes_vec.iter().copied().chain(std::iter::once(range.end)) {
+            if index > last_index {
+                res_vec.push(self.slice(last_index..index));
+            }
+            last_index = index + at_pattern.len();
+        }
+        res_vec
+    }
+
+
+    // The methods of `StringUtilsVecCharsV2`, with the same semantics.
+
+    pub fn join_vec(p_vec_vec_chars: &[&[char]]) -> CharRope {
+        CharRope::from_chars(&p_vec_vec_chars.concat())
+    }
+
+    pub fn join_str(p_vec_str: &[&str]) -> CharRope {
+        let mut rope = CharRope::new();
+        for str_tmp in p_vec_str {
+            rope.push_str(str_tmp);
+        }
+        rope
+    }
+
+    pub fn eq_vec(&self, other: &[char]) -> bool {
+        self.len() == other.len() && self.chars().eq(other.iter().copied())
+    }
+
+    pub fn eq_str(&self, p_str: &str) -> bool {
+        self.chars().eq(p_str.chars())
+    }
+
+    pub fn push_vec(& mut self, p_vec_chars: &[char]) {
+        self.insert(self.len(), p_vec_chars);
+    }
+
+    pub fn push_str(& mut self, p_str: &str) {
+        self.push_vec(&p_str.get_vec_chars());
+    }
+
+    pub fn push_str_start(& mut self, p_str: &str) {
+        self.push_vec_start(&p_str.get_vec_chars());
+    }
+
+    pub fn push_vec_start(& mut self, other_vec: &[char]) {
+        self.insert(0, other_vec);
+    }
+
+    pub fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError> {
+        self.insert_vec(&p_str.get_vec_chars(), at_pos)
+    }
+
+    pub fn insert_vec(& mut self, other_vec: &[char], at_pos: usize) -> Result<(), StringUtilsError> {
+        if at_pos >= self.len() {
+            return Err(StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        self.insert(at_pos, other_vec);
+        Ok(())
+    }
+
+    pub fn trim_start(& mut self) {
+        let count = self.chars().take_while(|c| c.is_whitespace()).count();
+        self.remove(..count);
+    }
+
+    pub fn trim_end(& mut self) {
+        let count = (0..self.len()).rev().take_while(|index| self[*index].is_whitespace()).count();
+        self.remove((self.len() - count)..);
+    }
+
+    pub fn trim(& mut self) {
+        self.trim_end();
+        self.trim_start();
+    }
+
+    /// Panics if start_pos or end_pos are out of bounds or if end_pos < start_pos.
+    pub fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_find_vec(p_vec_chars, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In find_vec() {}.", error),
+        }
+    }
+
+    /// Like `find_vec()` but returns an error instead of panicking.
+    pub fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let Some(end_pos_val) = vec_chars::check_find_args(self.len(), p_vec_chars, start_pos, end_pos)? else {
+            return Ok(None);
+        };
+        Ok(self.find_from(&CharFinder::new(p_vec_chars), start_pos, end_pos_val))
+    }
+
+    pub fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.find_vec(&p_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    pub fn contains_vec(& self, p_vec_chars: &[char]) -> bool {
+        self.find_vec(p_vec_chars, 0, None).is_some()
+    }
+
+    pub fn contains_str(& self, p_str: &str) -> bool {
+        self.contains_vec(&p_str.get_vec_chars())
+    }
+
+    pub fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        pattern_vec_chars.len() <= self.len()
+            && self.chars().zip(pattern_vec_chars.iter()).all(|(c, p)| c == *p)
+    }
+
+    pub fn start_with_str(& self, pattern_str: &str) -> bool {
+        self.start_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    pub fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        pattern_vec_chars.len() <= self.len()
+            && self.chunks_at(self.len() - pattern_vec_chars.len())
+                .flat_map(|chunk| chunk.iter())
+                .eq(pattern_vec_chars.iter())
+    }
+
+    pub fn ends_with_str(& self, pattern_str: &str) -> bool {
+        self.ends_with_vec(&pattern_str.get_vec_chars())
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In replace_vec() {}.", error),
+        }
+    }
+
+    /// Like `replace_vec()` but returns an error instead of panicking.
+    pub fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let res = self.try_find_vec(match_pattern_vec, start_pos, end_pos)?;
+        if let Some(index) = res {
+            self.replace_range(index..(index + match_pattern_vec.len()), replace_pattern_vec);
+        }
+        Ok(res)
+    }
+
+    /// Returns a None or the index of the first replace.
+    pub fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        self.replace_vec(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars(), start_pos, end_pos)
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize> {
+        let indexes_vec = self.find_all_in(match_pattern_vec, 0..self.len());
+        if indexes_vec.is_empty() {
+            return None;
+        }
+        // From the end, so the indexes before the replace don't change.
+        for index in indexes_vec.iter().rev() {
+            self.replace_range(*index..(index + match_pattern_vec.len()), replace_pattern_vec);
+        }
+        Some(indexes_vec.len())
+    }
+
+    /// Returns a None or the number of replaces.
+    pub fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        self.replace_vec_all(&match_pattern_str.get_vec_chars(), &replace_pattern_str.get_vec_chars())
+    }
+
+    /// Like `StringUtilsVecCharsV2::split_vec()`, the pieces are slices of
+    /// the rope.
+    pub fn split_vec(& self, at_pattern_vec: &[char]) -> Vec<CharRope> {
+        self.split_pieces(at_pattern_vec, 0..self.len())
+    }
+
+    pub fn split_str(& self, at_pattern_str: &str) -> Vec<CharRope> {
+        self.split_vec(&at_pattern_str.get_vec_chars())
+    }
+
+    /// Returns a None or the index of the first match inside of range.
+    pub fn find_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        let range = self.checked_range(range, "find_in");
+        if pattern.is_empty() || pattern.len() > range.len() {
+            return None;
+        }
+        let last_start = range.end - pattern.len();
+        self.find_from(&CharFinder::new(pattern), range.start, last_start)
+    }
+
+    /// Returns the number of non overlapping matches inside of range.
+    pub fn count_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> usize {
+        let range = self.checked_range(range, "count_in");
+        self.find_all_in(pattern, range).len()
+    }
+
+    /// Returns a None or the index of the first replace inside of range.
+    pub fn replace_in(& mut self, match_pattern: &[char], replace_pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        let index = self.find_in(match_pattern, range)?;
+        self.replace_range(index..(index + match_pattern.len()), replace_pattern);
+        Some(index)
+    }
+
+    /// Like `split_vec()` but only of the chars inside of range.
+    pub fn split_in(& self, at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<CharRope> {
+        let range = self.checked_range(range, "split_in");
+        self.split_pieces(at_pattern, range)
+    }
+
+    /// Like `StringUtilsVecCharsV2::map_str()`, in O(n).
+    pub fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize> {
+        let mut vec_chars = self.to_vec_chars();
+        let res = vec_chars.map_str(map);
+        *self = CharRope::from_chars(&vec_chars);
+        res
+    }
+
+    /// Like `StringUtilsVecCharsV2::map_str_with()`, in O(n).
+    pub fn map_str_with(& mut self, map: &[(&str, &str)], match_kind: MatchKind) -> HashMap<String, usize> {
+        let mut vec_chars = self.to_vec_chars();
+        let res = vec_chars.map_str_with(map, match_kind);
+        *self = CharRope::from_chars(&vec_chars);
+        res
+    }
+}
+
+
+/// Iterator over the chunks of chars of a `CharRope`, in order.
+pub struct CharRopeChunks<'a> {
+    // The nodes whose chunk and right subtree are still to visit.
+    stack: Vec<&'a Node>,
+    first: Option<&'a [char]>,
+}
+
+impl<'a> CharRopeChunks<'a> {
+    fn push_left_path(&mut self, mut link: &'a Link) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a> Iterator for CharRopeChunks<'a> {
+    type Item = &'a [char];
+
+    fn next(&mut self) -> Option<&'a [char]> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        let node = self.stack.pop()?;
+        self.push_left_path(&node.right);
+        Some(&node.chunk)
+    }
+}
+
+
+// Conversions.
+
+impl From<&str> for CharRope {
+    /// The chars are normalized like in `StringUtils::get_vec_chars()`.
+    fn from(p_str: &str) -> CharRope {
+        CharRope::from_chars(&p_str.get_vec_chars())
+    }
+}
+
+impl From<String> for CharRope {
+    fn from(string: String) -> CharRope {
+        CharRope::from(string.as_str())
+    }
+}
+
+impl From<&[char]> for CharRope {
+    fn from(chars: &[char]) -> CharRope {
+        CharRope::from_chars(chars)
+    }
+}
+
+impl From<Vec<char>> for CharRope {
+    fn from(vec_chars: Vec<char>) -> CharRope {
+        CharRope::from_chars(&vec_chars)
+    }
+}
+
+impl From<&CharRope> for Vec<char> {
+    fn from(rope: &CharRope) -> Vec<char> {
+        rope.to_vec_chars()
+    }
+}
+
+impl From<&CharRope> for String {
+    fn from(rope: &CharRope) -> String {
+        rope.chars().collect()
+    }
+}
+
+impl FromIterator<char> for CharRope {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> CharRope {
+        let vec_chars: Vec<char> = iter.into_iter().collect();
+        CharRope::from_chars(&vec_chars)
+    }
+}
+
+impl Index<usize> for CharRope {
+    type Output = char;
+
+    fn index(&self, index: usize) -> &char {
+        let mut link = &self.root;
+        let mut pos = index;
+        while let Some(node) = link {
+            let left_len = link_len(&node.left);
+            if pos < left_len {
+                link = &node.left;
+            } else if pos < left_len + node.chunk.len() {
+                return &node.chunk[pos - left_len];
+            } else {
+                pos -= left_len + node.chunk.len();
+                link = &node.right;
+            }
+        }
+        panic!("Error: In index() {}.", StringUtilsError::IndexOutOfBounds { index, len: self.len() });
+    }
+}
+
+impl PartialEq for CharRope {
+    fn eq(&self, other: &CharRope) -> bool {
+        self.len() == other.len() && self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for CharRope {}
+
+impl PartialEq<str> for CharRope {
+    fn eq(&self, other: &str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl PartialEq<&str> for CharRope {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq_str(other)
+    }
+}
+
+impl fmt::Display for CharRope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CharRope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        f.write_char('"')?;
+        for c in self.chars() {
+            for c_esc in c.escape_debug() {
+                f.write_char(c_esc)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
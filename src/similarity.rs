@@ -0,0 +1,387 @@
+//! String similarity and distance metrics.
+//!
+//! The functions are generic over the elements of the slices, so on a
+//! `&[char]` they compare chars and, on the clusters of `graphemes()`, they
+//! compare graphemes, where a flag or an emoji with a skin tone is one
+//! element, so "🇵🇹" to "🇧🇷" is 2 edits in chars and 1 edit in graphemes.
+//!
+//! The distances are in edits, the similarities are between 0.0 and 1.0,
+//! with 1.0 for equal sequences. The `_bounded` variants stop as soon as
+//! the distance is known to be greater than max, and return a None.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::mem;
+use std::ops::Range;
+
+use unic_segment::GraphemeIndices;
+
+use crate::error::StringUtilsError;
+
+/// Returns the extended grapheme clusters of the chars, as in UAX #29.
+pub fn graphemes(chars: &[char]) -> Vec<&[char]> {
+    let text: String = chars.iter().collect();
+    let mut res_graphemes = Vec::new();
+    let mut start = 0;
+    for (_, grapheme) in GraphemeIndices::new(&text) {
+        let end = start + grapheme.chars().count();
+        res_graphemes.push(&chars[start..end]);
+        start = end;
+    }
+    res_graphemes
+}
+
+/// The Levenshtein distance, the number of inserts, deletes and
+/// substitutions from a to b.
+pub fn levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    levenshtein_rows(a, b, usize::MAX).unwrap_or(usize::MAX)
+}
+
+/// Like `levenshtein()` but returns a None when the distance is greater
+/// than max.
+pub fn levenshtein_bounded<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    levenshtein_rows(a, b, max)
+}
+
+// The distances grow along any path of the table, so when all of a row is
+// greater than max the distance is too.
+fn levenshtein_rows<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_elem) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        let mut row_min = row[0];
+        for j in 1..=b.len() {
+            let value = (diagonal + usize::from(*a_elem != b[j - 1])).min(row[j] + 1).min(row[j - 1] + 1);
+            diagonal = row[j];
+            row[j] = value;
+            row_min = row_min.min(value);
+        }
+        if row_min > max {
+            return None;
+        }
+    }
+    Some(row[b.len()]).filter(|distance| *distance <= max)
+}
+
+/// The Levenshtein distance divided by the len of the longest, as a
+/// similarity, 1.0 - distance / max_len.
+pub fn normalized_levenshtein<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(a, b) as f64 / max_len as f64
+}
+
+/// The optimal string alignment distance, the Levenshtein distance with the
+/// transposition of two adjacent elements as one edit, but each substring
+/// is edited only once, so "ca" to "abc" is 3 and not 2.
+pub fn osa_distance<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    osa_rows(a, b, usize::MAX).unwrap_or(usize::MAX)
+}
+
+/// Like `osa_distance()` but returns a None when the distance is greater
+/// than max.
+pub fn osa_distance_bounded<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    osa_rows(a, b, max)
+}
+
+// A transposition jumps over one row, so any path passes by one of two
+// consecutive rows.
+fn osa_rows<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    let mut before_previous: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous_min = 0;
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut current_min = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (previous[j - 1] + cost).min(previous[j] + 1).min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(before_previous[j - 2] + 1);
+            }
+            current[j] = value;
+            current_min = current_min.min(value);
+        }
+        if current_min > max && previous_min > max {
+            return None;
+        }
+        previous_min = current_min;
+        mem::swap(&mut before_previous, &mut previous);
+        mem::swap(&mut previous, &mut current);
+    }
+    Some(previous[b.len()]).filter(|distance| *distance <= max)
+}
+
+/// The true Damerau-Levenshtein distance, with the transposition of two
+/// adjacent elements as one edit, even with other edits between them, so
+/// "ca" to "abc" is 2.
+pub fn damerau_levenshtein<T: Eq + Hash>(a: &[T], b: &[T]) -> usize {
+    damerau_rows(a, b, usize::MAX).unwrap_or(usize::MAX)
+}
+
+/// Like `damerau_levenshtein()` but returns a None when the distance is
+/// greater than max.
+pub fn damerau_levenshtein_bounded<T: Eq + Hash>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    damerau_rows(a, b, max)
+}
+
+// A transposition from the row k to the row i costs at least the i - k
+// rows it jumps over, as many as the deletions down to any row between, so
+// no cell is less than the min of a row before it.
+fn damerau_rows<T: Eq + Hash>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    // The table has an extra row and column, with a distance greater than
+    // any other, so the transpositions without a previous match are never
+    // the min.
+    let infinity = a.len() + b.len();
+    let width = b.len() + 2;
+    let mut table = vec![0; (a.len() + 2) * width];
+    table[0] = infinity;
+    for i in 0..=a.len() {
+        table[(i + 1) * width] = infinity;
+        table[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        table[j + 1] = infinity;
+        table[width + j + 1] = j;
+    }
+    // The last row of a where each element was seen.
+    let mut last_row: HashMap<&T, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        // The last column of b that matched a[i - 1] .
+        let mut last_match_col = 0;
+        let mut row_min = i;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+            let value = (table[i * width + j] + cost)
+                .min(table[(i + 1) * width + j] + 1)
+                .min(table[i * width + j + 1] + 1)
+                .min(table[k * width + l] + (i - k - 1) + 1 + (j - l - 1));
+            table[(i + 1) * width + j + 1] = value;
+            row_min = row_min.min(value);
+        }
+        if row_min > max {
+            return None;
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    Some(table[(a.len() + 1) * width + b.len() + 1]).filter(|distance| *distance <= max)
+}
+
+/// The Hamming distance, the number of positions with different elements.
+/// Returns an error if the lens are not equal.
+pub fn hamming<T: PartialEq>(a: &[T], b: &[T]) -> Result<usize, StringUtilsError> {
+    if a.len() != b.len() {
+        return Err(StringUtilsError::UnequalLengths { left: a.len(), right: b.len() });
+    }
+    Ok(a.iter().zip(b).filter(|(a_elem, b_elem)| a_elem != b_elem).count())
+}
+
+/// Like `hamming()` but returns a None when the distance is greater than
+/// max, or the lens are not equal.
+pub fn hamming_bounded<T: PartialEq>(a: &[T], b: &[T], max: usize) -> Option<usize> {
+    if a.len() != b.len() {
+        return None;
+    }
+    let mut distance = 0;
+    for (a_elem, b_elem) in a.iter().zip(b) {
+        if a_elem != b_elem {
+            distance += 1;
+            if distance > max {
+                return None;
+            }
+        }
+    }
+    Some(distance)
+}
+
+/// The Jaro similarity, from the matches inside of a window of half the
+/// longest len and the transpositions between them.
+pub fn jaro<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches: Vec<&T> = Vec::new();
+    for (i, a_elem) in a.iter().enumerate() {
+        let window_range = i.saturating_sub(window)..(i + window + 1).min(b.len());
+        for j in window_range {
+            if !b_matched[j] && *a_elem == b[j] {
+                b_matched[j] = true;
+                a_matches.push(a_elem);
+                break;
+            }
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+    let b_matches = b.iter().zip(&b_matched).filter(|(_, matched)| **matched).map(|(b_elem, _)| b_elem);
+    let half_transpositions = a_matches.iter().zip(b_matches).filter(|(a_elem, b_elem)| **a_elem != *b_elem).count();
+    let matches = a_matches.len() as f64;
+    let transpositions = (half_transpositions / 2) as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions) / matches) / 3.0
+}
+
+/// The Jaro-Winkler similarity, the Jaro similarity with a bonus of 0.1 for
+/// each element of the common prefix, up to 4.
+pub fn jaro_winkler<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    let jaro_similarity = jaro(a, b);
+    let prefix_len = a.iter().zip(b).take(4).take_while(|(a_elem, b_elem)| a_elem == b_elem).count();
+    jaro_similarity + prefix_len as f64 * 0.1 * (1.0 - jaro_similarity)
+}
+
+/// Returns the len of the longest common subsequence, the elements of a
+/// that are in b in the same order, but not always adjacent.
+pub fn longest_common_subsequence_len<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+    let mut row = vec![0; b.len() + 1];
+    for a_elem in a {
+        let mut diagonal = 0;
+        for j in 1..=b.len() {
+            let value = if *a_elem == b[j - 1] { diagonal + 1 } else { row[j].max(row[j - 1]) };
+            diagonal = row[j];
+            row[j] = value;
+        }
+    }
+    row[b.len()]
+}
+
+/// Returns the longest common subsequence, the first one of a when many
+/// have the same len.
+pub fn longest_common_subsequence<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    // The len of the longest common subsequence of a[i..] and b[j..] .
+    let width = b.len() + 1;
+    let mut table = vec![0; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i * width + j] = if a[i] == b[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+    let mut subsequence = Vec::with_capacity(table[0]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            subsequence.push(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    subsequence
+}
+
+/// Returns the ranges in a and in b of the longest common substring, the
+/// first one of a when many have the same len, or empty ranges when there
+/// is none.
+pub fn longest_common_substring<T: PartialEq>(a: &[T], b: &[T]) -> (Range<usize>, Range<usize>) {
+    // The len of the common suffix of a[..i] and b[..j] .
+    let mut previous = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+    let (mut best_len, mut a_end, mut b_end) = (0, 0, 0);
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            current[j] = if a[i - 1] == b[j - 1] { previous[j - 1] + 1 } else { 0 };
+            if current[j] > best_len {
+                best_len = current[j];
+                a_end = i;
+                b_end = j;
+            }
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+    (a_end - best_len..a_end, b_end - best_len..b_end)
+}
+
+/// The Jaccard similarity of the sets of n-grams, the size of the
+/// intersection divided by the size of the union.
+/// Panics if n is 0.
+pub fn jaccard<T: Eq + Hash>(a: &[T], b: &[T], n: usize) -> f64 {
+    let a_set = ngram_set(a, n, "jaccard");
+    let b_set = ngram_set(b, n, "jaccard");
+    if a_set.is_empty() && b_set.is_empty() {
+        return equal_or_zero(a, b);
+    }
+    let intersection = a_set.intersection(&b_set).count();
+    intersection as f64 / (a_set.len() + b_set.len() - intersection) as f64
+}
+
+/// The Sørensen-Dice similarity of the sets of n-grams, twice the size of
+/// the intersection divided by the sum of the sizes.
+/// Panics if n is 0.
+pub fn sorensen_dice<T: Eq + Hash>(a: &[T], b: &[T], n: usize) -> f64 {
+    let a_set = ngram_set(a, n, "sorensen_dice");
+    let b_set = ngram_set(b, n, "sorensen_dice");
+    if a_set.is_empty() && b_set.is_empty() {
+        return equal_or_zero(a, b);
+    }
+    2.0 * a_set.intersection(&b_set).count() as f64 / (a_set.len() + b_set.len()) as f64
+}
+
+/// The cosine similarity of the vectors of the counts of the n-grams.
+/// Panics if n is 0.
+pub fn cosine<T: Eq + Hash>(a: &[T], b: &[T], n: usize) -> f64 {
+    let a_counts = ngram_counts(a, n, "cosine");
+    let b_counts = ngram_counts(b, n, "cosine");
+    if a_counts.is_empty() || b_counts.is_empty() {
+        return if a_counts.is_empty() && b_counts.is_empty() { equal_or_zero(a, b) } else { 0.0 };
+    }
+    let dot: usize = a_counts.iter()
+        .filter_map(|(ngram, a_count)| b_counts.get(ngram).map(|b_count| a_count * b_count))
+        .sum();
+    let norm = |counts: &HashMap<&[T], usize>| counts.values().map(|count| (count * count) as f64).sum::<f64>().sqrt();
+    dot as f64 / (norm(&a_counts) * norm(&b_counts))
+}
+
+// The sequences shorter than n have no n-grams, they are only similar to
+// themselves.
+fn equal_or_zero<T: PartialEq>(a: &[T], b: &[T]) -> f64 {
+    if a == b { 1.0 } else { 0.0 }
+}
+
+fn ngram_set<'a, T: Eq + Hash>(chars: &'a [T], n: usize, method_name: &str) -> HashSet<&'a [T]> {
+    if n == 0 {
+        panic!("Error: In {}() the n of the n-grams is 0.", method_name);
+    }
+    chars.windows(n).collect()
+}
+
+fn ngram_counts<'a, T: Eq + Hash>(chars: &'a [T], n: usize, method_name: &str) -> HashMap<&'a [T], usize> {
+    if n == 0 {
+        panic!("Error: In {}() the n of the n-grams is 0.", method_name);
+    }
+    let mut counts = HashMap::new();
+    for ngram in chars.windows(n) {
+        *counts.entry(ngram).or_insert(0) += 1;
+    }
+    counts
+}
@@ -0,0 +1,106 @@
+//! Helpers on `[char]` slices.
+//!
+//! Conversion of a slice of chars, normally a sub slice of a `Vec<char>`,
+//! into a `String` or into a new `Vec<char>`, Python like indexing and
+//! slicing, the translation with a `TranslationTable` and the trim of the
+//! matches of a pattern.
+
+use std::borrow::Cow;
+
+use crate::char_string::CharStr;
+use crate::match_options;
+use crate::pattern::{self, CharPattern};
+use crate::str_utils;
+use crate::translation::TranslationTable;
+
+pub trait StringUtilsSlices {
+    fn to_string(&self) -> String;
+    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
+    fn to_vec_chars(&self) -> Vec<char>;
+    /// Views the chars as a `CharStr`, for the generic `CharPattern` methods.
+    fn as_char_str(&self) -> &CharStr;
+    /// Like `StringUtils::py_slice()`, borrowed for a step of 1, owned
+    /// otherwise. Panics if step is 0.
+    fn py_slice(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, [char]>;
+    /// Like `StringUtils::py_index()`.
+    fn py_index(&self, index: isize) -> Option<char>;
+    /// Returns the chars translated by the table, in one pass.
+    fn translate(&self, table: &TranslationTable) -> Vec<char>;
+
+    // The trim family, the pattern can be a char, a set like `['x', 'y']`
+    // for `strip("xy")` of Python, a predicate or a sequence of chars.
+
+    /// Returns the sub slice without the repeated matches at the start and
+    /// at the end.
+    fn trim_matches<P: CharPattern>(&self, pattern: P) -> &[char];
+    fn trim_start_matches<P: CharPattern>(&self, pattern: P) -> &[char];
+    fn trim_end_matches<P: CharPattern>(&self, pattern: P) -> &[char];
+
+    /// Removes the accents and the other combining marks of the canonical
+    /// decomposition (NFD), so "noçl" is "nocl". The chars without marks,
+    /// like 'ø', are kept as they are.
+    fn strip_diacritics(&self) -> Vec<char>;
+    /// Like `strip_diacritics()`, with the index in self of each char of
+    /// the result.
+    fn strip_diacritics_indexed(&self) -> (Vec<char>, Vec<usize>);
+}
+
+impl StringUtilsSlices for [char] {
+    fn to_string(&self) -> String {
+        self.iter().collect()
+    }
+
+    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        for c in self.iter() {
+            buf.push(*c);
+        }
+        buf
+    }
+
+    fn to_vec_chars(&self) -> Vec<char> {
+        self.to_vec()
+    }
+
+    fn as_char_str(&self) -> &CharStr {
+        CharStr::from_chars(self)
+    }
+
+    fn py_slice(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, [char]> {
+        if step == 1 {
+            return Cow::Borrowed(&self[str_utils::py_slice_range(self.len(), start, stop)]);
+        }
+        Cow::Owned(str_utils::py_slice_indices(self.len(), start, stop, step).map(|index| self[index]).collect())
+    }
+
+    fn py_index(&self, index: isize) -> Option<char> {
+        str_utils::py_index_position(self.len(), index).map(|index| self[index])
+    }
+
+    fn translate(&self, table: &TranslationTable) -> Vec<char> {
+        table.translate(self)
+    }
+
+    fn trim_matches<P: CharPattern>(&self, pattern: P) -> &[char] {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), self, true, true)]
+    }
+
+    fn trim_start_matches<P: CharPattern>(&self, pattern: P) -> &[char] {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), self, true, false)]
+    }
+
+    fn trim_end_matches<P: CharPattern>(&self, pattern: P) -> &[char] {
+        &self[pattern::trim_range(&mut pattern.into_searcher(), self, false, true)]
+    }
+
+    fn strip_diacritics(&self) -> Vec<char> {
+        self.iter().filter_map(|c| match_options::strip_diacritics_of(*c)).collect()
+    }
+
+    fn strip_diacritics_indexed(&self) -> (Vec<char>, Vec<usize>) {
+        self.iter()
+            .enumerate()
+            .filter_map(|(index, c)| Some((match_options::strip_diacritics_of(*c)?, index)))
+            .unzip()
+    }
+}
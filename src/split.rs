@@ -0,0 +1,250 @@
+//! Lazy iterators of the split family over a `CharStr`.
+//!
+//! They have the same semantics as the split methods of `str` in the Std,
+//! the empty pieces are kept, so "a,,b" split at ',' gives "a", "" and "b",
+//! and a string without the pattern gives the whole string. The adaptor
+//! `skip_empty()` removes the empty pieces, like `split_vec()` does.
+
+use std::ops::Range;
+
+use crate::char_string::CharStr;
+use crate::pattern::{CharMatcherSearcher, CharSearcher};
+
+// The state shared by the split iterators, it returns ranges of chars.
+struct SplitInternal<'a, S: CharSearcher> {
+    haystack: &'a [char],
+    searcher: S,
+    // The remaining chars to split are haystack[start..end] .
+    start: usize,
+    end: usize,
+    allow_trailing_empty: bool,
+    finished: bool,
+}
+
+impl<'a, S: CharSearcher> SplitInternal<'a, S> {
+    fn new(haystack: &'a [char], searcher: S, allow_trailing_empty: bool) -> SplitInternal<'a, S> {
+        SplitInternal {
+            haystack,
+            searcher,
+            start: 0,
+            end: haystack.len(),
+            allow_trailing_empty,
+            finished: false,
+        }
+    }
+
+    fn get_end(&mut self) -> Option<Range<usize>> {
+        if !self.finished {
+            self.finished = true;
+            if self.allow_trailing_empty || self.end > self.start {
+                return Some(self.start..self.end);
+            }
+        }
+        None
+    }
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        if self.finished {
+            return None;
+        }
+        let window = &self.haystack[self.start..self.end];
+        match self.searcher.find_at(window, 0) {
+            Some((match_start, match_end)) => {
+                let piece = self.start..(self.start + match_start);
+                self.start += match_end;
+                Some(piece)
+            },
+            None => self.get_end(),
+        }
+    }
+
+    fn next_inclusive(&mut self) -> Option<Range<usize>> {
+        if self.finished {
+            return None;
+        }
+        let window = &self.haystack[self.start..self.end];
+        match self.searcher.find_at(window, 0) {
+            Some((_, match_end)) => {
+                let piece = self.start..(self.start + match_end);
+                self.start += match_end;
+                Some(piece)
+            },
+            None => self.get_end(),
+        }
+    }
+
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        if self.finished {
+            return None;
+        }
+        if !self.allow_trailing_empty {
+            self.allow_trailing_empty = true;
+            match self.next_back() {
+                Some(piece) if !piece.is_empty() => return Some(piece),
+                _ => {
+                    if self.finished {
+                        return None;
+                    }
+                },
+            }
+        }
+        let window = &self.haystack[self.start..self.end];
+        match self.searcher.rfind_at(window, window.len()) {
+            Some((match_start, match_end)) => {
+                let piece = (self.start + match_end)..self.end;
+                self.end = self.start + match_start;
+                Some(piece)
+            },
+            None => {
+                self.finished = true;
+                Some(self.start..self.end)
+            },
+        }
+    }
+
+    fn piece(&self, range: Range<usize>) -> &'a CharStr {
+        CharStr::from_chars(&self.haystack[range])
+    }
+}
+
+
+/// Iterator of `CharStr::split()` and of `CharStr::split_terminator()` .
+pub struct Split<'a, S: CharSearcher>(SplitInternal<'a, S>);
+
+impl<'a, S: CharSearcher> Split<'a, S> {
+    pub(crate) fn new(haystack: &'a [char], searcher: S, allow_trailing_empty: bool) -> Split<'a, S> {
+        Split(SplitInternal::new(haystack, searcher, allow_trailing_empty))
+    }
+}
+
+impl<'a, S: CharSearcher> Iterator for Split<'a, S> {
+    type Item = &'a CharStr;
+
+    fn next(&mut self) -> Option<&'a CharStr> {
+        let range = self.0.next()?;
+        Some(self.0.piece(range))
+    }
+}
+
+impl<'a, S: CharSearcher> DoubleEndedIterator for Split<'a, S> {
+    fn next_back(&mut self) -> Option<&'a CharStr> {
+        let range = self.0.next_back()?;
+        Some(self.0.piece(range))
+    }
+}
+
+
+/// Iterator of `CharStr::split_inclusive()`, each piece ends with the match.
+pub struct SplitInclusive<'a, S: CharSearcher>(SplitInternal<'a, S>);
+
+impl<'a, S: CharSearcher> SplitInclusive<'a, S> {
+    pub(crate) fn new(haystack: &'a [char], searcher: S) -> SplitInclusive<'a, S> {
+        SplitInclusive(SplitInternal::new(haystack, searcher, false))
+    }
+}
+
+impl<'a, S: CharSearcher> Iterator for SplitInclusive<'a, S> {
+    type Item = &'a CharStr;
+
+    fn next(&mut self) -> Option<&'a CharStr> {
+        let range = self.0.next_inclusive()?;
+        Some(self.0.piece(range))
+    }
+}
+
+
+/// Iterator of `CharStr::splitn()`, the last piece is the rest of the chars.
+pub struct SplitN<'a, S: CharSearcher> {
+    internal: SplitInternal<'a, S>,
+    count: usize,
+}
+
+impl<'a, S: CharSearcher> SplitN<'a, S> {
+    pub(crate) fn new(haystack: &'a [char], searcher: S, count: usize) -> SplitN<'a, S> {
+        SplitN { internal: SplitInternal::new(haystack, searcher, true), count }
+    }
+}
+
+impl<'a, S: CharSearcher> Iterator for SplitN<'a, S> {
+    type Item = &'a CharStr;
+
+    fn next(&mut self) -> Option<&'a CharStr> {
+        let range = match self.count {
+            0 => return None,
+            1 => {
+                self.count = 0;
+                self.internal.get_end()?
+            },
+            _ => {
+                self.count -= 1;
+                self.internal.next()?
+            },
+        };
+        Some(self.internal.piece(range))
+    }
+}
+
+
+/// Iterator of `CharStr::rsplitn()`, the last piece is the rest of the chars.
+pub struct RSplitN<'a, S: CharSearcher> {
+    internal: SplitInternal<'a, S>,
+    count: usize,
+}
+
+impl<'a, S: CharSearcher> RSplitN<'a, S> {
+    pub(crate) fn new(haystack: &'a [char], searcher: S, count: usize) -> RSplitN<'a, S> {
+        RSplitN { internal: SplitInternal::new(haystack, searcher, true), count }
+    }
+}
+
+impl<'a, S: CharSearcher> Iterator for RSplitN<'a, S> {
+    type Item = &'a CharStr;
+
+    fn next(&mut self) -> Option<&'a CharStr> {
+        let range = match self.count {
+            0 => return None,
+            1 => {
+                self.count = 0;
+                self.internal.get_end()?
+            },
+            _ => {
+                self.count -= 1;
+                self.internal.next_back()?
+            },
+        };
+        Some(self.internal.piece(range))
+    }
+}
+
+
+/// Iterator of `CharStr::split_whitespace()` .
+pub type SplitWhitespace<'a> = SkipEmpty<Split<'a, CharMatcherSearcher<fn(char) -> bool>>>;
+
+
+/// Adaptor that removes the empty pieces of a split iterator.
+pub struct SkipEmpty<I> {
+    iter: I,
+}
+
+impl<'a, I: Iterator<Item = &'a CharStr>> Iterator for SkipEmpty<I> {
+    type Item = &'a CharStr;
+
+    fn next(&mut self) -> Option<&'a CharStr> {
+        self.iter.by_ref().find(|piece| !piece.is_empty())
+    }
+}
+
+impl<'a, I: DoubleEndedIterator<Item = &'a CharStr>> DoubleEndedIterator for SkipEmpty<I> {
+    fn next_back(&mut self) -> Option<&'a CharStr> {
+        self.iter.by_ref().rev().find(|piece| !piece.is_empty())
+    }
+}
+
+pub trait SkipEmptyExt<'a>: Iterator<Item = &'a CharStr> + Sized {
+    /// Removes the empty pieces, the behavior of `split_vec()` .
+    fn skip_empty(self) -> SkipEmpty<Self> {
+        SkipEmpty { iter: self }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a CharStr>> SkipEmptyExt<'a> for I {}
@@ -0,0 +1,179 @@
+//! Helpers on `str`.
+//!
+//! Char indexed `substring()` and `slice()` over a `&str` and the conversion
+//! of a `&str` into a normalized `Vec<char>`, and Python like indexing and
+//! slicing with negative indexes and a step.
+
+use std::borrow::Cow;
+use std::ops::{Bound, Range, RangeBounds};
+
+use crate::error::StringUtilsError;
+use crate::normalization::Normalization;
+
+pub trait StringUtils {
+    fn substring(&self, start: usize, len: usize) -> &str;
+    fn slice(&self, range: impl RangeBounds<usize>) -> &str;
+    /// Like `substring()` but returns an error if start + len is after the
+    /// end, instead of returning a shorter substring.
+    fn try_substring(&self, start: usize, len: usize) -> Result<&str, StringUtilsError>;
+    /// Like `slice()` but returns an error instead of panicking for an
+    /// invalid range or instead of a shorter substring for a range that
+    /// ends after the end.
+    fn try_slice(&self, range: impl RangeBounds<usize>) -> Result<&str, StringUtilsError>;
+    /// Normalized with NFC, like all the `*_str` methods of `Vec<char>` and
+    /// `CharStr`.
+    fn get_vec_chars(&self) -> Vec<char>;
+    fn get_vec_chars_with(&self, form: Normalization) -> Vec<char>;
+    /// Like `s[start:stop:step]` in Python, on char positions, with the
+    /// negative positions from the end and the out of range positions
+    /// clamped. Borrowed for a step of 1, owned otherwise.
+    /// Panics if step is 0.
+    fn py_slice(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, str>;
+    /// Like `s[index]` in Python, a negative index is from the end.
+    /// Returns None if out of range.
+    fn py_index(&self, index: isize) -> Option<char>;
+}
+
+impl StringUtils for str {
+    fn substring(&self, start: usize, len: usize) -> &str {
+        let mut char_pos = 0;
+        let mut byte_start = 0;
+        let mut it = self.chars();
+        loop {
+            if char_pos == start { break; }
+            if let Some(c) = it.next() {
+                char_pos += 1;
+                byte_start += c.len_utf8();
+            }
+            else { break; }
+        }
+        char_pos = 0;
+        let mut byte_end = byte_start;
+        loop {
+            if char_pos == len { break; }
+            if let Some(c) = it.next() {
+                char_pos += 1;
+                byte_end += c.len_utf8();
+            }
+            else { break; }
+        }
+        &self[byte_start..byte_end]
+    }
+
+    fn slice(&self, range: impl RangeBounds<usize>) -> &str {
+        let (start, end) = range_start_end(&range, usize::MAX);
+        self.substring(start, end - start)
+    }
+
+    fn try_substring(&self, start: usize, len: usize) -> Result<&str, StringUtilsError> {
+        let chars_len = self.chars().count();
+        match start.checked_add(len) {
+            Some(end) if end <= chars_len => Ok(self.substring(start, len)),
+            Some(end) => Err(StringUtilsError::IndexOutOfBounds { index: end, len: chars_len }),
+            None => Err(StringUtilsError::IndexOutOfBounds { index: usize::MAX, len: chars_len }),
+        }
+    }
+
+    fn try_slice(&self, range: impl RangeBounds<usize>) -> Result<&str, StringUtilsError> {
+        let range = check_range(&range, self.chars().count())?;
+        self.try_substring(range.start, range.len())
+    }
+
+    fn get_vec_chars(&self) -> Vec<char> {
+        self.get_vec_chars_with(Normalization::NFC)
+    }
+
+    fn get_vec_chars_with(&self, form: Normalization) -> Vec<char> {
+        form.chars(self).collect()
+    }
+
+    fn py_slice(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, str> {
+        let chars_len = self.chars().count();
+        if step == 1 {
+            let range = py_slice_range(chars_len, start, stop);
+            return Cow::Borrowed(self.substring(range.start, range.len()));
+        }
+        let chars: Vec<char> = self.chars().collect();
+        Cow::Owned(py_slice_indices(chars_len, start, stop, step).map(|index| chars[index]).collect())
+    }
+
+    fn py_index(&self, index: isize) -> Option<char> {
+        let index = py_index_position(self.chars().count(), index)?;
+        self.chars().nth(index)
+    }
+}
+
+
+/// Converts a range of char positions into the start and the exclusive end,
+/// an unbounded end is len.
+pub(crate) fn range_start_end(range: &impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(bound) => *bound,
+        Bound::Excluded(bound) => bound.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(bound) => bound.saturating_add(1),
+        Bound::Excluded(bound) => *bound,
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
+/// Like `range_start_end()` but checks that the range is valid for len chars.
+pub(crate) fn check_range(range: &impl RangeBounds<usize>, len: usize) -> Result<Range<usize>, StringUtilsError> {
+    let (start, end) = range_start_end(range, len);
+    if end < start {
+        return Err(StringUtilsError::InvalidRange { start, end });
+    }
+    if end > len {
+        return Err(StringUtilsError::IndexOutOfBounds { index: end, len });
+    }
+    Ok(start..end)
+}
+
+// Converts a Python position into a position from the start, clamped
+// between lower and upper like in `slice.indices()` of Python.
+fn py_clamp(len: usize, position: isize, lower: isize, upper: isize) -> isize {
+    if position < 0 {
+        (position + len as isize).max(lower)
+    } else {
+        position.min(upper)
+    }
+}
+
+/// The range of char positions of a Python slice with a step of 1.
+pub(crate) fn py_slice_range(len: usize, start: Option<isize>, stop: Option<isize>) -> Range<usize> {
+    let start = start.map_or(0, |start| py_clamp(len, start, 0, len as isize)) as usize;
+    let stop = stop.map_or(len, |stop| py_clamp(len, stop, 0, len as isize) as usize);
+    start..stop.max(start)
+}
+
+/// The char positions of a Python slice, in the order of the step.
+/// Panics if step is 0, like the ValueError of Python.
+pub(crate) fn py_slice_indices(len: usize, start: Option<isize>, stop: Option<isize>, step: isize) -> impl Iterator<Item = usize> {
+    if step == 0 {
+        panic!("Error: In py_slice() the step can't be 0.");
+    }
+    let len_i = len as isize;
+    // With a negative step the slice goes down to -1, before the first char.
+    let (lower, upper) = if step < 0 { (-1, len_i - 1) } else { (0, len_i) };
+    let (start_default, stop_default) = if step < 0 { (upper, lower) } else { (lower, upper) };
+    let start = start.map_or(start_default, |start| py_clamp(len, start, lower, upper));
+    let stop = stop.map_or(stop_default, |stop| py_clamp(len, stop, lower, upper));
+    // The unsigned step, -isize::MIN would overflow.
+    let count = if step > 0 && stop > start {
+        (stop - start - 1) as usize / step.unsigned_abs() + 1
+    } else if step < 0 && start > stop {
+        (start - stop - 1) as usize / step.unsigned_abs() + 1
+    } else {
+        0
+    };
+    (0..count).map(move |i| (start + i as isize * step) as usize)
+}
+
+/// The char position of a Python index, or None if out of range.
+pub(crate) fn py_index_position(len: usize, index: isize) -> Option<usize> {
+    let index = if index < 0 { index.checked_add(len as isize)? } else { index };
+    if index >= 0 && (index as usize) < len { Some(index as usize) } else { None }
+}
@@ -0,0 +1,173 @@
+//! The `TranslationTable`, like `str.maketrans()` and `str.translate()` of
+//! Python.
+//!
+//! Each char of the table is mapped to a char, to a sequence of chars or is
+//! deleted, and all the chars of a text are translated in one pass, so the
+//! chars produced by a translation are never translated again. The chars
+//! that are not in the table are kept.
+
+use std::collections::HashMap;
+
+use crate::error::StringUtilsError;
+use crate::str_utils::StringUtils;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Translation {
+    Char(char),
+    Chars(Box<[char]>),
+    Delete,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TranslationTable {
+    map: HashMap<char, Translation>,
+    // Number of mappings to more then one char, the only ones that make the
+    // text longer.
+    expanding: usize,
+}
+
+impl TranslationTable {
+    pub fn new() -> TranslationTable {
+        TranslationTable { map: HashMap::new(), expanding: 0 }
+    }
+
+    /// Like `str.maketrans(from, to, delete)` of Python, each char of from is
+    /// mapped to the char at the same position in to, and the chars of
+    /// delete are deleted, even if they are also in from. A repeated char
+    /// in from takes the last mapping. The strings are normalized like in
+    /// `StringUtils::get_vec_chars()`.
+    /// Returns an error if from and to don't have the same number of chars.
+    pub fn maketrans(from: &str, to: &str, delete: &str) -> Result<TranslationTable, StringUtilsError> {
+        let from_chars = from.get_vec_chars();
+        let to_chars = to.get_vec_chars();
+        if from_chars.len() != to_chars.len() {
+            return Err(StringUtilsError::UnequalLengths { left: from_chars.len(), right: to_chars.len() });
+        }
+        let mut table = TranslationTable::new();
+        for (from_c, to_c) in from_chars.into_iter().zip(to_chars) {
+            table.insert_char(from_c, to_c);
+        }
+        for c in delete.get_vec_chars() {
+            table.insert_delete(c);
+        }
+        Ok(table)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Maps from to the char to, replacing the mapping of from.
+    pub fn insert_char(& mut self, from: char, to: char) {
+        self.insert(from, Translation::Char(to));
+    }
+
+    /// Maps from to the chars of to, an empty to deletes from.
+    pub fn insert_vec(& mut self, from: char, to: &[char]) {
+        let translation = match to {
+            [] => Translation::Delete,
+            [to_c] => Translation::Char(*to_c),
+            _ => Translation::Chars(to.into()),
+        };
+        self.insert(from, translation);
+    }
+
+    /// Like `insert_vec()`, to is normalized like in `StringUtils::get_vec_chars()`.
+    pub fn insert_str(& mut self, from: char, to: &str) {
+        self.insert_vec(from, &to.get_vec_chars());
+    }
+
+    /// Deletes from, replacing the mapping of from.
+    pub fn insert_delete(& mut self, from: char) {
+        self.insert(from, Translation::Delete);
+    }
+
+    /// Removes the mapping of from, so it's kept in the translations.
+    /// Returns true if from was in the table.
+    pub fn remove(& mut self, from: char) -> bool {
+        match self.map.remove(&from) {
+            Some(translation) => {
+                if let Translation::Chars(_) = translation {
+                    self.expanding -= 1;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(& mut self, from: char, translation: Translation) {
+        if let Translation::Chars(_) = translation {
+            self.expanding += 1;
+        }
+        if let Some(Translation::Chars(_)) = self.map.insert(from, translation) {
+            self.expanding -= 1;
+        }
+    }
+
+    /// Returns the translation of c, an empty slice if c is deleted, or
+    /// None if c is kept.
+    pub fn get(&self, c: &char) -> Option<&[char]> {
+        match self.map.get(c)? {
+            Translation::Char(to_c) => Some(std::slice::from_ref(to_c)),
+            Translation::Chars(to_chars) => Some(to_chars),
+            Translation::Delete => Some(&[]),
+        }
+    }
+
+    /// Returns the translation of all the chars.
+    pub fn translate(&self, chars: &[char]) -> Vec<char> {
+        let mut res_vec_chars = Vec::with_capacity(chars.len());
+        for c in chars {
+            match self.map.get(c) {
+                None => res_vec_chars.push(*c),
+                Some(Translation::Char(to_c)) => res_vec_chars.push(*to_c),
+                Some(Translation::Chars(to_chars)) => res_vec_chars.extend_from_slice(to_chars),
+                Some(Translation::Delete) => (),
+            }
+        }
+        res_vec_chars
+    }
+
+    /// Translates all the chars of vec_chars in place, without a new
+    /// allocation when no char maps to many chars.
+    pub fn translate_in_place(&self, vec_chars: & mut Vec<char>) {
+        if self.expanding > 0 {
+            *vec_chars = self.translate(vec_chars);
+            return;
+        }
+        let mut write_pos = 0;
+        for read_pos in 0..vec_chars.len() {
+            let c = vec_chars[read_pos];
+            match self.map.get(&c) {
+                None => {
+                    vec_chars[write_pos] = c;
+                    write_pos += 1;
+                }
+                Some(Translation::Char(to_c)) => {
+                    vec_chars[write_pos] = *to_c;
+                    write_pos += 1;
+                }
+                Some(Translation::Chars(_)) => unreachable!(),
+                Some(Translation::Delete) => (),
+            }
+        }
+        vec_chars.truncate(write_pos);
+    }
+}
+
+impl FromIterator<(char, char)> for TranslationTable {
+    fn from_iter<I: IntoIterator<Item = (char, char)>>(iter: I) -> TranslationTable {
+        let mut table = TranslationTable::new();
+        for (from, to) in iter {
+            table.insert_char(from, to);
+        }
+        table
+    }
+}
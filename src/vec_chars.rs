@@ -0,0 +1,763 @@
+//! Helpers on `Vec<char>`.
+//!
+//! The trait `StringUtilsVecChars` converts a `Vec<char>` into a `String` and
+//! the trait `StringUtilsVecCharsV2` adds to `Vec<char>` many of the common
+//! String methods, join, push, insert, trim, find, contains, starts and ends
+//! with, replace, split and map, all of them working in char positions.
+
+use std::borrow::Cow;
+use std::iter;
+use std::mem;
+use std::collections::HashMap;
+use std::ops::{Range, RangeBounds};
+
+use crate::str_utils::{self, StringUtils};
+use crate::finder::CharFinder;
+use crate::aho_corasick::{CharAhoCorasick, MatchKind};
+use crate::error::StringUtilsError;
+use crate::slices::StringUtilsSlices;
+use crate::translation::TranslationTable;
+use crate::pattern::{self, CharPattern};
+use crate::match_options::{self, FoldedChars, MatchOptions};
+use crate::regex::{CharCaptures, CharRegex, CharRegexMatches};
+use crate::glob::CharGlob;
+use crate::fuzzy::{CharFuzzyFinder, FuzzyMatch};
+
+pub trait StringUtilsVecChars {
+    fn to_string(&self) -> String;
+    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
+}
+
+impl StringUtilsVecChars for Vec<char> {
+    fn to_string(&self) -> String { 
+        self.iter().collect()
+    }
+
+    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        for c in self.iter() {
+            buf.push(*c);
+        }
+        buf
+    }
+}
+
+pub trait StringUtilsVecCharsV2 {
+    // fn to_string(&self) -> String;
+    // fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String;
+    
+    fn join_vec(p_vec_vec_chars: &[&[char]]) -> Vec<char>;
+    fn join_str(p_vec_str: &[&str]) -> Vec<char>;
+ 
+    fn eq_vec(&self, other: &[char]) -> bool;
+    fn eq_str(&self, p_str: &str) -> bool;
+    /// Like `eq_str()` with the options, p_str is normalized like in
+    /// `StringUtils::get_vec_chars()`.
+    fn eq_str_with(&self, p_str: &str, options: MatchOptions) -> bool;
+
+    fn push_vec(& mut self, p_vec_chars: &[char]);
+    fn push_str(& mut self, p_str: &str);
+    fn push_str_start(& mut self, p_str: &str);
+    fn push_vec_start(& mut self, other_vec: &[char]);
+    fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError>;
+    fn insert_vec(& mut self, other_vec: &[char], at_pos: usize) -> Result<(), StringUtilsError>;
+
+    fn trim_start(& mut self);
+    fn trim_end(& mut self);
+    fn trim(& mut self);
+    /// Removes the repeated matches at the start and at the end in place,
+    /// with at most one move of the chars. The pattern can be a char, a set
+    /// like `['x', 'y']`, a predicate or a sequence of chars.
+    fn trim_matches<P: CharPattern>(& mut self, pattern: P);
+    fn trim_start_matches<P: CharPattern>(& mut self, pattern: P);
+    fn trim_end_matches<P: CharPattern>(& mut self, pattern: P);
+
+    /// Panics if start_pos or end_pos are out of bounds or if end_pos < start_pos.
+    fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
+    /// Like `find_vec()` but returns an error instead of panicking.
+    fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError>;
+    fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
+
+    fn contains_vec(& self, p_vec_chars: &[char]) -> bool;
+    fn contains_str(& self, p_str: &str) -> bool;
+
+    fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool;
+    fn start_with_str(& self, pattern_str: &str) -> bool;
+    fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool;
+    fn ends_with_str(& self, pattern_str: &str) -> bool;
+
+    /// Returns a None or the index of the first replace.
+    fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
+    /// Like `replace_vec()` but returns an error instead of panicking.
+    fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError>;
+    /// Returns a None or the index of the first replace.
+    fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize>;
+    
+    /// Returns a None or the number of replaces.
+    fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize>;
+    /// Returns a None or the number of replaces.
+    fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize>;
+    
+    fn split_vec(& self, at_pattern: &[char]) -> Vec<&[char]>;        
+    fn split_str(& self, at_pattern_str: &str) -> Vec<&[char]>;
+
+    // The variants with options, that can match without regard to the
+    // case, to the normalization or to the accents, see `MatchOptions`. The
+    // match can have a different len than the pattern, so the positions are
+    // always of self.
+
+    /// Like `find_str()` but returns the range of the match in self.
+    fn find_str_with(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>, options: MatchOptions) -> Option<Range<usize>>;
+    fn contains_str_with(& self, p_str: &str, options: MatchOptions) -> bool;
+    /// Returns a None or the number of replaces.
+    fn replace_str_all_with(& mut self, match_pattern_str: &str, replace_pattern_str: &str, options: MatchOptions) -> Option<usize>;
+    fn split_str_with(& self, at_pattern_str: &str, options: MatchOptions) -> Vec<&[char]>;
+
+    // The regex variants, with a `CharRegex` compiled once for many searches.
+
+    /// Returns a None or the range of the first match that starts at or
+    /// after start_pos. Panics if start_pos is greater then the len.
+    fn find_regex(& self, regex: &CharRegex, start_pos: usize) -> Option<Range<usize>>;
+    /// Iterator over the ranges of the non overlapping matches.
+    fn find_iter_regex<'a>(& 'a self, regex: & 'a CharRegex) -> CharRegexMatches<'a, 'a>;
+    /// Returns a None or the capture groups of the first match.
+    fn captures<'a>(& self, regex: & 'a CharRegex) -> Option<CharCaptures<'a>>;
+    /// Replaces all the matches, with `$1`, `${1}` and `${name}` replaced by
+    /// the chars of the group and `$$` by a `$`.
+    /// Returns a None or the number of replaces.
+    fn replace_regex(& mut self, regex: &CharRegex, replacement: &str) -> Option<usize>;
+    /// Like `split_vec()`, the pieces between the matches that have chars.
+    fn split_regex(& self, regex: &CharRegex) -> Vec<&[char]>;
+
+    // The glob variants, with a `CharGlob` compiled once for many searches.
+
+    /// Returns true if the glob matches all the chars of self.
+    fn matches_glob(& self, glob: &CharGlob) -> bool;
+    fn start_with_glob(& self, glob: &CharGlob) -> bool;
+    fn ends_with_glob(& self, glob: &CharGlob) -> bool;
+    fn contains_glob(& self, glob: &CharGlob) -> bool;
+    /// Returns a None or the range of the first match that starts at or
+    /// after start_pos. Panics if start_pos is greater then the len.
+    fn find_glob(& self, glob: &CharGlob, start_pos: usize) -> Option<Range<usize>>;
+    /// Returns the ranges of the non overlapping matches.
+    fn find_all_glob(& self, glob: &CharGlob) -> Vec<Range<usize>>;
+
+    // The fuzzy variants, the match can have up to max_edits inserted,
+    // deleted or substituted chars, see `CharFuzzyFinder`.
+
+    /// Returns a None or the best match, with the fewest edits, that starts
+    /// between start_pos and end_pos inclusive.
+    fn find_fuzzy(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch>;
+    /// Returns the non overlapping matches that start between start_pos
+    /// and end_pos inclusive.
+    fn find_fuzzy_all(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Vec<FuzzyMatch>;
+    fn find_fuzzy_str(& self, p_str: &str, max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch>;
+
+    // The range variants, search only inside of the range of char
+    // positions, the match has to be all inside of the range, with the
+    // exclusive end like in `&self[range]`, and return positions in self.
+    // They panic if the range is invalid or out of bounds, like `&self[range]`.
+
+    /// Returns a None or the index of the first match inside of range.
+    fn find_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize>;
+    /// Returns the number of non overlapping matches inside of range.
+    fn count_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> usize;
+    /// Returns a None or the index of the first replace inside of range.
+    fn replace_in(& mut self, match_pattern: &[char], replace_pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize>;
+    /// Like `split_vec()` but only of the chars inside of range.
+    fn split_in(& self, at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<&[char]>;
+
+    /// Replaces all the keys of the map by its values, at the same time in
+    /// one pass, with the longest key when many match at the same position.
+    /// Returns the number of replaces for each key.
+    fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize>;
+    /// Like `map_str()` but with the keys in order, for `MatchKind::LeftmostFirst` .
+    fn map_str_with(& mut self, map: &[(&str, &str)], match_kind: MatchKind) -> HashMap<String, usize>;
+    /// Translates each char by the table, in place and in one pass, like
+    /// `str.translate()` of Python.
+    fn translate_in_place(& mut self, table: &TranslationTable);
+    /// Removes the accents in place, like `StringUtilsSlices::strip_diacritics()`.
+    fn strip_diacritics_in_place(& mut self);
+
+    /// Like `StringUtilsSlices::py_slice()`.
+    fn py_slice(& self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, [char]>;
+    /// Like `StringUtilsSlices::py_index()`.
+    fn py_index(& self, index: isize) -> Option<char>;
+}
+
+impl StringUtilsVecCharsV2 for Vec<char> {
+    
+/*
+    fn to_string(&self) -> String { 
+        self.iter().collect()
+    }
+
+    fn to_string_buf<'a>(&self, buf: & 'a mut String) -> & 'a String {
+        buf.clear();
+        for c in self.iter() {
+            buf.push(*c);
+        }
+        buf
+    }
+*/
+
+    fn join_vec(p_vec_vec_chars: &[&[char]]) -> Vec<char> {
+        // Calculate the total length of the strings.
+        let mut capacity = 0_usize;
+        for src_vec_chars_tmp in p_vec_vec_chars {
+            capacity += src_vec_chars_tmp.len();
+        }
+        // Allocate the memory on the heap.
+        let mut vec_chars: Vec<char> = Vec::with_capacity(capacity);
+        for src_vec_chars_tmp in p_vec_vec_chars {
+            vec_chars.push_vec(src_vec_chars_tmp);
+        }
+        vec_chars
+    }
+
+    fn join_str(p_vec_str: &[&str]) -> Vec<char> {
+        // Calculate the total length of the strings.
+        let mut capacity = 0_usize;
+        for str_tmp in p_vec_str {
+            for _ in str_tmp.chars() {
+                capacity += 1;
+            }
+        }
+        // Allocate the memory on the heap.
+        let mut vec_chars: Vec<char> = Vec::with_capacity(capacity);
+        for str_tmp in p_vec_str {
+            vec_chars.push_str(str_tmp);
+        }
+        vec_chars
+    } 
+
+    #[inline]
+    fn eq_vec(&self, other: &[char]) -> bool {
+        self.len() == other.len() && iter::zip(self, other).all(|(a, b)| *a == *b)
+    }
+
+    #[inline]
+    fn eq_str(&self, p_str: &str) -> bool {
+        self.len() == p_str.chars().count() && iter::zip(self, p_str.chars()).all(|(a, b)| *a == b)
+    }
+
+    fn eq_str_with(&self, p_str: &str, options: MatchOptions) -> bool {
+        eq_str_with_chars(self, p_str, options)
+    }
+
+    fn push_vec(& mut self, p_vec_chars: &[char]) {
+        self.extend(p_vec_chars);
+    }
+  
+    fn push_str(& mut self, p_str: &str) {
+        let vec_chars = p_str.get_vec_chars();
+        self.extend(vec_chars);
+    }
+    
+    fn push_str_start(& mut self, p_str: &str) {              
+        let mut vec_chars = p_str.get_vec_chars();
+        vec_chars.extend(self.iter());
+        let _ = mem::replace(self, vec_chars);
+    }
+
+    fn push_vec_start(& mut self, other_vec: &[char]) {
+        let mut vec_tmp = other_vec.to_vec();
+        vec_tmp.extend(self.iter());
+        let _ = mem::replace(self, vec_tmp);
+    }
+
+    fn insert_str(& mut self, p_str: &str, at_pos: usize) -> Result<(), StringUtilsError> {
+        if at_pos >= self.len() {
+            return Err(StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        let vec_t1: Vec<char> = p_str.get_vec_chars();
+        let mut vec_tmp: Vec<char> = Vec::with_capacity(self.len() + vec_t1.len());
+        vec_tmp.extend(self[..at_pos].iter());
+        vec_tmp.extend(vec_t1.iter());
+        vec_tmp.extend(self[at_pos..].iter());
+        let _ = mem::replace(self, vec_tmp);
+        Ok(())
+    }
+
+    fn insert_vec(& mut self, other_vec: &[char], at_pos: usize) -> Result<(), StringUtilsError> {
+        if at_pos >= self.len() {
+            return Err(StringUtilsError::IndexOutOfBounds { index: at_pos, len: self.len() });
+        }
+        let mut vec_tmp: Vec<char> = Vec::with_capacity(self.len() + other_vec.len());
+        vec_tmp.extend(self[..at_pos].iter());
+        vec_tmp.extend(other_vec.iter());
+        vec_tmp.extend(self[at_pos..].iter());
+        let _ = mem::replace(self, vec_tmp);
+        Ok(())
+    }
+
+    fn trim_start(& mut self) {
+        self.trim_start_matches(char::is_whitespace);
+    }
+
+    fn trim_end(& mut self) {
+        self.trim_end_matches(char::is_whitespace);
+    }
+
+    fn trim(& mut self) {
+        self.trim_matches(char::is_whitespace);
+    }
+
+    fn trim_matches<P: CharPattern>(& mut self, pattern: P) {
+        let range = pattern::trim_range(&mut pattern.into_searcher(), self, true, true);
+        keep_range(self, range);
+    }
+
+    fn trim_start_matches<P: CharPattern>(& mut self, pattern: P) {
+        let range = pattern::trim_range(&mut pattern.into_searcher(), self, true, false);
+        keep_range(self, range);
+    }
+
+    fn trim_end_matches<P: CharPattern>(& mut self, pattern: P) {
+        let range = pattern::trim_range(&mut pattern.into_searcher(), self, false, true);
+        keep_range(self, range);
+    }
+
+    fn find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        find_chars(self, p_vec_chars, start_pos, end_pos)
+    }
+
+    fn try_find_vec(& self, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        try_find_chars(self, p_vec_chars, start_pos, end_pos)
+    }
+
+    fn find_str(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let pattern_vec_chars: Vec<char> = p_str.get_vec_chars(); 
+        self.find_vec(&pattern_vec_chars, start_pos, end_pos)
+    }
+
+    fn contains_vec(& self, p_vec_chars: &[char]) -> bool {
+        if self.find_vec(p_vec_chars, 0, None).is_some() {
+            return true;
+        }
+        false
+    }
+
+    fn contains_str(& self, p_str: &str) -> bool {
+        let vec_chars = p_str.get_vec_chars();
+        self.contains_vec(&vec_chars)
+    }
+
+
+
+    fn start_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        if pattern_vec_chars.len() > self.len() {
+            return false;
+        }
+        self.starts_with(pattern_vec_chars)
+    }
+
+
+    fn start_with_str(& self, pattern_str: &str) -> bool {
+        let pattern_vec_chars = pattern_str.get_vec_chars();
+        self.start_with_vec(&pattern_vec_chars)
+    }
+
+    fn ends_with_vec(& self, pattern_vec_chars: &[char]) -> bool {
+        if pattern_vec_chars.len() > self.len() {
+            return false;
+        }
+        self.ends_with(pattern_vec_chars)
+    }
+
+    fn ends_with_str(& self, pattern_str: &str) -> bool {
+        let pattern_vec_chars = pattern_str.get_vec_chars();
+        self.ends_with_vec(&pattern_vec_chars)
+    }
+
+
+
+    /// Returns a None or the index of the first replace.
+    fn replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        match self.try_replace_vec(match_pattern_vec, replace_pattern_vec, start_pos, end_pos) {
+            Ok(res) => res,
+            Err(StringUtilsError::EmptyPattern) => None,
+            Err(error) => panic!("Error: In replace_vec() {}.", error),
+        }
+    }
+
+    fn try_replace_vec(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+        let res = self.try_find_vec(match_pattern_vec, start_pos, end_pos)?;
+        if let Some(index) = res {
+            self.splice(index..(index + match_pattern_vec.len()), replace_pattern_vec.iter().copied());
+        }
+        Ok(res)
+    }
+
+    /// Returns a None or the index of the first replace.
+    fn replace_str(& mut self, match_pattern_str: &str, replace_pattern_str: &str, start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+        let match_pattern_vec = match_pattern_str.get_vec_chars();
+        let replace_pattern_vec = replace_pattern_str.get_vec_chars();
+        self.replace_vec(&match_pattern_vec, &replace_pattern_vec, start_pos, end_pos)
+    }
+
+    /// Returns a None or the number of replaces.
+    fn replace_vec_all(& mut self, match_pattern_vec: &[char], replace_pattern_vec: &[char]) -> Option<usize> {
+        // Find, from start to end, the ranges of the machs. Put's them on a Vec.
+        // The pattern is preprocessed only once for all the searches.
+        let finder = CharFinder::new(match_pattern_vec);
+        let ranges: Vec<Range<usize>> = finder.find_iter(self)
+            .map(|index| index..(index + match_pattern_vec.len()))
+            .collect();
+        replace_ranges(self, &ranges, replace_pattern_vec)
+    }
+
+    /// Returns a None or the number of replaces.
+    fn replace_str_all(& mut self, match_pattern_str: &str, replace_pattern_str: &str) -> Option<usize> {
+        self.replace_vec_all(&match_pattern_str.get_vec_chars(),
+                           &replace_pattern_str.get_vec_chars())
+    }
+
+
+
+    fn split_vec(& self, at_pattern_vec: &[char]) -> Vec<&[char]> {
+        split_chars(self, at_pattern_vec)
+    }
+
+    fn split_str(& self, at_pattern_str: &str) -> Vec<&[char]> {
+        self.split_vec(&at_pattern_str.get_vec_chars())
+    }
+
+    fn find_str_with(& self, p_str: &str, start_pos: usize, end_pos: Option<usize>, options: MatchOptions) -> Option<Range<usize>> {
+        find_str_with_chars(self, p_str, start_pos, end_pos, options)
+    }
+
+    fn contains_str_with(& self, p_str: &str, options: MatchOptions) -> bool {
+        self.find_str_with(p_str, 0, None, options).is_some()
+    }
+
+    /// Returns a None or the number of replaces.
+    fn replace_str_all_with(& mut self, match_pattern_str: &str, replace_pattern_str: &str, options: MatchOptions) -> Option<usize> {
+        let finder = CharFinder::new(&match_options::fold_chars(&match_pattern_str.get_vec_chars(), options));
+        let ranges = FoldedChars::new(self, options).find_all(&finder);
+        replace_ranges(self, &ranges, &replace_pattern_str.get_vec_chars())
+    }
+
+    fn split_str_with(& self, at_pattern_str: &str, options: MatchOptions) -> Vec<&[char]> {
+        split_str_with_chars(self, at_pattern_str, options)
+    }
+
+    fn find_regex(& self, regex: &CharRegex, start_pos: usize) -> Option<Range<usize>> {
+        find_regex_chars(self, regex, start_pos)
+    }
+
+    fn find_iter_regex<'a>(& 'a self, regex: & 'a CharRegex) -> CharRegexMatches<'a, 'a> {
+        regex.find_iter(self)
+    }
+
+    fn captures<'a>(& self, regex: & 'a CharRegex) -> Option<CharCaptures<'a>> {
+        regex.captures(self)
+    }
+
+    fn replace_regex(& mut self, regex: &CharRegex, replacement: &str) -> Option<usize> {
+        let (target_vec_chars, num_matches) = regex.replace_all(self, replacement);
+        if num_matches == 0 {
+            return None;
+        }
+        let _ = mem::replace(self, target_vec_chars);
+        Some(num_matches)
+    }
+
+    fn split_regex(& self, regex: &CharRegex) -> Vec<&[char]> {
+        split_regex_chars(self, regex)
+    }
+
+    fn matches_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches(self)
+    }
+
+    fn start_with_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches_start(self)
+    }
+
+    fn ends_with_glob(& self, glob: &CharGlob) -> bool {
+        glob.matches_end(self)
+    }
+
+    fn contains_glob(& self, glob: &CharGlob) -> bool {
+        glob.find(self).is_some()
+    }
+
+    fn find_glob(& self, glob: &CharGlob, start_pos: usize) -> Option<Range<usize>> {
+        find_glob_chars(self, glob, start_pos)
+    }
+
+    fn find_all_glob(& self, glob: &CharGlob) -> Vec<Range<usize>> {
+        glob.find_all(self)
+    }
+
+    fn find_fuzzy(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        find_fuzzy_chars(self, pattern, max_edits, start_pos, end_pos)
+    }
+
+    fn find_fuzzy_all(& self, pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Vec<FuzzyMatch> {
+        find_fuzzy_all_chars(self, pattern, max_edits, start_pos, end_pos)
+    }
+
+    fn find_fuzzy_str(& self, p_str: &str, max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+        self.find_fuzzy(&p_str.get_vec_chars(), max_edits, start_pos, end_pos)
+    }
+
+    fn find_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        find_in_chars(self, pattern, range)
+    }
+
+    fn count_in(& self, pattern: &[char], range: impl RangeBounds<usize>) -> usize {
+        count_in_chars(self, pattern, range)
+    }
+
+    fn replace_in(& mut self, match_pattern: &[char], replace_pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+        let index = self.find_in(match_pattern, range)?;
+        self.splice(index..(index + match_pattern.len()), replace_pattern.iter().copied());
+        Some(index)
+    }
+
+    fn split_in(& self, at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<&[char]> {
+        split_in_chars(self, at_pattern, range)
+    }
+
+    fn map_str(& mut self, map: & HashMap<&str, &str>) -> HashMap<String, usize> {
+        let map_vec: Vec<(&str, &str)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        self.map_str_with(&map_vec, MatchKind::LeftmostLongest)
+    }
+
+    fn map_str_with(& mut self, map: &[(&str, &str)], match_kind: MatchKind) -> HashMap<String, usize> {
+        let src_strs: Vec<&str> = map.iter().map(|(src_str, _)| *src_str).collect();
+        let target_vecs: Vec<Vec<char>> = map.iter().map(|(_, target_str)| target_str.get_vec_chars()).collect();
+        let target_slices: Vec<&[char]> = target_vecs.iter().map(|t| &t[..]).collect();
+        let automaton = CharAhoCorasick::new_str(&src_strs, match_kind);
+        let (target_vec_chars, counts) = automaton.replace_all(self, &target_slices);
+        let _ = mem::replace(self, target_vec_chars);
+
+        let mut res_hashmap: HashMap<String, usize> = HashMap::new();
+        for (src_str, num_replaces_for_seg_string) in src_strs.iter().zip(counts) {
+            *res_hashmap.entry(src_str.to_string()).or_insert(0) += num_replaces_for_seg_string;
+        }
+        res_hashmap
+    }
+
+    fn translate_in_place(& mut self, table: &TranslationTable) {
+        table.translate_in_place(self);
+    }
+
+    fn strip_diacritics_in_place(& mut self) {
+        // Each char is kept as one char or removed, so it's done in one pass.
+        self.retain_mut(|c| match match_options::strip_diacritics_of(*c) {
+            Some(stripped_c) => {
+                *c = stripped_c;
+                true
+            }
+            None => false,
+        });
+    }
+
+    fn py_slice(& self, start: Option<isize>, stop: Option<isize>, step: isize) -> Cow<'_, [char]> {
+        StringUtilsSlices::py_slice(&self[..], start, stop, step)
+    }
+
+    fn py_index(& self, index: isize) -> Option<char> {
+        StringUtilsSlices::py_index(&self[..], index)
+    }
+
+}
+
+
+// Shared implementations over a slice of chars, used by the Vec<char> traits
+// and by the CharStr type.
+
+pub(crate) fn find_chars(vec_chars: &[char], p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Option<usize> {
+    match try_find_chars(vec_chars, p_vec_chars, start_pos, end_pos) {
+        Ok(res) => res,
+        Err(StringUtilsError::EmptyPattern) => None,
+        Err(error) => panic!("Error: In find_vec() {}.", error),
+    }
+}
+
+pub(crate) fn try_find_chars(vec_chars: &[char], p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+    let Some(end_pos_val) = check_find_args(vec_chars.len(), p_vec_chars, start_pos, end_pos)? else {
+        return Ok(None);
+    };
+    Ok(CharFinder::new(p_vec_chars).find_at(vec_chars, start_pos, Some(end_pos_val)))
+}
+
+// Checks the arguments of the find methods on a string with len chars.
+// Returns the last position where a match can start, or a None when the
+// string is empty.
+pub(crate) fn check_find_args(len: usize, p_vec_chars: &[char], start_pos: usize, end_pos: Option<usize>) -> Result<Option<usize>, StringUtilsError> {
+    if len == 0 {
+        return Ok(None);
+    }
+    if start_pos >= len {
+        return Err(StringUtilsError::IndexOutOfBounds { index: start_pos, len });
+    }
+    let end_pos_val = if let Some(val) = end_pos {
+            if val >= len {
+                return Err(StringUtilsError::IndexOutOfBounds { index: val, len });
+            }
+            if val < start_pos {
+                return Err(StringUtilsError::InvalidRange { start: start_pos, end: val });
+            }
+            val
+        } else {
+            len - 1
+        };
+    if p_vec_chars.is_empty() {
+        return Err(StringUtilsError::EmptyPattern);
+    }
+    Ok(Some(end_pos_val))
+}
+
+fn checked_range(vec_chars: &[char], range: impl RangeBounds<usize>, method_name: &str) -> Range<usize> {
+    match str_utils::check_range(&range, vec_chars.len()) {
+        Ok(range) => range,
+        Err(error) => panic!("Error: In {}() {}.", method_name, error),
+    }
+}
+
+pub(crate) fn find_in_chars(vec_chars: &[char], pattern: &[char], range: impl RangeBounds<usize>) -> Option<usize> {
+    let range = checked_range(vec_chars, range, "find_in");
+    CharFinder::new(pattern).find(&vec_chars[range.clone()]).map(|index| index + range.start)
+}
+
+pub(crate) fn count_in_chars(vec_chars: &[char], pattern: &[char], range: impl RangeBounds<usize>) -> usize {
+    let range = checked_range(vec_chars, range, "count_in");
+    CharFinder::new(pattern).find_iter(&vec_chars[range]).count()
+}
+
+pub(crate) fn split_in_chars<'a>(vec_chars: &'a [char], at_pattern: &[char], range: impl RangeBounds<usize>) -> Vec<&'a [char]> {
+    let range = checked_range(vec_chars, range, "split_in");
+    split_chars(&vec_chars[range], at_pattern)
+}
+
+pub(crate) fn split_chars<'a>(vec_chars: &'a [char], at_pattern_vec: &[char]) -> Vec<&'a [char]> {
+    // Find, from start to end, the ranges of the machs. Put's them on a Vec.
+    let finder = CharFinder::new(at_pattern_vec);
+    let ranges: Vec<Range<usize>> = finder.find_iter(vec_chars)
+        .map(|index| index..(index + at_pattern_vec.len()))
+        .collect();
+    split_at_ranges(vec_chars, &ranges)
+}
+
+pub(crate) fn eq_str_with_chars(vec_chars: &[char], p_str: &str, options: MatchOptions) -> bool {
+    match_options::fold_chars(vec_chars, options) == match_options::fold_chars(&p_str.get_vec_chars(), options)
+}
+
+pub(crate) fn find_str_with_chars(vec_chars: &[char], p_str: &str, start_pos: usize, end_pos: Option<usize>, options: MatchOptions) -> Option<Range<usize>> {
+    let pattern_vec_chars = p_str.get_vec_chars();
+    let end_pos_val = match check_find_args(vec_chars.len(), &pattern_vec_chars, start_pos, end_pos) {
+        Ok(Some(end_pos_val)) => end_pos_val,
+        Ok(None) | Err(StringUtilsError::EmptyPattern) => return None,
+        Err(error) => panic!("Error: In find_str_with() {}.", error),
+    };
+    let finder = CharFinder::new(&match_options::fold_chars(&pattern_vec_chars, options));
+    FoldedChars::new(vec_chars, options).find_at(&finder, start_pos, end_pos_val)
+}
+
+pub(crate) fn split_str_with_chars<'a>(vec_chars: &'a [char], at_pattern_str: &str, options: MatchOptions) -> Vec<&'a [char]> {
+    let finder = CharFinder::new(&match_options::fold_chars(&at_pattern_str.get_vec_chars(), options));
+    let ranges = FoldedChars::new(vec_chars, options).find_all(&finder);
+    split_at_ranges(vec_chars, &ranges)
+}
+
+pub(crate) fn find_regex_chars(vec_chars: &[char], regex: &CharRegex, start_pos: usize) -> Option<Range<usize>> {
+    if start_pos > vec_chars.len() {
+        panic!("Error: In find_regex() {}.", StringUtilsError::IndexOutOfBounds { index: start_pos, len: vec_chars.len() });
+    }
+    regex.find_at(vec_chars, start_pos)
+}
+
+pub(crate) fn split_regex_chars<'a>(vec_chars: &'a [char], regex: &CharRegex) -> Vec<&'a [char]> {
+    let ranges: Vec<Range<usize>> = regex.find_iter(vec_chars).collect();
+    split_at_ranges(vec_chars, &ranges)
+}
+
+pub(crate) fn find_glob_chars(vec_chars: &[char], glob: &CharGlob, start_pos: usize) -> Option<Range<usize>> {
+    if start_pos > vec_chars.len() {
+        panic!("Error: In find_glob() {}.", StringUtilsError::IndexOutOfBounds { index: start_pos, len: vec_chars.len() });
+    }
+    glob.find_at(vec_chars, start_pos)
+}
+
+pub(crate) fn find_fuzzy_chars(vec_chars: &[char], pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Option<FuzzyMatch> {
+    let end_pos_val = match check_find_args(vec_chars.len(), pattern, start_pos, end_pos) {
+        Ok(Some(end_pos_val)) => end_pos_val,
+        Ok(None) | Err(StringUtilsError::EmptyPattern) => return None,
+        Err(error) => panic!("Error: In find_fuzzy() {}.", error),
+    };
+    CharFuzzyFinder::new(pattern, max_edits).find_at(vec_chars, start_pos, Some(end_pos_val))
+}
+
+pub(crate) fn find_fuzzy_all_chars(vec_chars: &[char], pattern: &[char], max_edits: usize, start_pos: usize, end_pos: Option<usize>) -> Vec<FuzzyMatch> {
+    let end_pos_val = match check_find_args(vec_chars.len(), pattern, start_pos, end_pos) {
+        Ok(Some(end_pos_val)) => end_pos_val,
+        Ok(None) | Err(StringUtilsError::EmptyPattern) => return Vec::new(),
+        Err(error) => panic!("Error: In find_fuzzy_all() {}.", error),
+    };
+    CharFuzzyFinder::new(pattern, max_edits).find_all_at(vec_chars, start_pos, Some(end_pos_val))
+}
+
+// Splits at the non overlapping ranges, in order, and keeps only the pieces
+// that have chars. Returns an empty Vec if there are no ranges.
+fn split_at_ranges<'a>(vec_chars: &'a [char], ranges: &[Range<usize>]) -> Vec<&'a [char]> {
+    let mut res_vec: Vec<&'a [char]> = Vec::new();
+    // Case where it didn't found any match, it will exit earlier.
+    if ranges.is_empty() {
+        return res_vec;
+    }
+
+    // Join the intervals between splits that have chars, that are not the split chars.
+    let mut last_index = 0_usize;
+    for range in ranges {
+        let slice_tmp = &vec_chars[last_index..range.start];
+        if !slice_tmp.is_empty() {
+            res_vec.push(slice_tmp);
+        }
+        last_index = range.end;
+    }
+    let slice_tmp = &vec_chars[last_index..];
+    if !slice_tmp.is_empty() {
+        res_vec.push(slice_tmp);
+    }
+
+    res_vec
+}
+
+// Replaces the non overlapping ranges, in order, by replace_pattern_vec.
+// Returns a None or the number of replaces.
+fn replace_ranges(vec_chars: & mut Vec<char>, ranges: &[Range<usize>], replace_pattern_vec: &[char]) -> Option<usize> {
+    // Case where it didn't found any match, it will exit earlier.
+    if ranges.is_empty() {
+        return None;
+    }
+
+    // We will copy to a new Vec<char> the data and do the replacement when coping.
+    let num_matches = ranges.len();
+    let matched_len: usize = ranges.iter().map(|range| range.len()).sum();
+    let capacity = vec_chars.len() - matched_len + num_matches * replace_pattern_vec.len();
+    let mut target_vec_chars: Vec<char> = Vec::with_capacity(capacity);
+    let mut last_index = 0_usize;
+    for range in ranges {
+        target_vec_chars.extend_from_slice(&vec_chars[last_index..range.start]);
+        target_vec_chars.extend_from_slice(replace_pattern_vec);
+        last_index = range.end;
+    }
+    target_vec_chars.extend_from_slice(&vec_chars[last_index..]);
+
+    // Copia a zona de memoria do src para o target self.
+    let _ = mem::replace(vec_chars, target_vec_chars);
+
+    Some(num_matches)
+}
+
+// Keeps only the chars of range, moved to the start in one copy.
+fn keep_range(vec_chars: & mut Vec<char>, range: Range<usize>) {
+    if range.start > 0 {
+        vec_chars.copy_within(range.clone(), 0);
+    }
+    vec_chars.truncate(range.len());
+}